@@ -0,0 +1,37 @@
+//! Compares `HyperGroupoid::is_associative` with Light's test `HyperGroupoid::is_associative_light` on cyclic groups,
+//! where the Light generating set is a single element, on `a,b -> {a,b}`, where it is the whole set, and on a
+//! non-associative table.
+//!
+//! Run with `cargo run --release --example bench_associativity`.
+use std::{hint::black_box, time::{Duration, Instant}};
+use nalgebra::DMatrix;
+use hyperstruc::hs::HyperGroupoid;
+
+fn time<T>(repetitions:u32,f:impl Fn()->T)->Duration{
+    let start = Instant::now();
+    for _ in 0..repetitions {
+        black_box(f());
+    }
+    start.elapsed()/repetitions
+}
+fn main(){
+    let repetitions = 20;
+    println!("{:>2} {:>14} {:>14} {:>14} {:>14} {:>14} {:>14}","n","Z_n naive","Z_n light","{a,b} naive","{a,b} light","non-assoc naive","non-assoc light");
+    for n in [4usize,8,12,16,24,32,48,64] {
+        // new_from_function goes through tags, which are limited to 10 elements
+        let from_fn = |f:fn(usize,usize,usize)->u64|HyperGroupoid::new_from_matrix(&DMatrix::from_fn(n, n, |a,b|f(a,b,n)));
+        let cyclic = from_fn(|a,b,n|1<<((a+b)%n));
+        let total = from_fn(|a,b,_|1<<a|1<<b);
+        // x,y -> {x+1}: (xy)z = {x+2} but x(yz) = {x+1}
+        let non_associative = from_fn(|a,_,n|1<<((a+1)%n));
+        assert!(cyclic.is_associative_light()&&total.is_associative_light()&&!non_associative.is_associative_light());
+        println!("{:>2} {:>14?} {:>14?} {:>14?} {:>14?} {:>14?} {:>14?}",
+            n,
+            time(repetitions, ||cyclic.is_associative()),
+            time(repetitions, ||cyclic.is_associative_light()),
+            time(repetitions, ||total.is_associative()),
+            time(repetitions, ||total.is_associative_light()),
+            time(repetitions, ||non_associative.is_associative()),
+            time(repetitions, ||non_associative.is_associative_light()));
+    }
+}
@@ -0,0 +1,43 @@
+//! Compares the products computed through `support()`, as `mul_by_representation` used to do,
+//! with `HyperGroupoid::mul_by_representation` and with a precomputed `ProductTable`.
+//!
+//! Run with `cargo run --release --example bench_product_table`.
+use std::{hint::black_box, time::{Duration, Instant}};
+use itertools::Itertools;
+use hyperstruc::{hs::HyperGroupoid, utilities::support};
+
+fn mul_by_support(hs:&HyperGroupoid,subset_a:&u64,subset_b:&u64)->u64{
+    support(subset_a, &(hs.n)).iter()
+        .cartesian_product(support(subset_b, &(hs.n)))
+        .fold(0u64, |acc,(x,y)|acc|hs.hyper_composition[(*x ,y)])
+}
+fn is_associative_by_support(hs:&HyperGroupoid)->bool{
+    let singletons = hs.get_singleton();
+    singletons.iter().all(|a|singletons.iter().all(|b|singletons.iter().all(|c|
+        mul_by_support(hs, &mul_by_support(hs, a, b), c)==mul_by_support(hs, a, &mul_by_support(hs, b, c)))))
+}
+fn time<T>(repetitions:u32,f:impl Fn()->T)->Duration{
+    let start = Instant::now();
+    for _ in 0..repetitions {
+        black_box(f());
+    }
+    start.elapsed()/repetitions
+}
+fn main(){
+    let repetitions = 200;
+    println!("{:>2} {:>14} {:>14} {:>14} {:>14} {:>14}","n","support","is_associative","table build","table","products");
+    for n in 3u64..=10 {
+        // a hypergroup, so that associativity is checked on every triple
+        let hs = HyperGroupoid::new_from_function(|a,b|1<<a|1<<b, &n);
+        let table = hs.product_table();
+        let subsets = (1u64..1<<n).step_by(((1usize<<n)/64).max(1)).collect_vec();
+        println!("{:>2} {:>14?} {:>14?} {:>14?} {:>14?} {:>14?}",
+            n,
+            time(repetitions, ||is_associative_by_support(&hs)),
+            time(repetitions, ||hs.is_associative()),
+            time(repetitions, ||hs.product_table()),
+            time(repetitions, ||table.is_associative()),
+            time(repetitions, ||subsets.iter().cartesian_product(subsets.iter())
+                .fold(0, |acc,(a,b)|acc^table.mul_by_representation(a, b))));
+    }
+}
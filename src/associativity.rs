@@ -0,0 +1,269 @@
+//!
+//! Faster associativity tests.
+//!
+//! # Light's test
+//! Light's test checks associativity of a magma only on triples `(x,s,y)` where `s` runs over a generating set. It adapts
+//! to hypergroupoids as follows. Let `S` be a set of elements such that every singleton `{h}` is a finite hyperproduct of
+//! elements of `S`. If `(xs)y = x(sy)` for all `x,y` in H and `s` in `S`, then the family of subsets `C` with `(xC)y = x(Cy)`
+//! for all `x,y` contains `{s}` for all `s` in `S` and is closed under products, so it contains every singleton and H is associative.
+//! Such an `S` is called a Light generating set. The test checks `|S|n^2` triples instead of `n^3`, so it is faster than
+//! [`HyperGroupoid::is_associative`] when `S` is small, e.g., for cyclic groups, and slower when `S=H`, which is always
+//! a Light generating set. See `examples/bench_associativity.rs`.
+//!
+//! # Bit-sliced batch test
+//! A [`BitSlicedTables`] stores up to 64 hypercomposition tables of the same order, transposed: the word of index
+//! `(a*n+b)*n+k` has its `j`-th bit equal to 1 if `k` belongs to `ab` in the `j`-th table. Products and comparisons are then
+//! computed for the 64 tables at once with word operations.
+//!
+use itertools::Itertools;
+use rayon::prelude::*;
+use crate::{hs::HyperGroupoid, product_table::{elements_of, ProductTable}, small_hs::small_hypergroupoid_dispatch, utilities::U1024};
+
+impl HyperGroupoid {
+/// Returns a Light generating set of H, i.e., a set `S` of elements such that every singleton is a finite
+/// hyperproduct of elements of `S`. Elements are returned as singletons. Only products of two elements that are
+/// singletons are followed, so that the set is computed from the `n^2` products of elements. It is built greedily and
+/// then made minimal, i.e., no proper subset of it generates all singletons in this way.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// let cardinality = 7u64;
+/// // The cyclic group Z_7, generated by 1.
+/// let z7 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality);
+/// assert_eq!(z7.light_generating_set(),vec![2]);
+/// assert!(z7.is_associative_light());
+/// // In a,b -> {a,b} singletons are only products of themselves.
+/// let hs = HyperGroupoid::new_from_function(|a,b|1<<a|1<<b, &cardinality);
+/// assert_eq!(hs.light_generating_set(),hs.get_singleton());
+/// assert!(hs.is_associative_light());
+/// ```
+pub fn light_generating_set(&self)->Vec<u64>{
+    light_generators(&ProductTable::new_without_subset_table(self)).into_iter().map(|s|1<<s).collect()
+}
+/// Return true if hyperstructure is associative, using Light's test on [`HyperGroupoid::light_generating_set`].
+/// It agrees with [`HyperGroupoid::is_associative`]. Triples `(x,0,y)` are checked before the generating set is
+/// computed, so that most non-associative hyperstructures are rejected after `n^2` triples.
+pub fn is_associative_light(&self)->bool{
+    let table = ProductTable::new_without_subset_table(self);
+    let n = self.n as usize;
+    let holds_on = |s:usize|(0..n).all(|x|(0..n).all(|y|
+        table.mul_by_representation(&table.entry(x, s), &(1<<y))==table.mul_by_representation(&(1<<x), &table.entry(s, y))));
+    n==0||holds_on(0)&&light_generators(&table).into_iter().filter(|s|*s!=0).all(holds_on)
+}
+}
+/// The elements reached from `generators` by taking products of two reached elements that are singletons.
+fn singleton_closure(table:&ProductTable,generators:&[usize])->u64{
+    let mut reached = generators.iter().fold(0u64, |acc,g|acc|1<<g);
+    let mut pending = elements_of(reached).collect_vec();
+    while let Some(x) = pending.pop() {
+        for y in elements_of(reached) {
+            for z in [table.entry(x, y),table.entry(y, x)] {
+                if z.is_power_of_two()&&z&reached==0 {
+                    reached|=z;
+                    pending.push(z.trailing_zeros() as usize);
+                }
+            }
+        }
+    }
+    reached
+}
+fn light_generators(table:&ProductTable)->Vec<usize>{
+    let n = table.n as usize;
+    let all = if n==64 {u64::MAX} else {(1u64<<n)-1};
+    let mut generators:Vec<usize> = Vec::new();
+    let mut reached = 0u64;
+    for x in 0..n {
+        if reached&(1<<x)==0 {
+            generators.push(x);
+            reached = singleton_closure(table, &generators);
+        }
+    }
+    let mut i = 0;
+    while i<generators.len() {
+        let mut others = generators.clone();
+        others.remove(i);
+        if !others.is_empty()&&singleton_closure(table, &others)==all {
+            generators = others;
+        } else {
+            i+=1;
+        }
+    }
+    generators
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitSlicedTables{
+    pub n:u64,
+    /// Number of tables, at most 64.
+    pub len:usize,
+    /// Lanes whose tag is longer than `n^3` bits.
+    oversized:u64,
+    words:Vec<u64>,
+}
+impl BitSlicedTables {
+    pub const WIDTH:usize = 64;
+fn empty(len:usize,cardinality:&u64)->Self{
+    assert!(len<=BitSlicedTables::WIDTH,"At most {} tables can be checked at once, found {}",BitSlicedTables::WIDTH,len);
+    assert!(*cardinality<=10,"Tags of hypergroupoids of order {} do not fit in U1024",cardinality);
+    BitSlicedTables { n: *cardinality, len, oversized: 0, words: vec![0u64;cardinality.pow(3) as usize] }
+}
+fn set_tag_bit(&mut self,lane:usize,position:usize){
+    let n = self.n as usize;
+    if position>=n.pow(3) {
+        self.oversized|=1<<lane;
+        return;
+    }
+    //bit `position` of the tag is the element k of the entry i, the first entry being the most significant one
+    let (i,k) = (n*n-1-position/n,position%n);
+    self.words[i*n+k]|=1<<lane;
+}
+/// Transposes up to 64 tags, read as in [`HyperGroupoid::new_from_tag_u1024`]. Tags need not represent hypergroupoids,
+/// see [`BitSlicedTables::valid_mask`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::associativity::BitSlicedTables;
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::utilities::U1024;
+///
+/// let cardinality = 3u64;
+/// let tags:Vec<U1024> = (0..64).map(|_|HyperGroupoid::new_random_from_cardinality(&cardinality).get_integer_tag_u1024()).collect();
+/// let tables = BitSlicedTables::from_tags_u1024(&tags, &cardinality);
+/// let mask = tables.hypergroup_mask();
+/// for (lane,tag) in tags.iter().enumerate() {
+///     assert_eq!((mask>>lane)&1==1,HyperGroupoid::new_from_tag_u1024(tag, &cardinality).is_hypergroup());
+/// }
+/// ```
+pub fn from_tags_u1024(tags:&[U1024],cardinality:&u64)->Self{
+    let mut tables = BitSlicedTables::empty(tags.len(), cardinality);
+    for (lane,tag) in tags.iter().enumerate() {
+        for (w,word) in tag.0.iter().enumerate() {
+            let mut rest = *word;
+            while rest!=0 {
+                tables.set_tag_bit(lane, 64*w+rest.trailing_zeros() as usize);
+                rest&=rest-1;
+            }
+        }
+    }
+    tables
+}
+pub fn from_tags_u128(tags:&[u128],cardinality:&u64)->Self{
+    let mut tables = BitSlicedTables::empty(tags.len(), cardinality);
+    for (lane,tag) in tags.iter().enumerate() {
+        let mut rest = *tag;
+        while rest!=0 {
+            tables.set_tag_bit(lane, rest.trailing_zeros() as usize);
+            rest&=rest-1;
+        }
+    }
+    tables
+}
+pub fn from_hypergroupoids(hypergroupoids:&[HyperGroupoid],cardinality:&u64)->Self{
+    let mut tables = BitSlicedTables::empty(hypergroupoids.len(), cardinality);
+    let n = *cardinality as usize;
+    for (lane,hs) in hypergroupoids.iter().enumerate() {
+        assert_eq!(hs.n,*cardinality,"All hypergroupoids must have order {}",cardinality);
+        for (a,b,k) in (0..n).flat_map(|a|(0..n).flat_map(move |b|(0..n).map(move |k|(a,b,k)))) {
+            if (hs.hyper_composition[(a,b)]>>k)&1==1 {
+                tables.words[(a*n+b)*n+k]|=1<<lane;
+            }
+        }
+    }
+    tables
+}
+fn lanes(&self)->u64{
+    if self.len==64 {u64::MAX} else {(1<<self.len)-1}
+}
+fn word(&self,a:usize,b:usize,k:usize)->u64{
+    let n = self.n as usize;
+    self.words[(a*n+b)*n+k]
+}
+/// Lanes representing a hypergroupoid, i.e., with a tag of at most `n^3` bits and no empty entry.
+pub fn valid_mask(&self)->u64{
+    let n = self.n as usize;
+    let mut mask = self.lanes()&!self.oversized;
+    for cell in 0..n*n {
+        mask&=(0..n).fold(0, |acc,k|acc|self.words[cell*n+k]);
+    }
+    mask
+}
+/// Lanes whose table is reproductive, i.e., `xH = H = Hx` for all `x`.
+pub fn reproductive_mask(&self)->u64{
+    let n = self.n as usize;
+    let mut mask = self.lanes();
+    for (x,k) in (0..n).flat_map(|x|(0..n).map(move |k|(x,k))) {
+        mask&=(0..n).fold(0, |acc,y|acc|self.word(x, y, k));
+        mask&=(0..n).fold(0, |acc,y|acc|self.word(y, x, k));
+        if mask==0 {break;}
+    }
+    mask
+}
+/// Lanes among `lanes` whose table is associative. Checking stops as soon as no lane is left.
+fn associative_among(&self,lanes:u64)->u64{
+    let n = self.n as usize;
+    let mut mask = lanes;
+    for (a,b,c) in (0..n).flat_map(|a|(0..n).flat_map(move |b|(0..n).map(move |c|(a,b,c)))) {
+        for k in 0..n {
+            // k in (ab)c iff k in ec for some e in ab, and k in a(bc) iff k in ae for some e in bc
+            let left = (0..n).fold(0, |acc,e|acc|(self.word(a, b, e)&self.word(e, c, k)));
+            let right = (0..n).fold(0, |acc,e|acc|(self.word(b, c, e)&self.word(a, e, k)));
+            mask&=!(left^right);
+        }
+        if mask==0 {break;}
+    }
+    mask
+}
+/// Lanes whose table is associative.
+pub fn associative_mask(&self)->u64{
+    self.associative_among(self.lanes())
+}
+/// Lanes representing a hypergroup.
+pub fn hypergroup_mask(&self)->u64{
+    self.associative_among(self.valid_mask()&self.reproductive_mask())
+}
+}
+/// Returns the tags representing hypergroups, checking them 64 at a time with [`BitSlicedTables`]. The order of tags is preserved.
+///
+/// # Example
+/// ```
+/// use hyperstruc::associativity::filter_hypergroups_u1024;
+/// use hyperstruc::hs::{circumference_radius_d, HyperGroupoid};
+///
+/// let cardinality = 4u64;
+/// let tag = HyperGroupoid::new_from_function(|a,b|1<<a|1<<b, &cardinality).get_integer_tag_u1024();
+/// let neighbours = circumference_radius_d(&tag, &2, &cardinality);
+/// let hypergroups = filter_hypergroups_u1024(&neighbours, &cardinality);
+/// assert!(hypergroups.iter().all(|x|HyperGroupoid::new_from_tag_u1024(x, &cardinality).is_hypergroup()));
+/// ```
+pub fn filter_hypergroups_u1024(tags:&[U1024],cardinality:&u64)->Vec<U1024>{
+    tags.par_chunks(BitSlicedTables::WIDTH)
+        .flat_map_iter(|chunk|{
+            let mask = BitSlicedTables::from_tags_u1024(chunk, cardinality).hypergroup_mask();
+            chunk.iter().enumerate().filter(move |(lane,_)|(mask>>lane)&1==1).map(|(_,tag)|*tag)
+        })
+        .collect()
+}
+/// Returns the tags in `min..=max` representing hypergroups. Tags are first filtered one by one, keeping those representing
+/// reproductive hypergroupoids, and the survivors are checked for associativity 64 at a time with [`BitSlicedTables`].
+pub fn filter_hypergroups_in_range_u128(min:u128,max:u128,cardinality:&u64)->Vec<u128>{
+    let block_size = 1u128<<12;
+    (min/block_size..=max/block_size).into_par_iter()
+        .flat_map_iter(|block|{
+            let candidates:Vec<u128> = (block*block_size..(block+1)*block_size)
+                .filter(|tag|(min..=max).contains(tag))
+                .filter(|tag|
+                    small_hypergroupoid_dispatch!(*cardinality, try_new_from_tag_u128(tag),
+                        |hs|hs.is_reproductive(),
+                        HyperGroupoid::try_new_from_tag_u128(tag, cardinality).map(|hs|hs.is_reproductive()))
+                    .unwrap_or(false))
+                .collect();
+            candidates.chunks(BitSlicedTables::WIDTH)
+                .flat_map(|chunk|{
+                    let mask = BitSlicedTables::from_tags_u128(chunk, cardinality).associative_mask();
+                    chunk.iter().enumerate().filter(move |(lane,_)|(mask>>lane)&1==1).map(|(_,tag)|*tag)
+                })
+                .collect::<Vec<u128>>()
+        })
+        .collect()
+}
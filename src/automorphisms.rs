@@ -0,0 +1,333 @@
+//!
+//! Automorphism groups of hypergroupoids.
+//!
+//! A permutation `σ` of `H` is an automorphism of `(H,∘)` if `σ(a∘b)=σ(a)∘σ(b)` for all `a,b` in `H`, i.e.,
+//! if [`HyperGroupoid::isomorphic_hypergroup_from_permutation`] leaves the table unchanged.
+//!
+//! The group is computed as a stabilizer chain along the base `0,1,...,n-1`: for every `i`, starting from the last one,
+//! we look for automorphisms fixing `0,...,i-1` pointwise and mapping `i` to each point not yet in the orbit of `i`.
+//! The automorphisms found this way generate the whole group and its order is the product of the orbit lengths
+//! (orbit-stabilizer theorem), so that we never need to list all the automorphisms.
+//!
+use std::collections::{HashSet, VecDeque};
+use itertools::Itertools;
+use permutation::Permutation;
+use crate::{canonical::{relabel_subset, table_of}, hs::HyperGroupoid, hypergroups::HyperGroup};
+
+#[derive(Debug, Clone)]
+pub struct AutomorphismGroup {
+    pub cardinality:u64,
+    /// Generators of the group. The identity is not included, so the trivial group has no generators.
+    pub generators:Vec<Permutation>,
+    pub order:usize,
+    /// Orbits of the group on `H`. Every orbit is sorted and orbits are sorted by their minimum.
+    pub orbits:Vec<Vec<u64>>,
+}
+impl AutomorphismGroup {
+    fn images(&self,sigma:&Permutation)->Vec<usize>{
+        (0..self.cardinality as usize).map(|x|sigma.apply_idx(x)).collect()
+    }
+    /// Returns all the automorphisms, starting from the identity.
+    ///
+    /// # Example
+    /// ```
+    /// use hyperstruc::hs::HyperGroupoid;
+    ///
+    /// let cardinality = 3u64;
+    /// let total = HyperGroupoid::new_from_function(|_,_|7u64, &cardinality);
+    /// let automorphisms = total.automorphism_group().elements();
+    /// assert_eq!(automorphisms.len(),6);
+    /// assert!(automorphisms.iter().all(|sigma|total.is_automorphism(sigma)));
+    /// ```
+    pub fn elements(&self)->Vec<Permutation>{
+        let n = self.cardinality as usize;
+        let generators:Vec<Vec<usize>> = self.generators.iter().map(|sigma|self.images(sigma)).collect();
+        let identity:Vec<usize> = (0..n).collect();
+        let mut elements = vec![identity.clone()];
+        let mut visited:HashSet<Vec<usize>> = HashSet::from([identity.clone()]);
+        let mut queue = VecDeque::from([identity]);
+        while let Some(sigma) = queue.pop_front() {
+            for g in &generators {
+                let product:Vec<usize> = sigma.iter().map(|x|g[*x]).collect();
+                if visited.insert(product.clone()) {
+                    elements.push(product.clone());
+                    queue.push_back(product);
+                }
+            }
+        }
+        elements.into_iter().map(Permutation::oneline).collect()
+    }
+    /// Image of the subset `subset`, represented as an integer, under the permutation `sigma`.
+    pub fn image_of_subset(&self,sigma:&Permutation,subset:&u64)->u64{
+        relabel_subset(*subset, &self.images(sigma))
+    }
+    /// Orbit of the subset `subset` under the automorphism group. Subsets are represented as integers and the orbit is sorted.
+    ///
+    /// # Example
+    /// ```
+    /// use hyperstruc::hs::HyperGroupoid;
+    ///
+    /// let cardinality = 3u64;
+    /// let hs = HyperGroupoid::new_from_function(|a,b|1<<a|1<<b, &cardinality);
+    /// let group = hs.automorphism_group();
+    /// assert_eq!(group.orbit_of_subset(&3u64),vec![3,5,6]);
+    /// ```
+    pub fn orbit_of_subset(&self,subset:&u64)->Vec<u64>{
+        let mut orbit = vec![*subset];
+        let mut index = 0;
+        while index<orbit.len() {
+            for sigma in &self.generators {
+                let image = self.image_of_subset(sigma, &orbit[index]);
+                if !orbit.contains(&image) {
+                    orbit.push(image);
+                }
+            }
+            index+=1;
+        }
+        orbit.sort();
+        orbit
+    }
+    /// Action induced on a family of subsets that is invariant under automorphisms, such as subhypergroups or beta classes.
+    /// Subsets are represented as integers. Every generator is returned as a permutation of the indexes of `subsets`,
+    /// i.e., the `k`-th permutation sends `i` to `j` when the `k`-th generator maps `subsets[i]` onto `subsets[j]`.
+    ///
+    /// It panics if the family is not invariant.
+    ///
+    /// # Example
+    /// ```
+    /// use hyperstruc::hs::HyperGroupoid;
+    ///
+    /// let cardinality = 3u64;
+    /// let hs = HyperGroupoid::new_from_function(|a,b|1<<a|1<<b, &cardinality);
+    /// let group = hs.automorphism_group();
+    /// let pairs = vec![3u64,5,6];
+    /// let action = group.action_on_subsets(&pairs);
+    /// assert_eq!(action.len(),group.generators.len());
+    /// ```
+    pub fn action_on_subsets(&self,subsets:&[u64])->Vec<Permutation>{
+        self.generators.iter()
+            .map(|sigma|
+                Permutation::oneline(
+                    subsets.iter()
+                        .map(|subset|{
+                            let image = self.image_of_subset(sigma, subset);
+                            subsets.iter().position(|s|*s==image).expect("The family of subsets is not invariant under automorphisms!")
+                        })
+                        .collect::<Vec<usize>>()
+                ))
+            .collect()
+    }
+    /// Orbits of the automorphism group on a family of subsets that is invariant under automorphisms.
+    /// Every orbit is sorted and orbits are sorted by their minimum.
+    pub fn orbits_on_subsets(&self,subsets:&[u64])->Vec<Vec<u64>>{
+        subsets.iter()
+            .map(|subset|self.orbit_of_subset(subset))
+            .sorted()
+            .dedup()
+            .collect()
+    }
+}
+/// Backtracking search of automorphisms extending a partial map `image`.
+struct AutomorphismSearch {
+    n:usize,
+    table:Vec<u64>,
+    invariants:Vec<Vec<u32>>,
+    image:Vec<Option<usize>>,
+    used:Vec<bool>,
+}
+impl AutomorphismSearch {
+    fn new(hs:&HyperGroupoid)->Self{
+        let n = hs.n as usize;
+        let table = table_of(hs);
+        let invariants = (0..n)
+            .map(|x|{
+                let mut invariant = vec![table[x*n+x].count_ones()];
+                invariant.extend((0..n).map(|y|table[x*n+y].count_ones()).sorted());
+                invariant.extend((0..n).map(|y|table[y*n+x].count_ones()).sorted());
+                invariant.push(table.iter().filter(|entry|(*entry>>x)&1==1).count() as u32);
+                invariant
+            })
+            .collect();
+        AutomorphismSearch { n, table, invariants, image:vec![None;n], used:vec![false;n] }
+    }
+    /// Checks that mapping `p` to `q` is compatible with the points already mapped.
+    fn is_compatible(&self,p:usize,q:usize)->bool{
+        let n = self.n;
+        if self.invariants[p]!=self.invariants[q] {return false;}
+        let mapped:Vec<(usize,usize)> = (0..n).filter_map(|x|self.image[x].map(|y|(x,y))).collect();
+        for (a,sa) in &mapped {
+            for (b,sb) in &mapped {
+                let entry = self.table[a*n+b];
+                let image_entry = self.table[sa*n+sb];
+                if entry.count_ones()!=image_entry.count_ones() {return false;}
+                if (entry>>p)&1!=(image_entry>>q)&1 {return false;}
+                if (*a==p||*b==p)&&mapped.iter().any(|(z,sz)|(entry>>z)&1!=(image_entry>>sz)&1) {return false;}
+            }
+        }
+        true
+    }
+    fn assign(&mut self,p:usize,q:usize){
+        self.image[p]=Some(q);
+        self.used[q]=true;
+    }
+    fn unassign(&mut self,p:usize){
+        self.used[self.image[p].unwrap()]=false;
+        self.image[p]=None;
+    }
+    /// Extends the current partial map on `0,...,p-1` to an automorphism, if possible.
+    fn extend(&mut self,p:usize)->Option<Vec<usize>>{
+        if p==self.n {
+            return Some(self.image.iter().map(|x|x.unwrap()).collect());
+        }
+        for q in 0..self.n {
+            if self.used[q]||!self.is_compatible_after_assignment(p, q) {continue;}
+            let found = self.extend(p+1);
+            self.unassign(p);
+            if found.is_some() {return found;}
+        }
+        None
+    }
+    /// Assigns `p` to `q` if it is compatible. The assignment is kept only when the function returns true.
+    fn is_compatible_after_assignment(&mut self,p:usize,q:usize)->bool{
+        self.assign(p, q);
+        if self.is_compatible(p, q) {
+            true
+        } else {
+            self.unassign(p);
+            false
+        }
+    }
+}
+impl HyperGroupoid {
+/// Returns true if `sigma` is an automorphism, i.e., if `sigma(ab)=sigma(a)sigma(b)` for all `a,b` in H.
+pub fn is_automorphism(&self,sigma:&Permutation)->bool{
+    self.isomorphic_hypergroup_from_permutation(sigma)==*self
+}
+/// Computes the automorphism group of the hypergroupoid.
+///
+/// The order of the group and the length of the isomorphism class are related by the orbit-stabilizer theorem:
+/// their product is `n!`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::utilities::U1024;
+///
+/// let cardinality = 3u64;
+/// let z3 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%3), &cardinality);
+/// let group = z3.automorphism_group();
+/// assert_eq!(group.order,2);
+/// assert_eq!(group.orbits,vec![vec![0],vec![1,2]]);
+///
+/// let tag = U1024::from(22097724u128);
+/// let hs = HyperGroupoid::new_from_tag_u1024(&tag, &cardinality);
+/// let group = hs.automorphism_group();
+/// assert_eq!(group.order*hs.collect_isomorphism_class().1.len(),6);
+/// assert!(group.generators.iter().all(|sigma|hs.is_automorphism(sigma)));
+/// ```
+pub fn automorphism_group(&self)->AutomorphismGroup{
+    let n = self.n as usize;
+    let mut search = AutomorphismSearch::new(self);
+    let mut generators:Vec<Vec<usize>> = Vec::new();
+    let mut order = 1usize;
+    for i in (0..n).rev() {
+        // Generators found so far fix 0,...,i-1 pointwise, so they generate the stabilizer of 0,...,i.
+        let mut orbit = vec![i];
+        let mut index = 0;
+        let close_orbit = |orbit:&mut Vec<usize>,index:&mut usize,generators:&Vec<Vec<usize>>|{
+            while *index<orbit.len() {
+                for g in generators {
+                    let y = g[orbit[*index]];
+                    if !orbit.contains(&y) {orbit.push(y);}
+                }
+                *index+=1;
+            }
+        };
+        close_orbit(&mut orbit,&mut index,&generators);
+        for target in i+1..n {
+            if orbit.contains(&target) {continue;}
+            for x in 0..i {
+                search.assign(x, x);
+            }
+            if search.is_compatible_after_assignment(i, target) {
+                if let Some(automorphism) = search.extend(i+1) {
+                    generators.push(automorphism);
+                    index = 0;
+                    close_orbit(&mut orbit,&mut index,&generators);
+                }
+                search.unassign(i);
+            }
+            for x in 0..i {
+                search.unassign(x);
+            }
+        }
+        order*=orbit.len();
+    }
+    let mut representative:Vec<usize> = (0..n).collect();
+    for g in &generators {
+        for x in 0..n {
+            let (rx,rgx) = (representative[x],representative[g[x]]);
+            if rx!=rgx {
+                let (min,max) = (rx.min(rgx),rx.max(rgx));
+                representative.iter_mut().filter(|r|**r==max).for_each(|r|*r=min);
+            }
+        }
+    }
+    let orbits = (0..n)
+        .into_group_map_by(|x|representative[*x])
+        .into_values()
+        .map(|orbit|orbit.into_iter().map(|x|x as u64).sorted().collect::<Vec<u64>>())
+        .sorted()
+        .collect();
+    AutomorphismGroup {
+        cardinality:self.n,
+        generators:generators.into_iter().map(Permutation::oneline).collect(),
+        order,
+        orbits
+    }
+}
+/// Returns all the automorphisms of the hypergroupoid. See [`AutomorphismGroup::elements`].
+pub fn collect_automorphisms(&self)->Vec<Permutation>{
+    self.automorphism_group().elements()
+}
+}
+impl HyperGroup {
+pub fn is_automorphism(&self,sigma:&Permutation)->bool{
+    self.0.is_automorphism(sigma)
+}
+pub fn automorphism_group(&self)->AutomorphismGroup{
+    self.0.automorphism_group()
+}
+pub fn collect_automorphisms(&self)->Vec<Permutation>{
+    self.0.collect_automorphisms()
+}
+/// Action of the automorphism group on the proper subhypergroups. It returns the subhypergroups, represented as integers,
+/// and the permutations of their indexes induced by the generators of the automorphism group.
+/// See [`AutomorphismGroup::action_on_subsets`].
+pub fn automorphism_action_on_subhypergroups(&self)->(Vec<u64>,Vec<Permutation>){
+    let subhypergroups = self.collect_proper_subhypergroups();
+    let action = self.automorphism_group().action_on_subsets(&subhypergroups);
+    (subhypergroups,action)
+}
+/// Action of the automorphism group on the classes of the fundamental relation beta. It returns the classes, represented as integers,
+/// and the permutations of their indexes induced by the generators of the automorphism group.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperGroup::new_from_function(|a,b|1<<((a+b)%4), &cardinality).unwrap();
+/// let (classes,action) = z4.automorphism_action_on_beta_classes();
+/// assert_eq!(classes,vec![1,2,4,8]);
+/// assert_eq!(action.len(),1);
+/// assert_eq!(action[0].apply_idx(1),3);
+/// ```
+pub fn automorphism_action_on_beta_classes(&self)->(Vec<u64>,Vec<Permutation>){
+    let classes:Vec<u64> = self.collect_beta_classes().iter()
+        .map(|(_,class)|class.iter().fold(0u64, |acc,x|acc|1<<x))
+        .collect();
+    let action = self.automorphism_group().action_on_subsets(&classes);
+    (classes,action)
+}
+}
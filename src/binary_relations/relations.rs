@@ -2,7 +2,7 @@ use std::{collections::HashSet};
 use nalgebra::DMatrix;
 use itertools::Itertools;
 
-use crate::binary_relations::relation_matrix::RelationMatrix;
+use crate::{binary_relations::relation_matrix::RelationMatrix, hypergroups::HyperStructureError};
 
 #[derive(Debug,Clone,PartialEq, Eq)]
 pub struct Relation {
@@ -107,6 +107,13 @@ impl Relation {
     pub fn are_in_relations(&self,x:&u64,y:&u64)->bool {
         self.rel.contains(&(*x,*y)) 
     }
+    /// Fallible version of [`Relation::quotient_set`]. It returns `HyperStructureError::NotEquivalence` if the relation is not an equivalence.
+    pub fn try_quotient_set(&self)->Result<Vec<(u64,Vec<u64>)>,HyperStructureError>{
+        if self.a!=self.b||!self.is_equivalence() {
+            return Err(HyperStructureError::NotEquivalence);
+        }
+        Ok(self.quotient_set())
+    }
     pub fn quotient_set(&self)->Vec<(u64,Vec<u64>)>{
     assert!(self.is_equivalence(), "Relation is not an equivalence!");
     self.a.iter().map(|x|
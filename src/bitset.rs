@@ -0,0 +1,151 @@
+//!
+//! Dynamically sized bitsets.
+//!
+//! A [`BitSet`] represents a subset of `H={0,1,...,n-1}` for any `n`, using one bit per element as the `u64`
+//! representation used elsewhere in the crate does, but spread over as many 64-bit blocks as needed.
+//! It is the storage backend of [`crate::large_hs::LargeHyperGroupoid`].
+//!
+use std::fmt::{self, Display};
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+use itertools::Itertools;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BitSet {
+    blocks:Vec<u64>,
+    size:usize,
+}
+impl BitSet {
+    /// The empty subset of a set with `size` elements.
+    pub fn new(size:usize)->Self{
+        BitSet { blocks: vec![0u64;size.div_ceil(64)], size }
+    }
+    /// The whole set `{0,1,...,size-1}`.
+    pub fn full(size:usize)->Self{
+        let mut full = BitSet { blocks: vec![u64::MAX;size.div_ceil(64)], size };
+        if !size.is_multiple_of(64) {
+            *full.blocks.last_mut().unwrap() = (1u64<<(size%64))-1;
+        }
+        full
+    }
+    pub fn singleton(size:usize,x:usize)->Self{
+        let mut singleton = BitSet::new(size);
+        singleton.insert(x);
+        singleton
+    }
+    /// The subset of `{0,1,...,size-1}` containing the given elements.
+    ///
+    /// # Example
+    /// ```
+    /// use hyperstruc::bitset::BitSet;
+    ///
+    /// let subset = BitSet::from_elements(100, &[3,64,99]);
+    /// assert_eq!(subset.len(),3);
+    /// assert!(subset.contains(64));
+    /// assert_eq!(subset.iter().collect::<Vec<usize>>(),vec![3,64,99]);
+    /// assert_eq!(subset.to_string(),"{3, 64, 99}");
+    /// ```
+    pub fn from_elements(size:usize,elements:&[usize])->Self{
+        let mut subset = BitSet::new(size);
+        elements.iter().for_each(|x|subset.insert(*x));
+        subset
+    }
+    /// Converts the usual `u64` representation of a subset of `{0,1,...,size-1}` into a bitset.
+    pub fn from_u64(size:usize,subset:u64)->Self{
+        assert!(size>=64||subset>>size==0,"{} does not represent a subset of a set with {} elements",subset,size);
+        let mut bitset = BitSet::new(size);
+        if size>0 {
+            bitset.blocks[0]=subset;
+        }
+        bitset
+    }
+    /// Returns the `u64` representation of the subset, if it only contains elements smaller than 64.
+    ///
+    /// # Example
+    /// ```
+    /// use hyperstruc::bitset::BitSet;
+    ///
+    /// assert_eq!(BitSet::from_u64(5, 10).to_u64(),Some(10));
+    /// assert_eq!(BitSet::singleton(70, 65).to_u64(),None);
+    /// ```
+    pub fn to_u64(&self)->Option<u64>{
+        if self.blocks.iter().skip(1).any(|block|*block!=0) {return None;}
+        Some(self.blocks.first().copied().unwrap_or(0))
+    }
+    /// Cardinality of the ambient set.
+    pub fn size(&self)->usize{
+        self.size
+    }
+    pub fn insert(&mut self,x:usize){
+        assert!(x<self.size,"{} is not an element of a set with {} elements",x,self.size);
+        self.blocks[x/64]|=1<<(x%64);
+    }
+    pub fn remove(&mut self,x:usize){
+        assert!(x<self.size,"{} is not an element of a set with {} elements",x,self.size);
+        self.blocks[x/64]&=!(1<<(x%64));
+    }
+    pub fn contains(&self,x:usize)->bool{
+        x<self.size&&(self.blocks[x/64]>>(x%64))&1==1
+    }
+    pub fn is_empty(&self)->bool{
+        self.blocks.iter().all(|block|*block==0)
+    }
+    /// Number of elements of the subset.
+    pub fn len(&self)->usize{
+        self.blocks.iter().map(|block|block.count_ones() as usize).sum()
+    }
+    pub fn is_singleton(&self)->bool{
+        self.len()==1
+    }
+    pub fn is_full(&self)->bool{
+        *self==BitSet::full(self.size)
+    }
+    pub fn is_subset(&self,other:&BitSet)->bool{
+        self.blocks.iter().zip(&other.blocks).all(|(a,b)|a&!b==0)
+    }
+    pub fn intersects(&self,other:&BitSet)->bool{
+        self.blocks.iter().zip(&other.blocks).any(|(a,b)|a&b!=0)
+    }
+    pub fn complement(&self)->BitSet{
+        let full = BitSet::full(self.size);
+        BitSet {
+            blocks: self.blocks.iter().zip(&full.blocks).map(|(a,f)|!a&f).collect(),
+            size: self.size
+        }
+    }
+    /// Elements of the subset, in increasing order.
+    pub fn iter(&self)->impl Iterator<Item = usize> + Clone + '_{
+        self.blocks.iter()
+            .enumerate()
+            .flat_map(|(i,block)|
+                (0..64).filter(move |x|(block>>x)&1==1).map(move |x|64*i+x))
+    }
+}
+impl BitOrAssign<&BitSet> for BitSet {
+    fn bitor_assign(&mut self, rhs: &BitSet) {
+        assert_eq!(self.size,rhs.size,"Subsets of different sets!");
+        self.blocks.iter_mut().zip(&rhs.blocks).for_each(|(a,b)|*a|=b);
+    }
+}
+impl BitOr for &BitSet {
+    type Output = BitSet;
+    fn bitor(self, rhs: &BitSet) -> BitSet {
+        let mut union = self.clone();
+        union|=rhs;
+        union
+    }
+}
+impl BitAnd for &BitSet {
+    type Output = BitSet;
+    fn bitand(self, rhs: &BitSet) -> BitSet {
+        assert_eq!(self.size,rhs.size,"Subsets of different sets!");
+        BitSet {
+            blocks: self.blocks.iter().zip(&rhs.blocks).map(|(a,b)|a&b).collect(),
+            size: self.size
+        }
+    }
+}
+impl Display for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{{}}}", self.iter().join(", "))
+    }
+}
@@ -0,0 +1,334 @@
+//!
+//! Canonical labelling of hypergroupoids.
+//!
+//! A hypergroupoid on `H = {0,1,...,n-1}` is seen as the ternary incidence structure `z ∈ x∘y`.
+//! Following the individualization-refinement scheme of nauty, elements of `H` are split into an ordered
+//! partition by invariants computed from that incidence, the partition is refined until it is equitable,
+//! and a non-trivial cell is individualized when refinement alone is not enough. Every leaf of the search
+//! tree is a discrete partition, i.e., a relabelling of `H`, and the canonical form is the relabelled table
+//! with the smallest tag. Automorphisms discovered along the way are used to prune isomorphic subtrees.
+//!
+//! Two hypergroupoids are isomorphic if and only if their canonical forms coincide, so isomorphism testing
+//! no longer needs to run over all the `n!` permutations of `H`.
+//!
+use std::collections::{HashSet, VecDeque};
+use itertools::Itertools;
+use nalgebra::DMatrix;
+use permutation::Permutation;
+use crate::{bitset::BitSet, hs::HyperGroupoid, hypergroups::HyperGroup, utilities::U1024};
+
+/// Ordered partition of `H`. Every cell is kept sorted.
+type OrderedPartition = Vec<Vec<usize>>;
+
+/// Entries of the tables handled by the search, i.e., subsets of `H` in the `u64` representation or as [`BitSet`]s.
+pub(crate) trait TableEntry: Clone+Ord {
+    fn for_each_element(&self,f:impl FnMut(usize));
+    fn has(&self,x:usize)->bool;
+    /// Image of the subset under the relabelling `x -> labelling[x]`.
+    fn relabel(&self,labelling:&[usize])->Self;
+}
+impl TableEntry for u64 {
+    fn for_each_element(&self,f:impl FnMut(usize)){
+        (0..64).filter(|x|(self>>x)&1==1).for_each(f);
+    }
+    fn has(&self,x:usize)->bool{
+        (self>>x)&1==1
+    }
+    fn relabel(&self,labelling:&[usize])->Self{
+        relabel_subset(*self, labelling)
+    }
+}
+impl TableEntry for BitSet {
+    fn for_each_element(&self,f:impl FnMut(usize)){
+        self.iter().for_each(f);
+    }
+    fn has(&self,x:usize)->bool{
+        self.contains(x)
+    }
+    fn relabel(&self,labelling:&[usize])->Self{
+        BitSet::from_elements(self.size(), &self.iter().map(|x|labelling[x]).collect_vec())
+    }
+}
+/// Row-major copy of the hypercomposition table, i.e., `table[a*n+b]` is the integer representation of `ab`.
+pub(crate) fn table_of(hs:&HyperGroupoid)->Vec<u64>{
+    let n = hs.n as usize;
+    (0..n).cartesian_product(0..n)
+        .map(|(a,b)|hs.hyper_composition[(a,b)])
+        .collect()
+}
+/// Image of the subset `subset` under the relabelling `x -> labelling[x]`.
+pub(crate) fn relabel_subset(subset:u64,labelling:&[usize])->u64{
+    labelling.iter()
+        .enumerate()
+        .filter(|(x,_)|(subset>>x)&1==1)
+        .fold(0u64, |acc,(_,y)|acc|1<<y)
+}
+/// Table of the isomorphic copy of `table` obtained by relabelling every element `x` as `labelling[x]`.
+pub(crate) fn relabel_table<S:TableEntry>(table:&[S],n:usize,labelling:&[usize])->Vec<S>{
+    let mut relabelled = table.to_vec();
+    for a in 0..n {
+        for b in 0..n {
+            relabelled[labelling[a]*n+labelling[b]]=table[a*n+b].relabel(labelling);
+        }
+    }
+    relabelled
+}
+/// Tag of a row-major table. The first entry of the table is the most significant group of `n` bits,
+/// exactly as in [`HyperGroupoid::get_integer_tag_u1024`]. Comparing tags is therefore the same as
+/// comparing tables lexicographically.
+pub(crate) fn tag_of_table(table:&[u64],n:usize)->U1024{
+    table.iter().fold(U1024::zero(), |acc,x|(acc<<n)|U1024::from(*x))
+}
+pub(crate) fn hypergroupoid_from_table(table:&[u64],n:usize)->HyperGroupoid{
+    HyperGroupoid{
+        h:(0..n as u64).collect(),
+        hyper_composition:DMatrix::from_row_slice(n, n, table),
+        n:n as u64
+    }
+}
+///
+/// Invariant of `x` with respect to the ordered partition with `k` cells described by `cell_of` (index of the cell
+/// of every element). It counts, cell by cell, how `x` behaves as left factor, as right factor and as element of a product.
+///
+fn element_invariant<S:TableEntry>(table:&[S],n:usize,x:usize,cell_of:&[usize],k:usize)->Vec<u32>{
+    let mut invariant = vec![0u32;3*k*k+k];
+    for y in 0..n {
+        let i = cell_of[y];
+        table[x*n+y].for_each_element(|z|invariant[i*k+cell_of[z]]+=1);
+        table[y*n+x].for_each_element(|z|invariant[k*k+i*k+cell_of[z]]+=1);
+        for w in 0..n {
+            if table[y*n+w].has(x) {
+                invariant[2*k*k+i*k+cell_of[w]]+=1;
+            }
+        }
+    }
+    table[x*n+x].for_each_element(|z|invariant[3*k*k+cell_of[z]]+=1);
+    invariant
+}
+///
+/// Refine the ordered partition until it is equitable with respect to the invariants of [`element_invariant`].
+/// Every cell is split into sub-cells sorted by invariant, so that the outcome does not depend on the labels of `H`.
+///
+fn refine<S:TableEntry>(table:&[S],n:usize,mut partition:OrderedPartition)->OrderedPartition{
+    loop {
+        let mut cell_of = vec![0usize;n];
+        for (i,cell) in partition.iter().enumerate() {
+            for x in cell {
+                cell_of[*x]=i;
+            }
+        }
+        let invariants = (0..n)
+            .map(|x|element_invariant(table, n, x, &cell_of, partition.len()))
+            .collect_vec();
+        let refined:OrderedPartition = partition.iter()
+            .flat_map(|cell|
+                cell.iter()
+                    .sorted_by(|x,y|invariants[**x].cmp(&invariants[**y]).then(x.cmp(y)))
+                    .chunk_by(|x|&invariants[**x])
+                    .into_iter()
+                    .map(|(_,sub_cell)|sub_cell.copied().sorted().collect_vec())
+                    .collect_vec())
+            .collect();
+        if refined.len()==partition.len() {
+            return refined;
+        }
+        partition = refined;
+    }
+}
+/// Orbits of the group generated by `generators` (permutations of `0..n` in one-line notation), as a vector
+/// mapping every element to the smallest element of its orbit.
+fn orbit_representatives(n:usize,generators:&[&Vec<usize>])->Vec<usize>{
+    let mut representative:Vec<usize> = (0..n).collect();
+    fn find(representative:&mut [usize],x:usize)->usize{
+        let mut root = x;
+        while representative[root]!=root {
+            root = representative[root];
+        }
+        representative[x]=root;
+        root
+    }
+    for g in generators {
+        for x in 0..n {
+            let (a,b) = (find(&mut representative, x),find(&mut representative, g[x]));
+            if a!=b {
+                representative[a.max(b)]=a.min(b);
+            }
+        }
+    }
+    (0..n).map(|x|find(&mut representative, x)).collect()
+}
+struct CanonicalSearch<'a,S:TableEntry>{
+    table:&'a [S],
+    n:usize,
+    best:Option<(Vec<S>,Vec<usize>)>,
+    automorphisms:Vec<Vec<usize>>,
+}
+impl<S:TableEntry> CanonicalSearch<'_,S> {
+    fn explore(&mut self,partition:OrderedPartition,prefix:&mut Vec<usize>){
+        let partition = refine(self.table, self.n, partition);
+        let Some(target) = partition.iter().position(|cell|cell.len()>1) else {
+            self.leaf(&partition);
+            return;
+        };
+        let mut explored:Vec<usize> = Vec::new();
+        for v in partition[target].clone() {
+            let stabilizer = self.automorphisms.iter()
+                .filter(|g|prefix.iter().all(|x|g[*x]==*x))
+                .collect_vec();
+            let orbits = orbit_representatives(self.n, &stabilizer);
+            if explored.iter().any(|w|orbits[*w]==orbits[v]) {
+                continue;
+            }
+            let mut child = partition.clone();
+            let rest = child[target].iter().filter(|x|**x!=v).copied().collect_vec();
+            child.splice(target..=target, [vec![v],rest]);
+            prefix.push(v);
+            self.explore(child, prefix);
+            prefix.pop();
+            explored.push(v);
+        }
+    }
+    fn leaf(&mut self,partition:&OrderedPartition){
+        let mut labelling = vec![0usize;self.n];
+        for (position,cell) in partition.iter().enumerate() {
+            labelling[cell[0]]=position;
+        }
+        let relabelled = relabel_table(self.table, self.n, &labelling);
+        match &self.best {
+            Some((best_table,best_labelling)) if *best_table==relabelled => {
+                let mut inverse = vec![0usize;self.n];
+                for (x,y) in best_labelling.iter().enumerate() {
+                    inverse[*y]=x;
+                }
+                let automorphism = labelling.iter().map(|y|inverse[*y]).collect_vec();
+                if automorphism.iter().enumerate().any(|(x,y)|x!=*y) {
+                    self.automorphisms.push(automorphism);
+                }
+            },
+            Some((best_table,_)) if *best_table<relabelled => {},
+            _ => self.best = Some((relabelled,labelling)),
+        }
+    }
+}
+/// The smallest relabelled table reached by the search, together with the labelling which reaches it.
+pub(crate) fn canonical_table<S:TableEntry>(table:&[S],n:usize)->(Vec<S>,Vec<usize>){
+    let mut search = CanonicalSearch{
+        table,
+        n,
+        best:None,
+        automorphisms:Vec::new(),
+    };
+    search.explore(vec![(0..n).collect()], &mut Vec::new());
+    search.best.unwrap()
+}
+impl HyperGroupoid {
+///
+/// Compute the canonical form of the hypergroupoid together with the permutation which reaches it.
+/// Isomorphic hypergroupoids have the same canonical form, therefore it can be used to test isomorphism
+/// and to identify isomorphism classes without running over all permutations of `H`.
+///
+/// The permutation `sigma` satisfies `self.isomorphic_hypergroup_from_permutation(&sigma) == canonical`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use permutation::Permutation;
+///
+/// let cardinality = 4u64;
+/// let hs = HyperGroupoid::new_random_from_cardinality(&cardinality);
+/// let (canonical, sigma) = hs.canonical_labelling();
+/// assert_eq!(hs.isomorphic_hypergroup_from_permutation(&sigma),canonical);
+///
+/// let tau = Permutation::oneline(vec![2,0,3,1]);
+/// let isomorphic_hs = hs.isomorphic_hypergroup_from_permutation(&tau);
+/// assert_eq!(isomorphic_hs.canonical_labelling().0,canonical);
+/// ```
+pub fn canonical_labelling(&self)->(HyperGroupoid,Permutation){
+    let n = self.n as usize;
+    let (canonical_table,labelling) = canonical_table(&table_of(self), n);
+    (hypergroupoid_from_table(&canonical_table, n),Permutation::oneline(labelling))
+}
+///
+/// Return the canonical tag of the hypergroupoid, i.e., the tag of its canonical form, and the permutation
+/// which sends the hypergroupoid to its canonical form. See [`HyperGroupoid::canonical_labelling`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::utilities::U1024;
+///
+/// let cardinality = 3u64;
+/// let hs_1 = HyperGroupoid::new_from_tag_u128(&22097724u128,&cardinality);
+/// let hs_2 = HyperGroupoid::new_from_tag_u128(&31958100u128,&cardinality);
+/// let (tag_1,sigma_1) = hs_1.canonical_form();
+/// let (tag_2,_) = hs_2.canonical_form();
+///
+/// assert_eq!(tag_1,tag_2);
+/// assert_eq!(hs_1.isomorphic_hypergroup_from_permutation(&sigma_1).get_integer_tag_u1024(),tag_1);
+/// ```
+pub fn canonical_form(&self)->(U1024,Permutation){
+    let (canonical,sigma) = self.canonical_labelling();
+    (tag_of_table(&table_of(&canonical), self.n as usize),sigma)
+}
+///
+/// Return a permutation `sigma` of `H` such that `self.isomorphic_hypergroup_from_permutation(&sigma) == *other`,
+/// if the two hypergroupoids are isomorphic.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// let cardinality = 3u64;
+/// let hs_1 = HyperGroupoid::new_from_tag_u128(&22097724u128,&cardinality);
+/// let hs_2 = HyperGroupoid::new_from_tag_u128(&31958100u128,&cardinality);
+/// let sigma = hs_1.find_isomorphism(&hs_2).unwrap();
+///
+/// assert_eq!(hs_1.isomorphic_hypergroup_from_permutation(&sigma),hs_2);
+/// ```
+pub fn find_isomorphism(&self,other:&Self)->Option<Permutation>{
+    if self.n!=other.n {return None;}
+    let (canonical_self,sigma) = self.canonical_labelling();
+    let (canonical_other,tau) = other.canonical_labelling();
+    if canonical_self!=canonical_other {return None;}
+    let tau_inverse = tau.inverse();
+    Some(Permutation::oneline(
+        (0..self.n as usize)
+            .map(|x|tau_inverse.apply_idx(sigma.apply_idx(x)))
+            .collect_vec()))
+}
+///
+/// Collect the tags of all the hypergroupoids isomorphic to `self`, sorted. The orbit of the table under the
+/// action of the symmetric group is explored through adjacent transpositions, so that every element of the
+/// class is computed once, instead of once for every permutation of `H`.
+///
+pub(crate) fn isomorphism_orbit(&self)->Vec<U1024>{
+    let n = self.n as usize;
+    let table = table_of(self);
+    let transpositions = (0..n.saturating_sub(1))
+        .map(|i|(0..n).map(|x| if x==i {i+1} else if x==i+1 {i} else {x}).collect_vec())
+        .collect_vec();
+    let mut visited:HashSet<Vec<u64>> = HashSet::from([table.clone()]);
+    let mut queue:VecDeque<Vec<u64>> = VecDeque::from([table]);
+    while let Some(current) = queue.pop_front() {
+        for tau in &transpositions {
+            let next = relabel_table(&current, n, tau);
+            if visited.insert(next.clone()) {
+                queue.push_back(next);
+            }
+        }
+    }
+    visited.iter().map(|t|tag_of_table(t, n)).sorted().collect()
+}
+}
+impl HyperGroup {
+pub fn canonical_labelling(&self)->(HyperGroup,Permutation){
+    let (canonical,sigma) = self.0.canonical_labelling();
+    (HyperGroup(canonical),sigma)
+}
+pub fn canonical_form(&self)->(U1024,Permutation){
+    self.0.canonical_form()
+}
+pub fn find_isomorphism(&self,other:&Self)->Option<Permutation>{
+    self.0.find_isomorphism(&other.0)
+}
+}
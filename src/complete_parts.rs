@@ -0,0 +1,112 @@
+//!
+//! Complete parts and complete hyperstructures.
+//!
+//! A subset `A` of H is a complete part if every hyperproduct meeting `A` is contained in `A`. Since `β` is the union of
+//! `q×q` over the hyperproducts `q`, the complete parts are exactly the unions of `β*`-classes, and the complete closure
+//! `C(A)`, i.e., the smallest complete part containing `A`, is the union of the `β*`-classes meeting `A`. In a hypergroup
+//! `β=β*`, so `C({x})` is the class of `x` returned by [`HyperGroupoid::collect_beta_classes`].
+//!
+//! A semihypergroup is complete if every hyperproduct of at least two elements is a complete part. Since a hyperproduct
+//! of `n>2` elements is a union of hyperproducts `ab`, it is enough to check the entries of the table.
+//!
+use itertools::Itertools;
+use crate::{fundamental_relations::equivalence_classes_from_blocks, hs::HyperGroupoid, hypergroups::HyperGroup};
+
+impl HyperGroupoid {
+/// The classes of `β*` as subsets, sorted by least element.
+fn beta_star_classes(&self)->Vec<u64>{
+    equivalence_classes_from_blocks(&self.collect_ph(), &self.n)
+}
+/// Return true if every hyperproduct meeting the subset is contained in it.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use nalgebra::DMatrix;
+///
+/// let cardinality=4u64;
+/// let hs = HyperGroupoid::new_from_matrix(
+///     &DMatrix::from_row_slice(
+///     cardinality as usize,
+///     cardinality as usize,
+///      &[6,10,10,10,
+///       10,10,10,10,
+///       10,10,10,10,
+///       10,10,10,10]));
+/// assert!(hs.is_complete_part(&1));
+/// assert!(!hs.is_complete_part(&2));
+/// assert_eq!(hs.complete_closure(&2),14);
+/// assert_eq!(hs.collect_complete_parts(),vec![1,14,15]);
+/// ```
+pub fn is_complete_part(&self,subset:&u64)->bool{
+    assert!(*subset>>self.n==0,"{} does not represent a subset of H",subset);
+    self.collect_ph().iter().all(|q|q&subset==0||q&!subset==0)
+}
+/// Returns the complete closure `C(A)` of a subset, i.e., the smallest complete part containing it. It is the union of
+/// the `β*`-classes meeting the subset.
+pub fn complete_closure(&self,subset:&u64)->u64{
+    assert!(*subset>>self.n==0,"{} does not represent a subset of H",subset);
+    self.beta_star_classes().iter()
+        .filter(|class|*class&subset!=0)
+        .fold(0, |acc,class|acc|class)
+}
+/// Returns all the nonempty complete parts, i.e., the nonempty unions of `β*`-classes, sorted.
+pub fn collect_complete_parts(&self)->Vec<u64>{
+    let classes = self.beta_star_classes();
+    classes.iter().powerset()
+        .skip(1)
+        .map(|union|union.into_iter().fold(0, |acc,class|acc|class))
+        .sorted()
+        .collect()
+}
+/// Return true if hs is a complete semihypergroup, i.e., it is associative and every hyperproduct of at least two
+/// elements is a complete part.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// // Classes {0,1} and {2} over Z_2: ab is the class of the sum of the classes of a and b.
+/// let cardinality = 3u64;
+/// let class = |x:u64|if x<2 {0} else {1};
+/// let hs = HyperGroupoid::new_from_function(|a,b|if (class(a)+class(b))%2==0 {3} else {4}, &cardinality);
+/// assert!(hs.is_complete());
+/// // The total hypergroup is complete, a,b -> {a,b} is not.
+/// assert!(HyperGroupoid::new_from_function(|_,_|7, &cardinality).is_complete());
+/// assert!(!HyperGroupoid::new_from_function(|a,b|1<<a|1<<b, &cardinality).is_complete());
+/// ```
+pub fn is_complete(&self)->bool{
+    if !self.is_associative() {return false;}
+    let classes = self.beta_star_classes();
+    self.hyper_composition.iter()
+        .all(|entry|classes.iter().all(|class|class&entry==0||class&!entry==0))
+}
+}
+impl HyperGroup {
+pub fn is_complete_part(&self,subset:&u64)->bool{
+    self.0.is_complete_part(subset)
+}
+/// Returns the complete closure `C(A)` of a subset. For an element `x`, `C({x})` is the `β`-class of `x`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// let cardinality = 3u64;
+/// let tag = 57784611u128;
+/// let hg = HyperGroup::new_from_tag_u128(&tag, &cardinality);
+/// for (x,class) in hg.collect_beta_classes() {
+///     assert_eq!(hg.complete_closure(&(1<<x)),class.iter().fold(0, |acc,y|acc|1<<y));
+/// }
+/// ```
+pub fn complete_closure(&self,subset:&u64)->u64{
+    self.0.complete_closure(subset)
+}
+pub fn collect_complete_parts(&self)->Vec<u64>{
+    self.0.collect_complete_parts()
+}
+/// Return true if the hypergroup is complete, i.e., `C(ab)=ab` for all `a,b` in H.
+pub fn is_complete(&self)->bool{
+    self.0.is_complete()
+}
+}
@@ -0,0 +1,149 @@
+//!
+//! Conjugation and normal subhypergroups.
+//!
+//! In a hypergroup with identities, the conjugate of a subset `K` by `x` is `xKx⁻¹`, the union of `xKx'` over all the
+//! two-sided inverses `x'` of `x` with respect to all the identities. Conjugation gives conjugacy classes of elements,
+//! i.e., the classes of the smallest equivalence containing the pairs `(y,z)` with `z` in `xyx⁻¹`, and conjugacy classes
+//! of subhypergroups, defined in the same way.
+//!
+//! A subhypergroup `K` is normal if `xK = Kx` for all `x` in H, see [`HyperGroup::subhypergroup_is_normal`]. In that
+//! case the double cosets `KxK = xK` are the cosets of `K`, and they give the quotient `H/K` when they partition H.
+//!
+use itertools::Itertools;
+use crate::{cosets::is_partition, fundamental_relations::equivalence_classes_from_blocks, hypergroups::{HyperGroup, HyperStructureError}};
+
+impl HyperGroup {
+/// Returns the two-sided inverses of `x` with respect to all the identities. Unlike [`HyperGroup::collect_inverses_of_x`],
+/// an identity `u` is included if `u` is in `xu` and in `ux`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperGroup::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality).unwrap();
+/// assert_eq!(z4.two_sided_inverses(&0b0001),0b0001);
+/// assert_eq!(z4.two_sided_inverses(&0b0010),0b1000);
+/// ```
+pub fn two_sided_inverses(&self,x:&u64)->u64{
+    self.collect_left_inverses_of_x(x).into_iter()
+        .zip(self.collect_right_inverses_of_x(x))
+        .fold(0, |acc,((u,left),(_,right))|{
+            let u_is_inverse = self.mul_by_representation(x, &u)&self.mul_by_representation(&u, x)&u!=0;
+            acc|(left&right)|if u_is_inverse {u} else {0}
+        })
+}
+/// Returns the conjugate `xKx⁻¹` of the subset `K`. It is empty if H has no identity.
+pub fn conjugate(&self,k:&u64,x:&u64)->u64{
+    let xk = self.mul_by_representation(x, k);
+    let inverses = self.two_sided_inverses(x);
+    if inverses==0 {return 0;}
+    self.mul_by_representation(&xk, &inverses)
+}
+/// Returns the conjugacy classes of elements, sorted by their least element. Every class is a singleton if H has no identity.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// // The symmetric group S3, with permutations in lexicographic order.
+/// let perms = [[0,1,2],[0,2,1],[1,0,2],[1,2,0],[2,0,1],[2,1,0]];
+/// let index = |p:[usize;3]|perms.iter().position(|q|*q==p).unwrap();
+/// let function = |a:u64,b:u64|{
+///     let (p,q) = (perms[a as usize],perms[b as usize]);
+///     1<<index([p[q[0]],p[q[1]],p[q[2]]])
+/// };
+/// let s3 = HyperGroup::new_from_function(function, &6).unwrap();
+/// assert_eq!(s3.collect_conjugacy_classes(),vec![0b000001,0b100110,0b011000]);
+/// ```
+pub fn collect_conjugacy_classes(&self)->Vec<u64>{
+    let blocks = self.get_singleton().iter()
+        .cartesian_product(self.get_singleton())
+        .map(|(y,x)|y|self.conjugate(y, &x))
+        .collect_vec();
+    equivalence_classes_from_blocks(&blocks, &self.cardinality())
+}
+/// Returns the conjugacy classes of subhypergroups, H included. Classes are sorted by their least subhypergroup, and
+/// subhypergroups in a class are sorted by representation.
+pub fn collect_subhypergroup_conjugacy_classes(&self)->Vec<Vec<u64>>{
+    let h = (1u64<<self.cardinality())-1;
+    let subhypergroups = self.collect_proper_subhypergroups().into_iter().chain(std::iter::once(h)).collect_vec();
+    let mut labels = (0..subhypergroups.len()).collect_vec();
+    for (i,k) in subhypergroups.iter().enumerate() {
+        for x in self.get_singleton() {
+            let conjugate = self.conjugate(k, &x);
+            if let Some(j) = subhypergroups.iter().position(|l|*l==conjugate) {
+                let (old,new) = (labels[i].max(labels[j]),labels[i].min(labels[j]));
+                labels.iter_mut().filter(|label|**label==old).for_each(|label|*label=new);
+            }
+        }
+    }
+    labels.iter().unique().sorted()
+        .map(|label|(0..subhypergroups.len()).filter(|i|labels[*i]==*label).map(|i|subhypergroups[i]).collect())
+        .collect()
+}
+/// Return true if `K` is a subhypergroup such that `xKx⁻¹ ⊆ K` for all `x` in H.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// let perms = [[0,1,2],[0,2,1],[1,0,2],[1,2,0],[2,0,1],[2,1,0]];
+/// let index = |p:[usize;3]|perms.iter().position(|q|*q==p).unwrap();
+/// let function = |a:u64,b:u64|{
+///     let (p,q) = (perms[a as usize],perms[b as usize]);
+///     1<<index([p[q[0]],p[q[1]],p[q[2]]])
+/// };
+/// let s3 = HyperGroup::new_from_function(function, &6).unwrap();
+/// let a3 = 0b011001u64;
+/// assert!(s3.subhypergroup_is_conjugation_invariant(&a3)&&s3.subhypergroup_is_normal(&a3));
+/// let k = 0b000011u64;
+/// assert!(!s3.subhypergroup_is_conjugation_invariant(&k)&&!s3.subhypergroup_is_normal(&k));
+/// ```
+pub fn subhypergroup_is_conjugation_invariant(&self,k:&u64)->bool{
+    self.is_sub_hypergroup(k)
+    &&
+    self.get_singleton().iter().all(|x|self.conjugate(k, x)&!k==0)
+}
+/// Return true if `K` is an invertible subhypergroup whose left and right cosets coincide. Since `x` is in `xK` and in `Kx`
+/// whenever `K` is invertible, this is the same as being invertible and normal.
+pub fn subhypergroup_is_invertible_normal(&self,k:&u64)->bool{
+    self.subhypergroup_is_invertible(k)&&self.collect_left_cosets(k)==self.collect_right_cosets(k)
+}
+/// Returns the quotient `H/K` of the normal subhypergroup `K`, whose elements are the cosets `xK = Kx`, together with the
+/// map `x -> xK`. It fails if `K` is not normal, if the cosets do not partition H or if the quotient is not a hypergroup.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// let perms = [[0,1,2],[0,2,1],[1,0,2],[1,2,0],[2,0,1],[2,1,0]];
+/// let index = |p:[usize;3]|perms.iter().position(|q|*q==p).unwrap();
+/// let function = |a:u64,b:u64|{
+///     let (p,q) = (perms[a as usize],perms[b as usize]);
+///     1<<index([p[q[0]],p[q[1]],p[q[2]]])
+/// };
+/// let s3 = HyperGroup::new_from_function(function, &6).unwrap();
+/// let z2 = HyperGroup::new_from_function(|a,b|1<<((a+b)%2), &2).unwrap();
+/// let (quotient,map) = s3.get_normal_quotient(&0b011001).unwrap();
+/// assert_eq!(quotient,z2);
+/// assert_eq!(map,vec![0,1,1,0,0,1]);
+/// assert!(s3.get_normal_quotient(&0b000011).is_err());
+/// ```
+pub fn get_normal_quotient(&self,k:&u64)->Result<(HyperGroup,Vec<u64>),HyperStructureError>{
+    if !self.subhypergroup_is_normal(k) {
+        return Err(HyperStructureError::NotNormalSubHypergroup { subset: *k });
+    }
+    let cosets = self.collect_left_cosets(k);
+    if !is_partition(&cosets, self.cardinality()) {
+        return Err(HyperStructureError::OverlappingDoubleCosets { subset: *k });
+    }
+    self.quotient_by_cosets(&cosets)
+}
+/// Returns the quotients `H/K` for all the proper normal subhypergroups `K` that have one, paired with `K`.
+pub fn collect_normal_quotients(&self)->Vec<(u64,HyperGroup)>{
+    self.collect_proper_normal_subhypergroups().into_iter()
+        .filter_map(|k|self.get_normal_quotient(&k).ok().map(|(quotient,_)|(k,quotient)))
+        .collect()
+}
+}
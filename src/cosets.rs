@@ -0,0 +1,132 @@
+//!
+//! Cosets and double cosets of subhypergroups.
+//!
+//! For a subhypergroup `K` of H and `x` in H, the left coset is `xK`, the right coset is `Kx` and the double coset is `KxK`.
+//! Unlike the case of groups, cosets of a subhypergroup need not partition H, even if `K` is closed. They do if `K` is
+//! invertible. If `K` is closed and its double cosets partition H, the quotient hypergroup `H//K` has the double cosets
+//! as elements, with `(KxK)(KyK) = {KzK : z in KxKyK}`.
+//!
+//! As in the rest of the crate, elements `x` are given as singletons, i.e., as powers of two.
+//!
+use itertools::Itertools;
+use crate::hypergroups::{HyperGroup, HyperStructureError};
+
+impl HyperGroup {
+/// Returns the left coset `xK`.
+pub fn left_coset(&self,k:&u64,x:&u64)->u64{
+    self.mul_by_representation(x, k)
+}
+/// Returns the right coset `Kx`.
+pub fn right_coset(&self,k:&u64,x:&u64)->u64{
+    self.mul_by_representation(k, x)
+}
+/// Returns the double coset `KxK`.
+pub fn double_coset(&self,k:&u64,x:&u64)->u64{
+    let kx = self.mul_by_representation(k, x);
+    self.mul_by_representation(&kx, k)
+}
+/// Returns the distinct left cosets `xK`, sorted by their least element.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// let cardinality = 6u64;
+/// let z6 = HyperGroup::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality).unwrap();
+/// let k = 0b001001u64; // {0,3}
+/// assert_eq!(z6.collect_left_cosets(&k),vec![0b001001,0b010010,0b100100]);
+/// assert_eq!(z6.subhypergroup_index(&k),3);
+/// assert!(z6.left_cosets_partition(&k));
+/// ```
+pub fn collect_left_cosets(&self,k:&u64)->Vec<u64>{
+    distinct_subsets(self.get_singleton().iter().map(|x|self.left_coset(k, x)))
+}
+/// Returns the distinct right cosets `Kx`, sorted by their least element.
+pub fn collect_right_cosets(&self,k:&u64)->Vec<u64>{
+    distinct_subsets(self.get_singleton().iter().map(|x|self.right_coset(k, x)))
+}
+/// Returns the distinct double cosets `KxK`, sorted by their least element.
+pub fn collect_double_cosets(&self,k:&u64)->Vec<u64>{
+    distinct_subsets(self.get_singleton().iter().map(|x|self.double_coset(k, x)))
+}
+/// Returns the index `[H:K]`, i.e., the number of distinct left cosets of `K`.
+pub fn subhypergroup_index(&self,k:&u64)->usize{
+    assert!(self.is_sub_hypergroup(k),"{} is not a subhypergroup",k);
+    self.collect_left_cosets(k).len()
+}
+/// Return true if the left cosets of `K` partition H.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// // In xy = {x,y}, the left cosets of K = {0} are {0}, {0,1} and {0,2}.
+/// let hg = HyperGroup::new_from_function(|a,b|1<<a|1<<b, &3).unwrap();
+/// assert!(!hg.left_cosets_partition(&0b001));
+/// assert!(!hg.subhypergroup_is_closed(&0b001));
+/// ```
+pub fn left_cosets_partition(&self,k:&u64)->bool{
+    is_partition(&self.collect_left_cosets(k), self.cardinality())
+}
+/// Return true if the right cosets of `K` partition H.
+pub fn right_cosets_partition(&self,k:&u64)->bool{
+    is_partition(&self.collect_right_cosets(k), self.cardinality())
+}
+/// Return true if the double cosets of `K` partition H.
+pub fn double_cosets_partition(&self,k:&u64)->bool{
+    is_partition(&self.collect_double_cosets(k), self.cardinality())
+}
+/// Returns the quotient hypergroup `H//K` of the closed subhypergroup `K`, together with the map `x -> KxK`:
+/// `map[x]` is the index of the double coset of `x`, as listed by [`HyperGroup::collect_double_cosets`], and it is an inclusion homomorphism.
+/// It fails if `K` is not closed or if its double cosets do not partition H.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// let cardinality = 6u64;
+/// let z6 = HyperGroup::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality).unwrap();
+/// let z3 = HyperGroup::new_from_function(|a,b|1<<((a+b)%3), &3).unwrap();
+/// let (quotient,map) = z6.get_double_coset_quotient(&0b001001).unwrap();
+/// assert_eq!(quotient,z3);
+/// assert_eq!(map,vec![0,1,2,0,1,2]);
+/// // K = {0,1} is not a subhypergroup of Z6.
+/// assert!(z6.get_double_coset_quotient(&0b000011).is_err());
+/// ```
+pub fn get_double_coset_quotient(&self,k:&u64)->Result<(HyperGroup,Vec<u64>),HyperStructureError>{
+    if !self.subhypergroup_is_closed(k) {
+        return Err(HyperStructureError::NotClosedSubHypergroup { subset: *k });
+    }
+    let cosets = self.collect_double_cosets(k);
+    if !is_partition(&cosets, self.cardinality()) {
+        return Err(HyperStructureError::OverlappingDoubleCosets { subset: *k });
+    }
+    self.quotient_by_cosets(&cosets)
+}
+/// The quotient whose elements are the given cosets, which must partition H, and whose hypercomposition sends
+/// `(A,B)` to the cosets meeting `AB`, together with the map sending `x` to the index of its coset.
+pub(crate) fn quotient_by_cosets(&self,cosets:&[u64])->Result<(HyperGroup,Vec<u64>),HyperStructureError>{
+    let map = (0..self.cardinality())
+        .map(|x|cosets.iter().position(|coset|(coset>>x)&1==1).unwrap() as u64)
+        .collect_vec();
+    let function = |a:u64,b:u64|{
+        let product = self.mul_by_representation(&cosets[a as usize], &cosets[b as usize]);
+        (0..cosets.len()).filter(|c|cosets[*c]&product!=0).fold(0, |acc,c|acc|1<<c)
+    };
+    let quotient = HyperGroup::new_from_function(function, &(cosets.len() as u64))?;
+    Ok((quotient,map))
+}
+/// Returns the quotients `H//K` for all the proper closed subhypergroups `K` that have one, paired with `K`.
+pub fn collect_double_coset_quotients(&self)->Vec<(u64,HyperGroup)>{
+    self.collect_proper_closed_subhypergroups().into_iter()
+        .filter_map(|k|self.get_double_coset_quotient(&k).ok().map(|(quotient,_)|(k,quotient)))
+        .collect()
+}
+}
+fn distinct_subsets(subsets:impl Iterator<Item = u64>)->Vec<u64>{
+    subsets.unique().sorted_by_key(|subset|subset.trailing_zeros()).collect()
+}
+pub(crate) fn is_partition(subsets:&[u64],cardinality:u64)->bool{
+    let union = subsets.iter().fold(0, |acc,subset|acc|subset);
+    union==(1<<cardinality)-1&&subsets.iter().map(|subset|subset.count_ones()).sum::<u32>()==cardinality as u32
+}
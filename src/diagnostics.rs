@@ -0,0 +1,262 @@
+//!
+//! Counterexample witnesses for the axioms of hyperstructures.
+//!
+//! Every predicate such as [`HyperGroupoid::is_associative`] has a `check_*` counterpart, returning the first
+//! violation found, and a `collect_*_violations` counterpart, returning all of them. A [`Violation`] carries the
+//! offending elements and both sides of the failing condition, so that a table that fails an axiom can be inspected
+//! directly. Elements are reported as integers in `[0,n-1]`, while subsets are represented as integers, as usual.
+//!
+use std::fmt::{self, Display};
+use itertools::Itertools;
+use crate::{bitset::BitSet, hs::HyperGroupoid, hypergroups::HyperGroup};
+
+/// Side of a product with the whole set. `Left` stands for `xH` (or `xK`, or `xz`), `Right` for `Hx` (or `Kx`, or `zx`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// `(ab)c` differs from `a(bc)`.
+    Associativity{a:u64,b:u64,c:u64,left:u64,right:u64},
+    /// `(ab)c` and `a(bc)` are disjoint.
+    WeakAssociativity{a:u64,b:u64,c:u64,left:u64,right:u64},
+    /// `xH` (or `Hx`, according to `side`) is `product` instead of `H`.
+    Reproductivity{x:u64,side:Side,product:u64},
+    /// `ab` differs from `ba`.
+    Commutativity{a:u64,b:u64,ab:u64,ba:u64},
+    /// `left_division(a,b)` meets `right_division(c,d)`, while `ad` and `bc` are disjoint.
+    Transposition{a:u64,b:u64,c:u64,d:u64,left_division:u64,right_division:u64,ad:u64,bc:u64},
+    /// `xK` (or `Kx`, according to `side`) is `product` instead of `K`, with `x` in `K`.
+    SubHypergroup{x:u64,side:Side,subset:u64,product:u64},
+    /// `x ρ y`, but some element of `xz` (or `zx`, according to `side`) is not related to any element of `yz` (or `zy`),
+    /// or conversely.
+    Regularity{x:u64,y:u64,z:u64,side:Side,product_x:u64,product_y:u64},
+    /// `x ρ y`, but some element of `xz` (or `zx`, according to `side`) is not related to some element of `yz` (or `zy`).
+    StrongRegularity{x:u64,y:u64,z:u64,side:Side,product_x:u64,product_y:u64},
+    /// The image `f(xy)` and the product `f(x)f(y)` do not satisfy the condition of the homomorphism.
+    Homomorphism{x:u64,y:u64,image_of_product:u64,product_of_images:u64},
+    /// `z` is in `xy`, but `x` is not in `zy⁻¹` (`side` is `Left`) or `y` is not in `x⁻¹z` (`side` is `Right`).
+    Reversibility{x:u64,y:u64,z:u64,side:Side},
+    /// `x(y+z)` differs from `xy+xz` (`side` is `Left`), or `(y+z)x` differs from `yx+zx` (`side` is `Right`).
+    Distributivity{x:u64,y:u64,z:u64,side:Side,left:u64,right:u64},
+    /// As `Associativity`, for the structures of [`crate::large_hs`], whose subsets are [`BitSet`]s.
+    LargeAssociativity{a:u64,b:u64,c:u64,left:BitSet,right:BitSet},
+    /// As `Reproductivity`, for the structures of [`crate::large_hs`], whose subsets are [`BitSet`]s.
+    LargeReproductivity{x:u64,side:Side,product:BitSet},
+}
+/// Writes the subset represented by `subset` as `{x, y, ...}`.
+fn subset_to_string(subset:&u64)->String{
+    format!("{{{}}}",(0..64).filter(|x|(subset>>x)&1==1).join(", "))
+}
+impl Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Associativity { a, b, c, left, right } =>
+                write!(f, "Associativity fails for ({a},{b},{c}): ({a}{b}){c} = {} while {a}({b}{c}) = {}.",subset_to_string(left),subset_to_string(right)),
+            Violation::WeakAssociativity { a, b, c, left, right } =>
+                write!(f, "Weak associativity fails for ({a},{b},{c}): ({a}{b}){c} = {} and {a}({b}{c}) = {} are disjoint.",subset_to_string(left),subset_to_string(right)),
+            Violation::Reproductivity { x, side:Side::Left, product } =>
+                write!(f, "Reproductivity fails for {x}: {x}H = {}.",subset_to_string(product)),
+            Violation::Reproductivity { x, side:Side::Right, product } =>
+                write!(f, "Reproductivity fails for {x}: H{x} = {}.",subset_to_string(product)),
+            Violation::Commutativity { a, b, ab, ba } =>
+                write!(f, "Commutativity fails for ({a},{b}): {a}{b} = {} while {b}{a} = {}.",subset_to_string(ab),subset_to_string(ba)),
+            Violation::Transposition { a, b, c, d, left_division, right_division, ad, bc } =>
+                write!(f, "Transposition fails for ({a},{b},{c},{d}): {b}\\{a} = {} meets {c}/{d} = {}, but {a}{d} = {} and {b}{c} = {} are disjoint.",
+                    subset_to_string(left_division),subset_to_string(right_division),subset_to_string(ad),subset_to_string(bc)),
+            Violation::SubHypergroup { x, side:Side::Left, subset, product } =>
+                write!(f, "K = {} is not a subhypergroup: {x}K = {}.",subset_to_string(subset),subset_to_string(product)),
+            Violation::SubHypergroup { x, side:Side::Right, subset, product } =>
+                write!(f, "K = {} is not a subhypergroup: K{x} = {}.",subset_to_string(subset),subset_to_string(product)),
+            Violation::Regularity { x, y, z, side:Side::Left, product_x, product_y } =>
+                write!(f, "Regularity fails for {x} ρ {y} and {z}: {x}{z} = {} and {y}{z} = {} are not related.",subset_to_string(product_x),subset_to_string(product_y)),
+            Violation::Regularity { x, y, z, side:Side::Right, product_x, product_y } =>
+                write!(f, "Regularity fails for {x} ρ {y} and {z}: {z}{x} = {} and {z}{y} = {} are not related.",subset_to_string(product_x),subset_to_string(product_y)),
+            Violation::StrongRegularity { x, y, z, side:Side::Left, product_x, product_y } =>
+                write!(f, "Strong regularity fails for {x} ρ {y} and {z}: not all elements of {x}{z} = {} and {y}{z} = {} are related.",subset_to_string(product_x),subset_to_string(product_y)),
+            Violation::StrongRegularity { x, y, z, side:Side::Right, product_x, product_y } =>
+                write!(f, "Strong regularity fails for {x} ρ {y} and {z}: not all elements of {z}{x} = {} and {z}{y} = {} are related.",subset_to_string(product_x),subset_to_string(product_y)),
+            Violation::Homomorphism { x, y, image_of_product, product_of_images } =>
+                write!(f, "Homomorphism fails for ({x},{y}): f({x}{y}) = {} while f({x})f({y}) = {}.",subset_to_string(image_of_product),subset_to_string(product_of_images)),
+            Violation::Reversibility { x, y, z, side:Side::Left } =>
+                write!(f, "Reversibility fails for ({x},{y},{z}): {z} is in {x}{y}, but {x} is not in {z}{y}⁻¹."),
+            Violation::Reversibility { x, y, z, side:Side::Right } =>
+                write!(f, "Reversibility fails for ({x},{y},{z}): {z} is in {x}{y}, but {y} is not in {x}⁻¹{z}."),
+            Violation::Distributivity { x, y, z, side:Side::Left, left, right } =>
+                write!(f, "Distributivity fails for ({x},{y},{z}): {x}({y}+{z}) = {} while {x}{y}+{x}{z} = {}.",subset_to_string(left),subset_to_string(right)),
+            Violation::Distributivity { x, y, z, side:Side::Right, left, right } =>
+                write!(f, "Distributivity fails for ({x},{y},{z}): ({y}+{z}){x} = {} while {y}{x}+{z}{x} = {}.",subset_to_string(left),subset_to_string(right)),
+            Violation::LargeAssociativity { a, b, c, left, right } =>
+                write!(f, "Associativity fails for ({a},{b},{c}): ({a}{b}){c} = {left} while {a}({b}{c}) = {right}."),
+            Violation::LargeReproductivity { x, side:Side::Left, product } =>
+                write!(f, "Reproductivity fails for {x}: {x}H = {product}."),
+            Violation::LargeReproductivity { x, side:Side::Right, product } =>
+                write!(f, "Reproductivity fails for {x}: H{x} = {product}."),
+        }
+    }
+}
+impl HyperGroupoid {
+fn triples(&self)->impl Iterator<Item = (u64,u64,u64)>{
+    let n = self.n;
+    (0..n).cartesian_product(0..n).cartesian_product(0..n).map(|((a,b),c)|(a,b,c))
+}
+fn associativity_violations(&self)->impl Iterator<Item = Violation> + '_{
+    self.triples()
+        .filter_map(|(a,b,c)|{
+            let left = self.mul_by_representation(&self.hyper_composition[(a as usize,b as usize)], &(1<<c));
+            let right = self.mul_by_representation(&(1<<a), &self.hyper_composition[(b as usize,c as usize)]);
+            (left!=right).then_some(Violation::Associativity { a, b, c, left, right })
+        })
+}
+fn weak_associativity_violations(&self)->impl Iterator<Item = Violation> + '_{
+    self.triples()
+        .filter_map(|(a,b,c)|{
+            let left = self.mul_by_representation(&self.hyper_composition[(a as usize,b as usize)], &(1<<c));
+            let right = self.mul_by_representation(&(1<<a), &self.hyper_composition[(b as usize,c as usize)]);
+            (left&right==0).then_some(Violation::WeakAssociativity { a, b, c, left, right })
+        })
+}
+fn reproductivity_violations(&self)->impl Iterator<Item = Violation> + '_{
+    let h = (1<<self.n)-1;
+    let left = (0..self.n).map(move |x|(x,Side::Left,self.mul_by_representation(&(1<<x), &h)));
+    let right = (0..self.n).map(move |x|(x,Side::Right,self.mul_by_representation(&h, &(1<<x))));
+    left.chain(right)
+        .filter(move |(_,_,product)|*product!=h)
+        .map(|(x,side,product)|Violation::Reproductivity { x, side, product })
+}
+fn commutativity_violations(&self)->impl Iterator<Item = Violation> + '_{
+    (0..self.n).tuple_combinations()
+        .filter_map(|(a,b)|{
+            let ab = self.hyper_composition[(a as usize,b as usize)];
+            let ba = self.hyper_composition[(b as usize,a as usize)];
+            (ab!=ba).then_some(Violation::Commutativity { a, b, ab, ba })
+        })
+}
+/// Returns the first triple `(a,b,c)` such that `(ab)c` differs from `a(bc)`, if any.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::diagnostics::Violation;
+/// use nalgebra::DMatrix;
+///
+/// let matrix = DMatrix::from_row_slice(2, 2, &[1,2,2,2]);
+/// let hs = HyperGroupoid::new_from_matrix(&matrix);
+/// assert!(hs.check_associativity().is_ok());
+///
+/// let matrix = DMatrix::from_row_slice(2, 2, &[2,2,1,1]);
+/// let hs = HyperGroupoid::new_from_matrix(&matrix);
+/// let violation = hs.check_associativity().unwrap_err();
+/// assert_eq!(violation,Violation::Associativity { a: 0, b: 0, c: 0, left: 1, right: 2 });
+/// assert_eq!(violation.to_string(),"Associativity fails for (0,0,0): (00)0 = {0} while 0(00) = {1}.");
+/// ```
+pub fn check_associativity(&self)->Result<(),Violation>{
+    self.associativity_violations().next().map_or(Ok(()), Err)
+}
+/// Returns all the triples `(a,b,c)` such that `(ab)c` differs from `a(bc)`.
+pub fn collect_associativity_violations(&self)->Vec<Violation>{
+    self.associativity_violations().collect()
+}
+/// Returns the first triple `(a,b,c)` such that `(ab)c` and `a(bc)` are disjoint, if any.
+pub fn check_weak_associativity(&self)->Result<(),Violation>{
+    self.weak_associativity_violations().next().map_or(Ok(()), Err)
+}
+/// Returns all the triples `(a,b,c)` such that `(ab)c` and `a(bc)` are disjoint.
+pub fn collect_weak_associativity_violations(&self)->Vec<Violation>{
+    self.weak_associativity_violations().collect()
+}
+/// Returns the first element `x` such that `xH` or `Hx` differs from `H`, if any. Left products are checked first.
+pub fn check_reproductivity(&self)->Result<(),Violation>{
+    self.reproductivity_violations().next().map_or(Ok(()), Err)
+}
+/// Returns all the elements `x` such that `xH` or `Hx` differs from `H`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::diagnostics::{Side, Violation};
+/// use nalgebra::DMatrix;
+///
+/// let matrix = DMatrix::from_row_slice(2, 2, &[1,1,2,2]);
+/// let hs = HyperGroupoid::new_from_matrix(&matrix);
+/// let violations = hs.collect_reproductivity_violations();
+/// assert_eq!(violations,vec![
+///     Violation::Reproductivity { x: 0, side: Side::Left, product: 1 },
+///     Violation::Reproductivity { x: 1, side: Side::Left, product: 2 }]);
+/// println!("{}",violations[0]);
+/// ```
+pub fn collect_reproductivity_violations(&self)->Vec<Violation>{
+    self.reproductivity_violations().collect()
+}
+/// Returns the first pair `(a,b)`, with `a<b`, such that `ab` differs from `ba`, if any.
+pub fn check_commutativity(&self)->Result<(),Violation>{
+    self.commutativity_violations().next().map_or(Ok(()), Err)
+}
+/// Returns all the pairs `(a,b)`, with `a<b`, such that `ab` differs from `ba`.
+pub fn collect_commutativity_violations(&self)->Vec<Violation>{
+    self.commutativity_violations().collect()
+}
+/// Returns the first reason why the hypergroupoid is not a hypergroup, if any. Associativity is checked first.
+pub fn check_hypergroup(&self)->Result<(),Violation>{
+    self.associativity_violations().chain(self.reproductivity_violations()).next().map_or(Ok(()), Err)
+}
+/// Returns all the violations of associativity and reproductivity.
+pub fn collect_hypergroup_violations(&self)->Vec<Violation>{
+    self.associativity_violations().chain(self.reproductivity_violations()).collect()
+}
+}
+impl HyperGroup {
+fn transposition_violations(&self)->impl Iterator<Item = Violation> + '_{
+    let n = self.cardinality();
+    (0..n).cartesian_product(0..n).cartesian_product((0..n).cartesian_product(0..n))
+        .filter_map(|((a,b),(c,d))|{
+            let left_division = self.left_division(&(1<<a), &(1<<b));
+            let right_division = self.right_division(&(1<<c), &(1<<d));
+            if left_division&right_division==0 {return None;}
+            let ad = self.mul_by_representation(&(1<<a), &(1<<d));
+            let bc = self.mul_by_representation(&(1<<b), &(1<<c));
+            (ad&bc==0).then_some(Violation::Transposition { a, b, c, d, left_division, right_division, ad, bc })
+        })
+}
+fn sub_hypergroup_violations(&self,k:&u64)->impl Iterator<Item = Violation> + '_{
+    let k = *k;
+    let n = self.cardinality();
+    assert!(k<1<<n,"K is not a subset of H!");
+    let left = (0..n).filter(move |x|(k>>x)&1==1).map(move |x|(x,Side::Left,self.mul_by_representation(&(1<<x), &k)));
+    let right = (0..n).filter(move |x|(k>>x)&1==1).map(move |x|(x,Side::Right,self.mul_by_representation(&k, &(1<<x))));
+    left.chain(right)
+        .filter(move |(_,_,product)|*product!=k)
+        .map(move |(x,side,product)|Violation::SubHypergroup { x, side, subset:k, product })
+}
+/// Returns the first quadruple `(a,b,c,d)` for which transposition fails, if any. See [`HyperGroup::is_transposition`].
+pub fn check_transposition(&self)->Result<(),Violation>{
+    self.transposition_violations().next().map_or(Ok(()), Err)
+}
+/// Returns all the quadruples `(a,b,c,d)` for which transposition fails.
+pub fn collect_transposition_violations(&self)->Vec<Violation>{
+    self.transposition_violations().collect()
+}
+/// Returns the first element `x` of `K` such that `xK` or `Kx` differs from `K`, if any.
+/// The subset `K` is represented by the integer `k`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+/// use hyperstruc::diagnostics::{Side, Violation};
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperGroup::new_from_function(|a,b|1<<((a+b)%4), &cardinality).unwrap();
+/// assert!(z4.check_sub_hypergroup(&5u64).is_ok());
+/// let violation = z4.check_sub_hypergroup(&3u64).unwrap_err();
+/// assert_eq!(violation,Violation::SubHypergroup { x: 1, side: Side::Left, subset: 3, product: 6 });
+/// ```
+pub fn check_sub_hypergroup(&self,k:&u64)->Result<(),Violation>{
+    self.sub_hypergroup_violations(k).next().map_or(Ok(()), Err)
+}
+/// Returns all the elements `x` of `K` such that `xK` or `Kx` differs from `K`.
+pub fn collect_sub_hypergroup_violations(&self,k:&u64)->Vec<Violation>{
+    self.sub_hypergroup_violations(k).collect()
+}
+}
@@ -1,10 +1,17 @@
 
+use std::collections::HashMap;
 use itertools::Itertools;
-use permutation::Permutation;
-use rayon::iter::{IntoParallelIterator, ParallelBridge, ParallelIterator};
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelBridge, ParallelIterator};
 use crate::unital_magma::UnitalMagma;
-use crate::utilities::{get_min_max, get_min_max_u1024, representing_hypergroupoid_u1024, write, U1024RangeExt, U1024};
+use crate::utilities::{get_min_max, get_min_max_u1024, write, U1024RangeExt, U1024};
+use crate::canonical::{relabel_subset, tag_of_table};
 use crate::hs::HyperGroupoid;
+use crate::hypergroups::HyperGroup;
+use crate::hyperring::HyperRing;
+use crate::finite_fields::FiniteField;
+use nalgebra::DMatrix;
+use crate::small_hs::small_hypergroupoid_dispatch;
+use crate::associativity::filter_hypergroups_in_range_u128;
 use crate::utilities::representing_hypergroupoid;
 
 
@@ -12,9 +19,10 @@ pub fn collect_beta_equivalence(cardinality:&u64)->Vec<u128>{
     let size = cardinality.pow(3);
     (2u128.pow((size-cardinality) as u32)..2u128.pow(size as u32)).
         into_par_iter().filter(|i|
-            (representing_hypergroupoid(i,&cardinality))
-            &
-            (HyperGroupoid::new_from_tag_u128(i, cardinality).beta_relation().is_transitive())
+            small_hypergroupoid_dispatch!(*cardinality, try_new_from_tag_u128(i),
+                |hs|hs.beta_relation().is_transitive(),
+                HyperGroupoid::try_new_from_tag_u128(i, cardinality).map(|hs|hs.beta_relation().is_transitive()))
+            .unwrap_or(false)
         )
         .collect()
 }
@@ -32,9 +40,10 @@ pub fn collect_hypergroupoid_with_scalar_identity(cardinality:&u64)->Vec<u128>{
     (2u128.pow((size-cardinality) as u32)..2u128.pow(size as u32))
         .into_par_iter()
         .filter(|i|
-            representing_hypergroupoid(&i,&cardinality)
-            &
-            !(HyperGroupoid::new_from_tag_u128(i, &cardinality).collect_scalar_identities().is_empty())
+            small_hypergroupoid_dispatch!(*cardinality, try_new_from_tag_u128(i),
+                |hs|!hs.collect_scalar_identities().is_empty(),
+                HyperGroupoid::try_new_from_tag_u128(i, cardinality).map(|hs|!hs.collect_scalar_identities().is_empty()))
+            .unwrap_or(false)
         )
         .collect()
 
@@ -45,10 +54,11 @@ pub fn collect_invertible_magmata(cardinality:&u64)->Vec<u128>{
     (2u128.pow((size-cardinality) as u32)..2u128.pow(size as u32))
         .into_par_iter()
         .filter(|i|
-            representing_hypergroupoid(&mut i.clone(),&cardinality)
-            &
-            (HyperGroupoid::new_from_tag_u128(i, &cardinality).collect_scalar_identities().len()==1)
-            &
+            small_hypergroupoid_dispatch!(*cardinality, try_new_from_tag_u128(i),
+                |hs|hs.collect_scalar_identities().len()==1,
+                HyperGroupoid::try_new_from_tag_u128(i, cardinality).map(|hs|hs.collect_scalar_identities().len()==1))
+            .unwrap_or(false)
+            &&
             (UnitalMagma::new_from_tag_u128(i, &cardinality).is_invertible_unital_magma())
         )
         .collect()
@@ -56,68 +66,112 @@ pub fn collect_invertible_magmata(cardinality:&u64)->Vec<u128>{
 }
 pub fn collect_hypergroups(cardinality:&u64)->Vec<u128>{
     let (min,max)= get_min_max(cardinality);
-    (min..=max).into_par_iter()
-        .filter(|i|
-            (representing_hypergroupoid(&mut i.clone(),&cardinality))
-            &&
-            (HyperGroupoid::new_from_tag_u128(i, cardinality).is_hypergroup())
-        )
+    filter_hypergroups_in_range_u128(min, max, cardinality)
+}
+/// Returns the tags of complete hypergroups among the given tags of hypergroups, e.g., [`crate::hg_3::representants_3::TAG_HG_3`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::enumeration::{collect_hypergroups, filter_complete_hypergroups};
+///
+/// let cardinality = 2u64;
+/// let hypergroups = collect_hypergroups(&cardinality);
+/// let complete = filter_complete_hypergroups(&hypergroups, &cardinality);
+/// // The total hypergroup and the two labellings of Z_2.
+/// assert_eq!(complete.len(),3);
+/// ```
+pub fn filter_complete_hypergroups(tags:&[u128],cardinality:&u64)->Vec<u128>{
+    tags.par_iter()
+        .filter(|tag|HyperGroupoid::new_from_tag_u128(tag, cardinality).is_complete())
+        .copied()
+        .collect()
+}
+/// Returns the tags of polygroups among the given tags of hypergroups, e.g., [`crate::hg_3::representants_3::TAG_HG_3`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::enumeration::{collect_hypergroups, filter_polygroups};
+///
+/// let cardinality = 2u64;
+/// let hypergroups = collect_hypergroups(&cardinality);
+/// let polygroups = filter_polygroups(&hypergroups, &cardinality);
+/// // The two labellings of Z_2 and of the polygroup with tt = {e,t}.
+/// assert_eq!(polygroups.len(),4);
+/// ```
+pub fn filter_polygroups(tags:&[u128],cardinality:&u64)->Vec<u128>{
+    tags.par_iter()
+        .filter(|tag|HyperGroup::new_from_tag_u128(tag, cardinality).is_polygroup())
+        .copied()
         .collect()
 }
 /*This works, but still to slow with respect to (u128..u128).into_par_iter() */
 pub fn collect_hypergroups_u1024(cardinality:&u64)->Vec<U1024>{
     let (min,max)= get_min_max_u1024(cardinality);
     let hgs:Vec<U1024> = min.to(max+1).into_iter().par_bridge().filter(|i|
-        (representing_hypergroupoid_u1024(i,&cardinality))
-        &&
-        (HyperGroupoid::new_from_tag_u1024(i, cardinality).is_hypergroup())
+        small_hypergroupoid_dispatch!(*cardinality, try_new_from_tag_u1024(i),
+            |hs|hs.is_hypergroup(),
+            HyperGroupoid::try_new_from_tag_u1024(i, cardinality).map(|hs|hs.is_hypergroup()))
+        .unwrap_or(false)
     )
     .collect();
 let hgs:Vec<U1024>=hgs.iter().sorted().map(|x|*x).collect();
 hgs
     
 }
-pub fn enumeration_hyperstructure(structure:&str,cardinality:&u64)->Vec<usize>{
-    let tags= match structure {
-        "hypergroups"=> collect_hypergroups(&cardinality),
-        "unital magmata"=>collect_hypergroupoid_with_scalar_identity(&*cardinality),
-        "invertible magmata"=> collect_invertible_magmata(&cardinality),
-        _=>panic!("unknown structure! Works with 'hypergroups, unital magmata,invertible magmata, L_mosaics'. ")
-    };
-    //let tags = collect_hypergroups(&cardinality);
-    let _= write(format!("{:?}",tags.clone()),&format!("tag_{structure}_{cardinality}"));
-    let permut_vec:Vec<Vec<usize>> = (0..*cardinality as usize).permutations(*cardinality as usize).collect();
-    let permutation:Vec<Permutation> = permut_vec.iter().map(|sigma| Permutation::oneline(sigma.clone())).collect();
-    let mut classes:Vec<(u64,Vec<u64>)>=Vec::new();
-
-    for tag in tags {
-    let mut isomorphism_classes:Vec<u64>=Vec::new();
-
-    for sigma in &permutation {        
-        let isomorphic_image_tag = HyperGroupoid::new_from_tag_u128(&tag, &cardinality).isomorphic_hypergroup_from_permutation(&sigma).get_integer_tag();
-        isomorphism_classes.push(isomorphic_image_tag.try_into().unwrap());
-
+/// Groups the tags of an isomorphism-closed family of hypergroupoids into isomorphism classes.
+/// 
+/// Tags are grouped by their canonical form, so that each hypergroupoid is relabelled once instead of
+/// being acted on by all the n! permutations. Every class is returned as `(representant, class)`,
+/// where `class` is the sorted vector of tags in the class and `representant` is its minimum.
+/// Classes are sorted by representant.
+pub fn isomorphism_classes_u1024(tags:&Vec<U1024>,cardinality:&u64)->Vec<(U1024,Vec<U1024>)>{
+    let canonical_tags:Vec<(U1024,U1024)> = tags.par_iter()
+        .map(|tag|(HyperGroupoid::new_from_tag_u1024(tag, cardinality).canonical_form().0,*tag))
+        .collect();
+    let mut classes:HashMap<U1024,Vec<U1024>>=HashMap::new();
+    for (canonical_tag,tag) in canonical_tags {
+        classes.entry(canonical_tag).or_default().push(tag);
     }
-    isomorphism_classes=isomorphism_classes.iter().sorted().dedup().map(|x|*x).collect();
-    let representant_of_class=isomorphism_classes.iter().min().unwrap();
-    
-        classes.push((*representant_of_class,isomorphism_classes));
-
+    classes.into_values()
+        .map(|class|{
+            let class:Vec<U1024>=class.into_iter().sorted().dedup().collect();
+            (class[0],class)
+        })
+        .sorted_by(|x,y|x.0.cmp(&y.0))
+        .collect()
 }
-    let classes:Vec<&(u64, Vec<u64>)>=classes.iter().sorted_by(|x,y|x.0.cmp(&y.0)).dedup().collect();
+fn write_classes_by_size<T:std::fmt::Debug>(classes:&[(T,Vec<T>)],cardinality:&u64,file_name:&str)->Vec<usize>{
+    let permutations_number:usize = (1..=*cardinality as usize).product();
     let mut c:Vec<usize>=Vec::new();
-    let mut c_k:Vec<&(u64,Vec<u64>)>;
+    let mut c_k:Vec<&(T,Vec<T>)>;
     let mut s = String::new();
-    for k in 1..=permut_vec.len(){
-        c_k=classes.iter().filter(|y|(*y.1).len()==k).into_iter().map(|x|*x).collect_vec();
+    for k in 1..=permutations_number{
+        c_k=classes.iter().filter(|y|y.1.len()==k).collect_vec();
         c.push(c_k.len());
         let add_str=format!("{:?}\n",c_k);
         s.push_str(&add_str);
-        let _ = write(s.clone(),&format!("enumeration_{structure}_{cardinality}"));
-        
     }
+    let _ = write(s,file_name);
     c
 }
+pub fn enumeration_hyperstructure(structure:&str,cardinality:&u64)->Vec<usize>{
+    let tags= match structure {
+        "hypergroups"=> collect_hypergroups(&cardinality),
+        "complete hypergroups"=> filter_complete_hypergroups(&collect_hypergroups(cardinality), cardinality),
+        "polygroups"=> filter_polygroups(&collect_hypergroups(cardinality), cardinality),
+        "unital magmata"=>collect_hypergroupoid_with_scalar_identity(&*cardinality),
+        "invertible magmata"=> collect_invertible_magmata(&cardinality),
+        _=>panic!("unknown structure! Works with 'hypergroups, complete hypergroups, polygroups, unital magmata,invertible magmata, L_mosaics'. ")
+    };
+    //let tags = collect_hypergroups(&cardinality);
+    let _= write(format!("{:?}",tags.clone()),&format!("tag_{structure}_{cardinality}"));
+    let tags:Vec<U1024>=tags.iter().map(|tag|U1024::from(*tag)).collect();
+    let classes:Vec<(u64,Vec<u64>)>=isomorphism_classes_u1024(&tags, cardinality).iter()
+        .map(|(representant,class)|
+            (representant.as_u64(),class.iter().map(|tag|tag.as_u64()).collect()))
+        .collect();
+    write_classes_by_size(&classes, cardinality, &format!("enumeration_{structure}_{cardinality}"))
+}
 pub fn enumeration_hyperstructure_u1024(structure:&str,cardinality:&u64)->Vec<usize>{
     let tags= match structure {
         "hypergroups"=> collect_hypergroups_u1024(&cardinality),
@@ -125,36 +179,299 @@ pub fn enumeration_hyperstructure_u1024(structure:&str,cardinality:&u64)->Vec<us
     };
     //let tags = collect_hypergroups(&cardinality);
     let _= write(format!("{:?}",tags.clone()),&format!("tag_{structure}_{cardinality}_{}","u1024"));
-    let permut_vec:Vec<Vec<usize>> = (0..*cardinality as usize).permutations(*cardinality as usize).collect();
-    let permutation:Vec<Permutation> = permut_vec.iter().map(|sigma| Permutation::oneline(sigma.clone())).collect();
-    let mut classes:Vec<(u64,Vec<u64>)>=Vec::new();
-
-    for tag in tags {
-    let mut isomorphism_classes:Vec<u64>=Vec::new();
-
-    for sigma in &permutation {        
-        let isomorphic_image_tag = HyperGroupoid::new_from_tag_u1024(&tag, &cardinality).isomorphic_hypergroup_from_permutation(&sigma).get_integer_tag();
-        isomorphism_classes.push(isomorphic_image_tag.try_into().unwrap());
-
+    let classes=isomorphism_classes_u1024(&tags, cardinality);
+    write_classes_by_size(&classes, cardinality, &format!("enumeration_{structure}_{cardinality}_{}","u1024"))
+}
+/// Partial hypercomposition table explored by the orderly generator.
+/// 
+/// Cells are filled in row-major order, i.e., in the order in which they appear in the tag, and a value `0`
+/// marks a cell that is still empty. Values of a cell are tried in increasing order, so that tables are
+/// produced by increasing tag.
+struct OrderlySearch {
+    n:usize,
+    table:Vec<u64>,
+    permutations:Vec<(Vec<usize>,Vec<usize>)>,
+}
+impl OrderlySearch {
+    fn new(n:usize)->Self{
+        let permutations = (0..n).permutations(n)
+            .skip(1)
+            .map(|sigma|{
+                let mut inverse = vec![0usize;n];
+                for (x,y) in sigma.iter().enumerate(){
+                    inverse[*y]=x;
+                }
+                (sigma,inverse)
+            })
+            .collect();
+        OrderlySearch { n, table:vec![0u64;n*n], permutations }
+    }
+    fn full(&self)->u64{
+        (1<<self.n)-1
+    }
+    /// Known part of the product `set*c` (or `c*set` when `left` is false), together with a flag saying
+    /// whether all the cells involved are already filled.
+    fn partial_product(&self,set:u64,c:usize,left:bool)->(u64,bool){
+        let n = self.n;
+        (0..n).filter(|x|(set>>x)&1==1)
+            .fold((0u64,true), |(acc,complete),x|{
+                let value = if left {self.table[x*n+c]} else {self.table[c*n+x]};
+                (acc|value,complete&&value!=0)
+            })
+    }
+    /// Checks the triples `(a,b,c)` that read the cell `(i,j)`. Whenever one of `(ab)c` and `a(bc)` is fully
+    /// determined, the known part of the other one must be contained in it.
+    fn is_associative_at(&self,i:usize,j:usize)->bool{
+        let n = self.n;
+        for (a,b,c) in (0..n).cartesian_product(0..n).cartesian_product(0..n).map(|((a,b),c)|(a,b,c)){
+            let ab = self.table[a*n+b];
+            let bc = self.table[b*n+c];
+            let reads_cell = (a==i&&b==j)||(b==i&&c==j)||(c==j&&(ab>>i)&1==1)||(a==i&&(bc>>j)&1==1);
+            if !reads_cell||ab==0||bc==0 {continue;}
+            let (left,left_complete)=self.partial_product(ab, c, true);
+            let (right,right_complete)=self.partial_product(bc, a, false);
+            if (left_complete&&right&!left!=0)||(right_complete&&left&!right!=0){
+                return false;
+            }
+        }
+        true
+    }
+    /// Checks reproductivity of the row and of the column of `(i,j)` once they are complete.
+    fn is_reproductive_at(&self,i:usize,j:usize)->bool{
+        let n = self.n;
+        let row_ok = j!=n-1||(0..n).fold(0u64, |acc,b|acc|self.table[i*n+b])==self.full();
+        let column_ok = i!=n-1||(0..n).fold(0u64, |acc,a|acc|self.table[a*n+j])==self.full();
+        row_ok&&column_ok
+    }
+    /// Returns false if some relabelling of the first `filled` cells is already lexicographically smaller
+    /// than the table itself. In that case no completion of the table is the minimum of its isomorphism class.
+    fn is_minimal(&self,filled:usize)->bool{
+        let n = self.n;
+        for (sigma,inverse) in &self.permutations {
+            for cell in 0..filled {
+                let source = inverse[cell/n]*n+inverse[cell%n];
+                if source>=filled {break;}
+                let image = relabel_subset(self.table[source], sigma);
+                if image<self.table[cell] {return false;}
+                if image>self.table[cell] {break;}
+            }
+        }
+        true
+    }
+    fn accepts(&self,cell:usize)->bool{
+        let (i,j)=(cell/self.n,cell%self.n);
+        self.is_reproductive_at(i, j)&&self.is_associative_at(i, j)&&self.is_minimal(cell+1)
+    }
+    /// Fills the table from `cell` on and calls `visitor` on the tag of every complete hypergroup reached.
+    fn explore<F:FnMut(U1024)>(&mut self,cell:usize,visitor:&mut F){
+        if cell==self.n*self.n {
+            visitor(tag_of_table(&self.table, self.n));
+            return;
+        }
+        for value in 1..=self.full(){
+            self.table[cell]=value;
+            if self.accepts(cell){
+                self.explore(cell+1, visitor);
+            }
+        }
+        self.table[cell]=0;
+    }
+    /// Partial tables with the first `depth` cells filled that pass all the tests.
+    fn prefixes(&mut self,cell:usize,depth:usize,prefixes:&mut Vec<Vec<u64>>){
+        if cell==depth {
+            prefixes.push(self.table[..depth].to_vec());
+            return;
+        }
+        for value in 1..=self.full(){
+            self.table[cell]=value;
+            if self.accepts(cell){
+                self.prefixes(cell+1, depth, prefixes);
+            }
+        }
+        self.table[cell]=0;
     }
-    isomorphism_classes=isomorphism_classes.iter().sorted().dedup().map(|x|*x).collect();
-    let representant_of_class=isomorphism_classes.iter().min().unwrap();
-    
-        classes.push((*representant_of_class,isomorphism_classes));
-
-    
 }
-    let classes:Vec<&(u64, Vec<u64>)>=classes.iter().sorted_by(|x,y|x.0.cmp(&y.0)).dedup().collect();
-    let mut c:Vec<usize>=Vec::new();
-    let mut c_k:Vec<&(u64,Vec<u64>)>;
+/// Orderly generation of hypergroups of order `cardinality`.
+/// 
+/// The hypercomposition table is filled cell by cell, in the order of the tag. A partial table is discarded as soon as
+/// 
+/// - a row or a column is complete and it is not reproductive;
+/// - for some triple `(a,b,c)` one of `(ab)c` and `a(bc)` is determined and it does not contain what is already known of the other;
+/// - some relabelling of the filled cells is lexicographically smaller than the table.
+/// 
+/// The last test keeps only the tables whose tag is the minimum of their isomorphism class, so every class is reached exactly once,
+/// through its representant. Nothing is ever scanned outside of the search tree, which makes orders with tags longer than 64 bits reachable.
+/// 
+/// The result has the same `(representant, class)` shape used by [`enumeration_hyperstructure`], sorted by representant.
+/// 
+/// # Example
+/// ```
+/// use hyperstruc::enumeration::collect_hypergroups_orderly;
+/// use hyperstruc::utilities::U1024;
+/// 
+/// let cardinality = 2u64;
+/// let classes = collect_hypergroups_orderly(&cardinality);
+/// assert_eq!(classes.len(),8);
+/// assert_eq!(classes[0],(U1024::from(105u64),vec![U1024::from(105u64),U1024::from(150u64)]));
+/// 
+/// let cardinality = 3u64;
+/// let classes = collect_hypergroups_orderly(&cardinality);
+/// assert_eq!(classes.len(),3999);
+/// assert_eq!(classes.iter().map(|(_,class)|class.len()).sum::<usize>(),23192);
+/// ```
+pub fn collect_hypergroups_orderly(cardinality:&u64)->Vec<(U1024,Vec<U1024>)>{
+    let representants = std::sync::Mutex::new(Vec::new());
+    visit_hypergroups_orderly(cardinality, |tag|representants.lock().unwrap().push(tag));
+    let mut classes:Vec<(U1024,Vec<U1024>)> = representants.into_inner().unwrap().par_iter()
+        .map(|tag|{
+            let class = HyperGroupoid::new_from_tag_u1024(tag, cardinality).isomorphism_orbit();
+            debug_assert_eq!(class[0],*tag);
+            (*tag,class)
+        })
+        .collect();
+    classes.sort_by_key(|x|x.0);
+    classes
+}
+/// Runs the orderly generator of [`collect_hypergroups_orderly`] and calls `visitor` on the representant of every
+/// isomorphism class of hypergroups of order `cardinality`, without storing them. Representants are visited in parallel
+/// and in no particular order. This is the entry point for orders where the classes do not fit in memory.
+/// 
+/// # Example
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use hyperstruc::enumeration::visit_hypergroups_orderly;
+/// 
+/// let cardinality = 3u64;
+/// let classes = AtomicUsize::new(0);
+/// visit_hypergroups_orderly(&cardinality, |_|{classes.fetch_add(1, Ordering::Relaxed);});
+/// assert_eq!(classes.into_inner(),3999);
+/// ```
+pub fn visit_hypergroups_orderly<F>(cardinality:&u64,visitor:F)
+    where F: Fn(U1024) + Sync {
+    let n = *cardinality as usize;
+    let mut search = OrderlySearch::new(n);
+    let mut prefixes:Vec<Vec<u64>>=Vec::new();
+    search.prefixes(0, n.min(2), &mut prefixes);
+    prefixes.into_par_iter()
+        .for_each(|prefix|{
+            let mut search = OrderlySearch::new(n);
+            search.table[..prefix.len()].copy_from_slice(&prefix);
+            search.explore(prefix.len(), &mut |tag|visitor(tag));
+        });
+}
+/// Enumerates hypergroups of order `cardinality` with [`collect_hypergroups_orderly`] and writes classes, grouped by size,
+/// in the file `enumeration_hypergroups_{cardinality}_orderly.txt`. It returns the number of classes of each size.
+pub fn enumeration_hypergroups(cardinality:&u64)->Vec<usize>{
+    let classes = collect_hypergroups_orderly(cardinality);
+    write_classes_by_size(&classes, cardinality, &format!("enumeration_hypergroups_{cardinality}_orderly"))
+}
+/// An entry `(additive tag, multiplicative tag, quotient_of)` of the catalogue written by [`write_hyperfield_catalogue`].
+pub type HyperfieldEntry = (u128,u128,Option<(u64,u64)>);
+/// A class of isomorphic hyperfields, see [`classify_hyperfields`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyperfieldClass {
+    pub representative:HyperRing,
+    /// Tags of the addition and of the multiplication of the representative, as in [`HyperGroupoid::get_integer_tag`].
+    pub tags:(u128,u128),
+    /// The least `(q,d)` such that `F_q/G` is isomorphic to the class, where `G` is the subgroup of order `d` of `F_q*`,
+    /// among fields of order at most the given bound. It is `None` if there is none.
+    pub quotient_of:Option<(u64,u64)>,
+}
+/// Multiplication tables of `{0} ∪ G`, with `0` absorbing, for every group `G` of order `cardinality-1` up to isomorphism.
+/// The identity of `G` is `1`.
+fn multiplicative_groups(cardinality:&u64)->Vec<DMatrix<u64>>{
+    assert!((2..=5).contains(cardinality),"Hyperfields are enumerated for orders from 2 to 5");
+    let n = *cardinality as usize;
+    let m = n-1;
+    let table = |product:&dyn Fn(usize,usize)->usize|DMatrix::from_fn(n, n, |a,b|if a==0||b==0 {1} else {1<<(product(a-1,b-1)+1)});
+    let mut groups = vec![table(&|i,j|(i+j)%m)];
+    if m==4 {
+        groups.push(table(&|i,j|i^j));
+    }
+    groups
+}
+/// Returns all the hyperfields of order `cardinality`, from 2 to 5, whose multiplicative group is one of the tables of
+/// a fixed labelling, one for every group of order `cardinality-1`. By distributivity `a+b = a(1+a⁻¹b)`, so additions
+/// are searched through the sums `1+y`, jointly with the multiplication.
+pub fn collect_hyperfields(cardinality:&u64)->Vec<HyperRing>{
+    let n = *cardinality as usize;
+    let subsets = (1u64<<n)-1;
+    let candidates = subsets.pow(n as u32-1);
+    multiplicative_groups(cardinality).into_iter()
+        .flat_map(|multiplication|{
+            let multiplication = HyperGroupoid::new_from_matrix(&multiplication);
+            let inverse = |a:usize|(1..n).find(|b|multiplication.hyper_composition[(a,*b)]==2).unwrap();
+            let inverses = (0..n).map(|a|if a==0 {0} else {inverse(a)}).collect_vec();
+            (0..candidates).into_par_iter()
+                .filter_map(|index|{
+                    // ones[y] = 1+y
+                    let ones = std::iter::once(2u64).chain((0..n-1).map(|i|(index/subsets.pow(i as u32))%subsets+1)).collect_vec();
+                    if ones.iter().filter(|one|*one&1==1).count()!=1 {return None;}
+                    let addition = DMatrix::from_fn(n, n, |a,b|{
+                        if a==0 {return 1<<b;}
+                        let c = multiplication.hyper_composition[(inverses[a],b)].trailing_zeros() as usize;
+                        multiplication.mul_by_representation(&(1<<a), &ones[c])
+                    });
+                    let addition = HyperGroupoid::new_from_matrix(&addition);
+                    if !addition.is_commutative()||addition.check_associativity().is_err() {return None;}
+                    HyperRing::new(&HyperGroup::try_new_from_hypergroupoid(&addition).ok()?, &multiplication).ok()
+                })
+                .collect::<Vec<HyperRing>>()
+        })
+        .collect()
+}
+/// Returns the hyperfields of order `cardinality`, from 2 to 5, up to isomorphism. Classes are sorted by the tags of
+/// their representative, which is the one with least tags among those found by [`collect_hyperfields`]. Each class is
+/// compared with the quotient hyperfields `F_q/G` with `q` at most `max_field_order`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::enumeration::classify_hyperfields;
+///
+/// let classes = classify_hyperfields(&2, &3);
+/// // The field F_2 and the Krasner hyperfield F_3/F_3*.
+/// assert_eq!(classes.len(),2);
+/// assert!(classes.iter().all(|class|class.quotient_of.is_some()));
+/// ```
+pub fn classify_hyperfields(cardinality:&u64,max_field_order:&u64)->Vec<HyperfieldClass>{
+    let hyperfields = collect_hyperfields(cardinality).into_iter()
+        .sorted_by_key(|hyperfield|(hyperfield.addition.0.get_integer_tag(),hyperfield.multiplication.get_integer_tag()))
+        .collect_vec();
+    let mut representatives:Vec<HyperRing> = Vec::new();
+    for hyperfield in hyperfields {
+        if !representatives.iter().any(|representative|representative.is_isomorphic_to(&hyperfield)) {
+            representatives.push(hyperfield);
+        }
+    }
+    let quotients = (2..=*max_field_order)
+        .filter(|q|(q-1).is_multiple_of(cardinality-1))
+        .filter_map(|q|FiniteField::new(&q).ok())
+        .map(|field|{
+            let d = (field.order-1)/(cardinality-1);
+            let quotient = HyperRing::new_quotient_hyperfield(&field, &field.multiplicative_subgroup(&d)).unwrap();
+            ((field.order,d),quotient)
+        })
+        .collect_vec();
+    representatives.into_iter()
+        .map(|representative|{
+            let quotient_of = quotients.iter().find(|(_,quotient)|quotient.is_isomorphic_to(&representative)).map(|(qd,_)|*qd);
+            let tags = (representative.addition.0.get_integer_tag(),representative.multiplication.get_integer_tag());
+            HyperfieldClass { representative, tags, quotient_of }
+        })
+        .collect()
+}
+/// Classifies the hyperfields of the given orders with [`classify_hyperfields`] and writes them in the file
+/// `catalogue_hyperfields.txt`, as constants `HYPERFIELDS_{n}` of triples `(additive tag, multiplicative tag, quotient_of)`.
+/// It returns the number of classes of each order.
+pub fn write_hyperfield_catalogue(cardinalities:&[u64],max_field_order:&u64)->Vec<usize>{
     let mut s = String::new();
-    for k in 1..=permut_vec.len(){
-        c_k=classes.iter().filter(|y|(*y.1).len()==k).into_iter().map(|x|*x).collect_vec();
-        c.push(c_k.len());
-        let add_str=format!("{:?}\n",c_k);
-        s.push_str(&add_str);
-        let _ = write(s.clone(),&format!("enumeration_{structure}_{cardinality}_{}","u1024"));
-        
+    let mut numbers = Vec::new();
+    for cardinality in cardinalities {
+        let classes = classify_hyperfields(cardinality, max_field_order);
+        let entries = classes.iter().map(|class|format!("({}, {}, {:?})",class.tags.0,class.tags.1,class.quotient_of)).join(", ");
+        s.push_str(&format!("pub const HYPERFIELDS_{cardinality}:[HyperfieldEntry;{}] = [{entries}];\n",classes.len()));
+        numbers.push(classes.len());
     }
-    c
+    let _ = write(s, "catalogue_hyperfields");
+    numbers
 }
@@ -0,0 +1,242 @@
+//!
+//! Small finite fields and Krasner's quotient hyperfields `F/G`.
+//!
+//! The field of order `q = p^k` is `F_p[t]/(f)`, where `f` is the least monic irreducible polynomial of degree `k` in
+//! lexicographic order of coefficients. The element `a_0 + a_1 t + ... + a_{k-1} t^{k-1}` is represented by the integer
+//! `a_0 + a_1 p + ... + a_{k-1} p^{k-1}`, so that `0` and `1` are the zero and the unit and, for `k = 1`, elements are
+//! the usual residues modulo `p`.
+//!
+//! For a subgroup `G` of the multiplicative group `F*`, the quotient `F/G` has the classes `xG` as elements, with
+//! `xG + yG = {zG : z in xG + yG}` and `xG yG = xyG`. It is a hyperfield, whose elements are labelled as follows: `0` is
+//! `{0}` and `i+1` is `g^i G`, where `g` is [`FiniteField::generator`].
+//!
+use itertools::Itertools;
+use nalgebra::DMatrix;
+use crate::{hyperring::HyperRing, hypergroups::HyperStructureError};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiniteField {
+    pub characteristic:u64,
+    pub degree:u32,
+    pub order:u64,
+    /// Coefficients of the monic irreducible polynomial `f`, from the constant term to the leading one.
+    pub modulus:Vec<u64>,
+    addition:DMatrix<u64>,
+    multiplication:DMatrix<u64>,
+}
+impl FiniteField {
+/// Returns the field of order `order`, or an error if `order` is not a prime power.
+///
+/// # Example
+/// ```
+/// use hyperstruc::finite_fields::FiniteField;
+///
+/// let f4 = FiniteField::new(&4).unwrap();
+/// assert_eq!(f4.modulus,vec![1,1,1]);
+/// // t*t = t+1
+/// assert_eq!(f4.mul(2, 2),3);
+/// assert_eq!(f4.add(3, 3),0);
+/// assert!(FiniteField::new(&6).is_err());
+/// ```
+pub fn new(order:&u64)->Result<Self,HyperStructureError>{
+    let (characteristic,degree) = prime_power(*order).ok_or(HyperStructureError::NotPrimePower { order: *order })?;
+    let q = *order as usize;
+    let p = characteristic;
+    let digits = |a:u64|(0..degree).map(|i|(a/p.pow(i))%p).collect_vec();
+    let number = |coefficients:&[u64]|coefficients.iter().rev().fold(0, |acc,c|acc*p+c);
+    let addition = DMatrix::from_fn(q, q, |a,b|{
+        let (a,b) = (digits(a as u64),digits(b as u64));
+        number(&a.iter().zip(b).map(|(x,y)|(x+y)%p).collect_vec())
+    });
+    (0..p.pow(degree))
+        .map(|lower|{
+            let mut modulus = digits(lower);
+            modulus.push(1);
+            modulus
+        })
+        .find_map(|modulus|{
+            let multiplication = DMatrix::from_fn(q, q, |a,b|number(&multiply_modulo(&digits(a as u64), &digits(b as u64), &modulus, p)));
+            let no_zero_divisors = (1..q).cartesian_product(1..q).all(|(a,b)|multiplication[(a,b)]!=0);
+            no_zero_divisors.then_some(FiniteField { characteristic, degree, order: *order, modulus, addition: addition.clone(), multiplication })
+        })
+        .ok_or(HyperStructureError::NotPrimePower { order: *order })
+}
+pub fn add(&self,a:u64,b:u64)->u64{
+    self.addition[(a as usize,b as usize)]
+}
+pub fn mul(&self,a:u64,b:u64)->u64{
+    self.multiplication[(a as usize,b as usize)]
+}
+/// Returns `a^e`, with `a^0 = 1`.
+pub fn pow(&self,a:u64,e:u64)->u64{
+    (0..e).fold(1, |acc,_|self.mul(acc, a))
+}
+/// Returns the least generator of the cyclic group `F*`.
+pub fn generator(&self)->u64{
+    (1..self.order)
+        .find(|g|(1..self.order-1).all(|e|self.pow(*g, e)!=1))
+        .unwrap()
+}
+/// Returns the subgroup of `F*` of order `d`, which must divide `q-1`, as the sorted vector of its elements.
+pub fn multiplicative_subgroup(&self,d:&u64)->Vec<u64>{
+    assert!(*d>0&&(self.order-1).is_multiple_of(*d),"{} does not divide {}",d,self.order-1);
+    let g = self.pow(self.generator(), (self.order-1)/d);
+    (0..*d).map(|i|self.pow(g, i)).sorted().collect()
+}
+/// Returns all the subgroups of the cyclic group `F*`, one for every divisor of `q-1`, sorted by order.
+///
+/// # Example
+/// ```
+/// use hyperstruc::finite_fields::FiniteField;
+///
+/// let f7 = FiniteField::new(&7).unwrap();
+/// assert_eq!(f7.collect_multiplicative_subgroups(),vec![vec![1],vec![1,6],vec![1,2,4],vec![1,2,3,4,5,6]]);
+/// ```
+pub fn collect_multiplicative_subgroups(&self)->Vec<Vec<u64>>{
+    (1..self.order).filter(|d|(self.order-1).is_multiple_of(*d)).map(|d|self.multiplicative_subgroup(&d)).collect()
+}
+/// Returns the field as a hyperring, whose hyperaddition and multiplication are single-valued.
+pub fn to_hyperring(&self)->HyperRing{
+    HyperRing::new_from_functions(|a,b|1<<self.add(a, b), |a,b|1<<self.mul(a, b), &self.order).unwrap()
+}
+}
+/// Returns `(p,k)` if `n = p^k` for a prime `p` and `k > 0`.
+fn prime_power(n:u64)->Option<(u64,u32)>{
+    let p = (2..=n).find(|d|n.is_multiple_of(*d))?;
+    let mut m = n;
+    let mut k = 0;
+    while m.is_multiple_of(p) {
+        m/=p;
+        k+=1;
+    }
+    (m==1).then_some((p,k))
+}
+/// The product of two polynomials of degree less than `k`, reduced modulo the monic polynomial `modulus` of degree `k`.
+fn multiply_modulo(a:&[u64],b:&[u64],modulus:&[u64],p:u64)->Vec<u64>{
+    let k = modulus.len()-1;
+    let mut product = vec![0u64;2*k];
+    for (i,x) in a.iter().enumerate() {
+        for (j,y) in b.iter().enumerate() {
+            product[i+j] = (product[i+j]+x*y)%p;
+        }
+    }
+    for i in (k..2*k).rev() {
+        let c = product[i];
+        if c==0 {continue;}
+        for (j,m) in modulus.iter().enumerate() {
+            product[i-k+j] = (product[i-k+j]+(p-c)*m)%p;
+        }
+    }
+    product.truncate(k);
+    product
+}
+impl HyperRing {
+/// Returns Krasner's quotient hyperfield `F/G` of the subgroup `G` of `F*`, labelled as in the module documentation.
+/// If it has at most 10 elements, the additive hypergroup has tag `addition.get_integer_tag_u1024()`, as every
+/// [`crate::hypergroups::HyperGroup`] of that order.
+///
+/// # Example
+/// ```
+/// use hyperstruc::finite_fields::FiniteField;
+/// use hyperstruc::hyperring::HyperRing;
+/// use hyperstruc::hypergroups::HyperStructureError;
+///
+/// let f7 = FiniteField::new(&7).unwrap();
+/// // F_7 modulo the squares {1,2,4} is the weak sign hyperfield: 1+1 = {1,-1}, unlike the sign hyperfield.
+/// let weak_sign = HyperRing::new_quotient_hyperfield(&f7, &[1,2,4]).unwrap();
+/// assert_eq!(weak_sign.add_by_representation(&0b010, &0b010),0b110);
+/// assert!(!weak_sign.is_isomorphic_to(&HyperRing::sign()));
+/// // F_5 modulo the trivial subgroup is F_5, with elements relabelled as 0, 1, 2, 4, 3.
+/// let f5 = FiniteField::new(&5).unwrap();
+/// let quotient = HyperRing::new_quotient_hyperfield(&f5, &[1]).unwrap();
+/// assert_ne!(quotient,f5.to_hyperring());
+/// assert_eq!(quotient.find_isomorphism(&f5.to_hyperring()),Some(vec![0,1,2,4,3]));
+/// // F_7 modulo F_7* is the Krasner hyperfield.
+/// let krasner = HyperRing::new_quotient_hyperfield(&f7, &[1,2,3,4,5,6]).unwrap();
+/// assert_eq!(krasner,HyperRing::krasner());
+/// assert!(HyperRing::new_quotient_hyperfield(&f7, &[1,2]).is_err());
+/// // F_131 modulo {1,-1} would have 66 elements.
+/// let f131 = FiniteField::new(&131).unwrap();
+/// assert!(matches!(HyperRing::new_quotient_hyperfield(&f131, &[1,130]),Err(HyperStructureError::TooManyElements { found: 66 })));
+/// ```
+pub fn new_quotient_hyperfield(field:&FiniteField,subgroup:&[u64])->Result<HyperRing,HyperStructureError>{
+    let group = subgroup.iter().copied().sorted().dedup().collect_vec();
+    let is_subgroup = !group.is_empty()
+        &&group.iter().all(|x|*x!=0&&*x<field.order)
+        &&group.iter().cartesian_product(group.iter()).all(|(x,y)|group.contains(&field.mul(*x, *y)));
+    if !is_subgroup {
+        return Err(HyperStructureError::NotMultiplicativeSubgroup);
+    }
+    let index = (field.order-1)/group.len() as u64;
+    if index>=64 {
+        return Err(HyperStructureError::TooManyElements { found: index+1 });
+    }
+    let generator = field.generator();
+    let representatives = std::iter::once(0).chain((0..index).map(|i|field.pow(generator, i))).collect_vec();
+    let mut label = vec![0u64;field.order as usize];
+    for (i,r) in representatives.iter().enumerate().skip(1) {
+        for h in &group {
+            label[field.mul(*r, *h) as usize] = i as u64;
+        }
+    }
+    let addition = |a:u64,b:u64|{
+        let (x,y) = (representatives[a as usize],representatives[b as usize]);
+        group.iter().fold(0, |acc,h|acc|1<<label[field.add(x, field.mul(y, *h)) as usize])
+    };
+    let multiplication = |a:u64,b:u64|1<<label[field.mul(representatives[a as usize], representatives[b as usize]) as usize];
+    HyperRing::new_from_functions(addition, multiplication, &(index+1))
+}
+/// Returns a bijection `f` such that `f(x+y) = f(x)+f(y)` and `f(xy) = f(x)f(y)`, if any. It is the first one in
+/// lexicographic order of images, found by backtracking.
+pub fn find_isomorphism(&self,other:&HyperRing)->Option<Vec<u64>>{
+    if self.cardinality()!=other.cardinality() {return None;}
+    IsomorphismSearch::new(self, other).extend(0)
+}
+pub fn is_isomorphic_to(&self,other:&HyperRing)->bool{
+    self.find_isomorphism(other).is_some()
+}
+}
+/// Backtracking search of isomorphisms of hyperrings, mapping `0,1,...,n-1` in this order to unused images.
+struct IsomorphismSearch<'a> {
+    source:&'a HyperRing,
+    target:&'a HyperRing,
+    /// `pairs[p]` are the pairs `(x,y)` whose sum and product can be checked as soon as `p` is mapped.
+    pairs:Vec<Vec<(usize,usize)>>,
+    map:Vec<u64>,
+    used:u64,
+}
+impl<'a> IsomorphismSearch<'a> {
+    fn new(source:&'a HyperRing,target:&'a HyperRing)->Self{
+        let n = source.cardinality() as usize;
+        let mut pairs = vec![Vec::new();n];
+        for (x,y) in (0..n).cartesian_product(0..n) {
+            let entries = source.addition.0.hyper_composition[(x,y)]|source.multiplication.hyper_composition[(x,y)];
+            let last = x.max(y).max(63-entries.leading_zeros() as usize);
+            pairs[last].push((x,y));
+        }
+        IsomorphismSearch { source, target, pairs, map: Vec::with_capacity(n), used: 0 }
+    }
+    fn holds(&self,x:usize,y:usize)->bool{
+        let image = |entry:u64|(0..self.map.len()).filter(|z|(entry>>z)&1==1).fold(0, |acc,z|acc|1<<self.map[z]);
+        let (fx,fy) = (self.map[x] as usize,self.map[y] as usize);
+        image(self.source.addition.0.hyper_composition[(x,y)])==self.target.addition.0.hyper_composition[(fx,fy)]
+        &&
+        image(self.source.multiplication.hyper_composition[(x,y)])==self.target.multiplication.hyper_composition[(fx,fy)]
+    }
+    /// Extends the injective map on `0,...,p-1` to an isomorphism, if possible, stopping at the first one.
+    fn extend(&mut self,p:usize)->Option<Vec<u64>>{
+        if p==self.pairs.len() {
+            return Some(self.map.clone());
+        }
+        for q in 0..self.pairs.len() as u64 {
+            if (self.used>>q)&1==1 {continue;}
+            self.map.push(q);
+            self.used|=1<<q;
+            let found = if self.pairs[p].iter().all(|(x,y)|self.holds(*x, *y)) {self.extend(p+1)} else {None};
+            self.used&=!(1<<q);
+            self.map.pop();
+            if found.is_some() {return found;}
+        }
+        None
+    }
+}
@@ -0,0 +1,536 @@
+//!
+//! Fundamental relations defined as closures.
+//!
+//! An equivalence `θ` on H is strongly regular if `x θ y` implies that every element of `xz ∪ yz` is in relation
+//! with every other, and the same for `zx ∪ zy`, for all `z` in H. Several fundamental relations are the smallest
+//! strongly regular equivalence containing some given blocks, e.g., `γ*` is the smallest one for which every
+//! `ab ∪ ba` lies in a single class. [`strongly_regular_closure`] computes such closures with a union-find structure.
+//!
+//! Besides `γ*` the module provides two relations related to commutativity, returned together with their transitive closures:
+//! - `ε`, where `x ε y` if `x` and `y` belong to `ab ∪ ba` for some `a,b`. In a group `ε*` is the conjugacy relation,
+//!   so in general `ε*` is not strongly regular;
+//! - Freni's `α`, where `x α y` if `x` belongs to a hyperproduct of `z_1,...,z_n` and `y` to a hyperproduct of the same
+//!   elements in another order. Its transitive closure is `γ*`.
+//!
+//! A [`FundamentalRelationsReport`] compares the classes of `β*`, `γ*` and `ε*`.
+//!
+//! An equivalence `ρ` is regular if `x ρ y` implies that every element of `xz` is related to some element of `yz` and
+//! conversely, and the same for `zx` and `zy`, for all `z`. Then `H/ρ` is a hypergroupoid whose products do not depend on
+//! the representatives. If `ρ` is strongly regular, `H/ρ` is a magma, and a group if H is a hypergroup.
+//!
+//! # Reference
+//! D. Freni, *A new characterization of the derived hypergroup via strongly regular equivalences*,
+//! Communications in Algebra 30(8) (2002), 3977–3989.
+//!
+use std::{collections::HashSet, fmt::Display};
+use itertools::Itertools;
+use nalgebra::DMatrix;
+use crate::{binary_relations::relations::Relation, diagnostics::{Side, Violation}, hs::{HyperGroupoid, QuotientHyperGroupoid}, hypergroups::{HyperGroup, HyperStructureError}, product_table::elements_of, quotient_hg::QuotientHyperGroup, utilities::vec_to_set};
+
+/// Union-find structure on the elements `0,1,...,n-1`.
+struct Partition{
+    parent:Vec<usize>,
+}
+impl Partition {
+    fn new(n:usize)->Self{
+        Partition { parent: (0..n).collect() }
+    }
+    fn find(&mut self,x:usize)->usize{
+        let mut root = x;
+        while self.parent[root]!=root {root = self.parent[root];}
+        let mut y = x;
+        while self.parent[y]!=root {
+            let next = self.parent[y];
+            self.parent[y] = root;
+            y = next;
+        }
+        root
+    }
+    fn merge_pairs(&mut self,pairs:&[(u64,u64)]){
+        for (x,y) in pairs {
+            self.merge_subset(1<<x|1<<y);
+        }
+    }
+    /// Puts all elements of the subset in the same class. Returns true if two classes were merged.
+    fn merge_subset(&mut self,subset:u64)->bool{
+        let mut elements = elements_of(subset);
+        let Some(first) = elements.next() else {return false;};
+        let mut merged = false;
+        for x in elements {
+            let (r,s) = (self.find(first),self.find(x));
+            if r!=s {
+                self.parent[r.max(s)] = r.min(s);
+                merged = true;
+            }
+        }
+        merged
+    }
+    /// Classes as subsets, sorted by their least element.
+    fn classes(&mut self)->Vec<u64>{
+        let n = self.parent.len();
+        (0..n).map(|x|(self.find(x),x))
+            .into_group_map()
+            .into_values()
+            .map(|class|class.iter().fold(0u64, |acc,x|acc|1<<x))
+            .sorted_by_key(|class|class.trailing_zeros())
+            .collect()
+    }
+}
+/// Returns the classes, as subsets sorted by least element, of the smallest strongly regular equivalence on hs such
+/// that every subset in `blocks` is contained in a single class.
+///
+/// # Example
+/// ```
+/// use hyperstruc::fundamental_relations::strongly_regular_closure;
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// // Z_4: the only strongly regular equivalences are the congruences modulo a subgroup.
+/// let cardinality = 4u64;
+/// let z4 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality);
+/// assert_eq!(strongly_regular_closure(&z4, &[]),vec![1,2,4,8]);
+/// assert_eq!(strongly_regular_closure(&z4, &[0b0101]),vec![0b0101,0b1010]);
+/// assert_eq!(strongly_regular_closure(&z4, &[0b0011]),vec![0b1111]);
+/// ```
+pub fn strongly_regular_closure(hs:&HyperGroupoid,blocks:&[u64])->Vec<u64>{
+    strongly_regular_closure_of_operations(&[hs], blocks)
+}
+/// Returns the classes, as subsets sorted by least element, of the smallest equivalence that is strongly regular with
+/// respect to all the hypercompositions in `operations`, defined on the same set, and such that every subset in `blocks`
+/// is contained in a single class. This gives the fundamental relations of structures with several hyperoperations.
+pub fn strongly_regular_closure_of_operations(operations:&[&HyperGroupoid],blocks:&[u64])->Vec<u64>{
+    let tables = operations.iter().map(|hs|hs.product_table()).collect_vec();
+    let n = operations.first().map_or(0, |hs|hs.n as usize);
+    assert!(operations.iter().all(|hs|hs.n as usize==n),"All the hypercompositions must be defined on the same set");
+    let mut partition = Partition::new(n);
+    for block in blocks {
+        partition.merge_subset(*block);
+    }
+    loop {
+        let mut merged = false;
+        for class in partition.classes() {
+            for (table,z) in tables.iter().cartesian_product(0..n) {
+                merged|=partition.merge_subset(table.mul_by_representation(&class, &(1<<z)));
+                merged|=partition.merge_subset(table.mul_by_representation(&(1<<z), &class));
+            }
+        }
+        if !merged {break partition.classes();}
+    }
+}
+/// The relation `⋃ A×B` over the given pairs of subsets.
+fn relation_from_pairs_of_subsets(pairs:&[(u64,u64)],cardinality:&u64)->Relation{
+    let rel:Vec<(u64,u64)> = pairs.iter()
+        .flat_map(|(a,b)|elements_of(*a).cartesian_product(elements_of(*b).collect_vec()))
+        .map(|(x,y)|(x as u64,y as u64))
+        .sorted()
+        .dedup()
+        .collect();
+    let h:HashSet<u64> = (0..*cardinality).collect();
+    Relation { a: h.clone(), b: h, rel }
+}
+/// Classes, as subsets sorted by least element, of the transitive closure of a reflexive and symmetric relation.
+fn transitive_closure_classes(relation:&Relation)->Vec<u64>{
+    let mut partition = Partition::new(relation.a.len());
+    partition.merge_pairs(&relation.rel);
+    partition.classes()
+}
+/// Classes, as subsets sorted by least element, of the smallest equivalence such that every subset in `blocks` is contained
+/// in a single class.
+pub(crate) fn equivalence_classes_from_blocks(blocks:&[u64],cardinality:&u64)->Vec<u64>{
+    let mut partition = Partition::new(*cardinality as usize);
+    for block in blocks {
+        partition.merge_subset(*block);
+    }
+    partition.classes()
+}
+/// The equivalence relation whose classes are the given subsets.
+pub(crate) fn relation_from_classes(classes:&[u64],cardinality:&u64)->Relation{
+    let rel:Vec<(u64,u64)> = classes.iter()
+        .flat_map(|class|elements_of(*class).cartesian_product(elements_of(*class).collect_vec()))
+        .map(|(x,y)|(x as u64,y as u64))
+        .sorted()
+        .collect();
+    let h:HashSet<u64> = (0..*cardinality).collect();
+    Relation { a: h.clone(), b: h, rel }
+}
+/// Classes as returned by [`Relation::quotient_set`], i.e., `(representant, elements)` sorted by representant.
+pub(crate) fn classes_as_quotient_set(classes:&[u64])->Vec<(u64,Vec<u64>)>{
+    classes.iter()
+        .map(|class|(class.trailing_zeros() as u64,elements_of(*class).map(|x|x as u64).collect_vec()))
+        .sorted()
+        .collect()
+}
+/// The quotient `H/θ` as a [`QuotientHyperGroupoid`], for the equivalence `θ` whose classes are given as subsets sorted
+/// by least element.
+fn quotient_hypergroupoid_by_classes(hs:&HyperGroupoid,classes:&[u64])->QuotientHyperGroupoid{
+    let table = quotient_by_classes(hs, classes);
+    let class_elements = classes.iter().map(|class|elements_of(*class).map(|x|x as u64).collect_vec()).collect_vec();
+    let m = classes.len();
+    let hyper_composition = DMatrix::from_fn(m, m, |a,b|elements_of(table.hyper_composition[(a,b)]).map(|i|class_elements[i].clone()).collect_vec());
+    QuotientHyperGroupoid { base_hypergroup: hs.clone(), equivalence_relation: relation_from_classes(classes, &hs.n), hyper_composition, n: m as u64 }
+}
+/// The hypergroupoid `H/θ` on `{0,1,...,m-1}`, where the `i`-th element is the `i`-th class, and `ij` is the set of
+/// classes meeting the product of the classes `i` and `j`.
+pub(crate) fn quotient_by_classes(hs:&HyperGroupoid,classes:&[u64])->HyperGroupoid{
+    let table = hs.product_table();
+    let function = |a:u64,b:u64|{
+        let product = table.mul_by_representation(&classes[a as usize], &classes[b as usize]);
+        classes.iter().positions(|class|class&product!=0).fold(0u64, |acc,i|acc|1<<i)
+    };
+    HyperGroupoid::new_from_function(function, &(classes.len() as u64))
+}
+impl HyperGroupoid {
+/// Classes of `γ*`, the smallest strongly regular equivalence such that the quotient is commutative, as subsets.
+fn gamma_classes(&self)->Vec<u64>{
+    let n = self.n as usize;
+    let blocks = (0..n).cartesian_product(0..n)
+        .map(|(a,b)|self.hyper_composition[(a,b)]|self.hyper_composition[(b,a)])
+        .collect_vec();
+    strongly_regular_closure(self, &blocks)
+}
+/// Returns the relation `γ*`, i.e., the smallest strongly regular equivalence on H such that the quotient is commutative.
+/// On semihypergroups it is the transitive closure of Freni's relation `γ`, where `x γ y` if `x` belongs to a hyperproduct
+/// `z_1...z_n` and `y` to a hyperproduct `z_σ(1)...z_σ(n)` of the same elements in another order. On hypergroups `γ=γ*`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// // S_3: the abelianization is Z_2, the classes are even and odd permutations.
+/// let s3 = [[0,1,2,3,4,5],[1,0,4,5,2,3],[2,3,0,1,5,4],[3,2,5,4,0,1],[4,5,1,0,3,2],[5,4,3,2,1,0]];
+/// let cardinality = 6u64;
+/// let hs = HyperGroupoid::new_from_function(|a,b|1<<s3[a as usize][b as usize], &cardinality);
+/// assert!(hs.is_hypergroup());
+/// assert_eq!(hs.collect_gamma_classes(),vec![(0,vec![0,3,4]),(1,vec![1,2,5])]);
+/// assert_eq!(hs.collect_beta_classes().len(),6);
+/// assert!(hs.gamma_relation().is_equivalence());
+/// ```
+pub fn gamma_relation(&self)->Relation{
+    relation_from_classes(&self.gamma_classes(), &self.n)
+}
+pub fn collect_gamma_classes(&self)->Vec<(u64,Vec<u64>)>{
+    classes_as_quotient_set(&self.gamma_classes())
+}
+/// The quotient `H/γ*`. On hypergroups it is an abelian group.
+pub fn get_abelian_fundamental_group(&self)->QuotientHyperGroupoid{
+    quotient_hypergroupoid_by_classes(self, &self.gamma_classes())
+}
+/// The quotient `H/γ*` represented over `{0,1,...,m-1}`, where `m` is the number of `γ*`-classes, ordered by least element.
+pub fn get_isomorphic_abelian_fundamental_group(&self)->HyperGroupoid{
+    quotient_by_classes(self, &self.gamma_classes())
+}
+}
+impl HyperGroup {
+pub fn gamma_relation(&self)->Relation{
+    self.0.gamma_relation()
+}
+pub fn collect_gamma_classes(&self)->Vec<(u64,Vec<u64>)>{
+    self.0.collect_gamma_classes()
+}
+pub fn get_abelian_fundamental_group(&self)->QuotientHyperGroup{
+    QuotientHyperGroup(self.0.get_abelian_fundamental_group())
+}
+/// Returns the abelian group `H/γ*`, represented over `{0,1,...,m-1}` as in [`HyperGroup::get_isomorphic_fundamental_group`].
+pub fn get_isomorphic_abelian_fundamental_group(&self)->HyperGroup{
+    HyperGroup::new_from_hypergroupiod(&self.0.get_isomorphic_abelian_fundamental_group())
+}
+/// Compute the γ-heart of a hypergroup, i.e., the `γ*`-class of the identity of the abelian group `H/γ*`.
+/// It contains the heart, since `β*` is contained in `γ*`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+/// use std::collections::HashSet;
+///
+/// let cardinality = 3u64;
+/// let tag = 57784611u128;
+/// let hg = HyperGroup::new_from_tag_u128(&tag, &cardinality);
+/// assert_eq!(hg.heart(), HashSet::from([0, 1]));
+/// assert!(hg.heart().is_subset(&hg.gamma_heart()));
+/// ```
+pub fn gamma_heart(&self)->HashSet<u64>{
+    let classes = self.0.gamma_classes();
+    let group = self.get_isomorphic_abelian_fundamental_group();
+    let identity = group.collect_identities();
+    assert!(identity.len()==1);
+    vec_to_set(&elements_of(classes[identity[0].trailing_zeros() as usize]).map(|x|x as u64).collect_vec())
+}
+/// Returns the relation `ε` of the hypergroup, see [`HyperGroupoid::epsilon_relation`].
+pub fn epsilon_relation(&self)->Relation{
+    self.0.epsilon_relation()
+}
+pub fn epsilon_star_relation(&self)->Relation{
+    self.0.epsilon_star_relation()
+}
+pub fn get_epsilon_quotient(&self)->QuotientHyperGroup{
+    QuotientHyperGroup(self.0.get_epsilon_quotient())
+}
+/// Returns the relation `α` of the hypergroup, see [`HyperGroupoid::alpha_relation`].
+pub fn alpha_relation(&self)->Relation{
+    self.0.alpha_relation()
+}
+pub fn alpha_star_relation(&self)->Relation{
+    self.0.alpha_star_relation()
+}
+/// The quotient `H/α*`, which is the abelian group [`HyperGroup::get_abelian_fundamental_group`].
+pub fn get_alpha_quotient(&self)->QuotientHyperGroup{
+    QuotientHyperGroup(self.0.get_alpha_quotient())
+}
+pub fn fundamental_relations_report(&self)->FundamentalRelationsReport{
+    self.0.fundamental_relations_report()
+}
+}
+impl HyperGroupoid {
+/// Returns the relation `ε`, where `x ε y` if `x` and `y` belong to `ab ∪ ba` for some `a,b` in H.
+/// It is reflexive and symmetric, but not transitive in general.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// // S_3: ε* is the conjugacy relation.
+/// let s3 = [[0,1,2,3,4,5],[1,0,4,5,2,3],[2,3,0,1,5,4],[3,2,5,4,0,1],[4,5,1,0,3,2],[5,4,3,2,1,0]];
+/// let cardinality = 6u64;
+/// let hs = HyperGroupoid::new_from_function(|a,b|1<<s3[a as usize][b as usize], &cardinality);
+/// let epsilon = hs.epsilon_relation();
+/// assert!(epsilon.is_reflexive()&&epsilon.is_symmetric());
+/// assert_eq!(epsilon.rel.len(),14);
+/// let epsilon_star = hs.epsilon_star_relation();
+/// assert_eq!(epsilon_star.quotient_set(),vec![(0,vec![0]),(1,vec![1,2,5]),(3,vec![3,4])]);
+/// ```
+pub fn epsilon_relation(&self)->Relation{
+    let n = self.n as usize;
+    let blocks = (0..n).cartesian_product(0..n)
+        .map(|(a,b)|{
+            let block = self.hyper_composition[(a,b)]|self.hyper_composition[(b,a)];
+            (block,block)
+        })
+        .collect_vec();
+    relation_from_pairs_of_subsets(&blocks, &self.n)
+}
+/// Returns `ε*`, the transitive closure of [`HyperGroupoid::epsilon_relation`]. It is contained in `γ*`.
+pub fn epsilon_star_relation(&self)->Relation{
+    relation_from_classes(&transitive_closure_classes(&self.epsilon_relation()), &self.n)
+}
+/// The quotient `H/ε*`. Since `ε*` need not be strongly regular, it is a hypergroupoid.
+pub fn get_epsilon_quotient(&self)->QuotientHyperGroupoid{
+    quotient_hypergroupoid_by_classes(self, &transitive_closure_classes(&self.epsilon_relation()))
+}
+/// The pairs `(A,B)` of hyperproducts where `B` is obtained from `A` by exchanging the factors of some of the products
+/// used to compute it, i.e., the smallest set containing `({z},{z})` for all `z` and such that `(A,B)` and `(C,D)` give
+/// `(AC,BD)` and `(AC,DB)`. It is closed under `(A,B) -> (B,A)`.
+fn collect_alpha_pairs(&self)->Vec<(u64,u64)>{
+    let table = self.product_table();
+    let mut seen:HashSet<(u64,u64)> = HashSet::new();
+    let mut pairs:Vec<(u64,u64)> = self.get_singleton().into_iter().map(|z|(z,z)).filter(|p|seen.insert(*p)).collect();
+    let mut next = 0;
+    while next<pairs.len() {
+        let (c,d) = pairs[next];
+        for i in 0..=next {
+            let (a,b) = pairs[i];
+            let ac = table.mul_by_representation(&a, &c);
+            let ca = table.mul_by_representation(&c, &a);
+            for product in [
+                (ac,table.mul_by_representation(&b, &d)),
+                (ac,table.mul_by_representation(&d, &b)),
+                (ca,table.mul_by_representation(&d, &b)),
+                (ca,table.mul_by_representation(&b, &d))] {
+                if seen.insert(product) {
+                    pairs.push(product);
+                }
+            }
+        }
+        next+=1;
+    }
+    pairs
+}
+/// Returns Freni's relation `α`, where `x α y` if `x` belongs to a hyperproduct of `z_1,...,z_n` and `y` to a hyperproduct of
+/// the same elements in another order. Products and permutations are the ones obtained by exchanging the two factors of some
+/// of the binary products that compute the hyperproduct, so that the relation is also defined on non associative hypergroupoids.
+/// `α` contains `β`, and its transitive closure is `γ*`: it is strongly regular and it puts every `ab ∪ ba` in a single class.
+/// The number of pairs of hyperproducts may grow as `4^n`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// let cardinality = 3u64;
+/// let tag = 57784611u128;
+/// let hs = HyperGroupoid::new_from_tag_u128(&tag, &cardinality);
+/// let alpha = hs.alpha_relation();
+/// assert!(hs.beta_relation().rel.iter().all(|pair|alpha.rel.contains(pair)));
+/// assert_eq!(hs.alpha_star_relation(),hs.gamma_relation());
+/// ```
+pub fn alpha_relation(&self)->Relation{
+    relation_from_pairs_of_subsets(&self.collect_alpha_pairs(), &self.n)
+}
+/// Returns `α*`, the transitive closure of [`HyperGroupoid::alpha_relation`]. It coincides with [`HyperGroupoid::gamma_relation`].
+pub fn alpha_star_relation(&self)->Relation{
+    relation_from_classes(&transitive_closure_classes(&self.alpha_relation()), &self.n)
+}
+pub fn get_alpha_quotient(&self)->QuotientHyperGroupoid{
+    quotient_hypergroupoid_by_classes(self, &transitive_closure_classes(&self.alpha_relation()))
+}
+/// Compares the classes of `β*`, `γ*` and `ε*`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality);
+/// let report = z4.fundamental_relations_report();
+/// assert!(report.all_equal());
+/// // In S_3, β* is the equality, γ* has the classes A_3 and S_3-A_3 and ε* is the conjugacy.
+/// let s3 = [[0,1,2,3,4,5],[1,0,4,5,2,3],[2,3,0,1,5,4],[3,2,5,4,0,1],[4,5,1,0,3,2],[5,4,3,2,1,0]];
+/// let cardinality = 6u64;
+/// let hs = HyperGroupoid::new_from_function(|a,b|1<<s3[a as usize][b as usize], &cardinality);
+/// let report = hs.fundamental_relations_report();
+/// assert!(!report.beta_equals_gamma());
+/// assert!(!report.gamma_equals_epsilon());
+/// assert_eq!(report.epsilon_classes.len(),3);
+/// ```
+pub fn fundamental_relations_report(&self)->FundamentalRelationsReport{
+    FundamentalRelationsReport {
+        beta_classes: classes_as_quotient_set(&strongly_regular_closure(self, &[])),
+        gamma_classes: self.collect_gamma_classes(),
+        epsilon_classes: self.epsilon_star_relation().quotient_set(),
+    }
+}
+}
+/// Classes of `β*`, `γ*` and `ε*` of a hypergroupoid, as returned by [`Relation::quotient_set`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FundamentalRelationsReport{
+    pub beta_classes:Vec<(u64,Vec<u64>)>,
+    pub gamma_classes:Vec<(u64,Vec<u64>)>,
+    pub epsilon_classes:Vec<(u64,Vec<u64>)>,
+}
+impl FundamentalRelationsReport {
+    pub fn beta_equals_gamma(&self)->bool{
+        self.beta_classes==self.gamma_classes
+    }
+    pub fn gamma_equals_epsilon(&self)->bool{
+        self.gamma_classes==self.epsilon_classes
+    }
+    /// Return true if `β* = γ* = ε*`.
+    pub fn all_equal(&self)->bool{
+        self.beta_equals_gamma()&&self.gamma_equals_epsilon()
+    }
+}
+impl Display for FundamentalRelationsReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let classes = |classes:&Vec<(u64,Vec<u64>)>|classes.iter().map(|(_,class)|format!("{:?}",class)).join(" ");
+        writeln!(f, "β*: {}", classes(&self.beta_classes))?;
+        writeln!(f, "γ*: {}", classes(&self.gamma_classes))?;
+        writeln!(f, "ε*: {}", classes(&self.epsilon_classes))?;
+        match (self.beta_equals_gamma(),self.gamma_equals_epsilon()) {
+            (true,true) => write!(f, "β* = γ* = ε*"),
+            (true,false) => write!(f, "β* = γ*, ε* differs"),
+            (false,true) => write!(f, "γ* = ε*, β* differs"),
+            (false,false) => write!(f, "β* ≠ γ* and γ* ≠ ε*"),
+        }
+    }
+}
+impl Relation {
+/// Row `x` of the relation as a subset, i.e., the set of `y` such that `x ρ y`.
+fn related_subsets(&self,cardinality:&u64)->Vec<u64>{
+    let mut rows = vec![0u64;*cardinality as usize];
+    for (x,y) in &self.rel {
+        rows[*x as usize]|=1<<y;
+    }
+    rows
+}
+/// Triples `(x,y,z)` with `x ρ y`, together with the products `xz,yz` and `zx,zy`.
+fn products_of_related_pairs<'a>(&'a self,hs:&'a HyperGroupoid)->impl Iterator<Item = (u64,u64,u64,Side,u64,u64)> + 'a{
+    self.rel.iter()
+        .cartesian_product(0..hs.n)
+        .flat_map(|((x,y),z)|{
+            let (xi,yi,zi) = (*x as usize,*y as usize,z as usize);
+            [
+                (*x,*y,z,Side::Left,hs.hyper_composition[(xi,zi)],hs.hyper_composition[(yi,zi)]),
+                (*x,*y,z,Side::Right,hs.hyper_composition[(zi,xi)],hs.hyper_composition[(zi,yi)]),
+            ]
+        })
+}
+/// Returns the first counterexample to the regularity of the relation with respect to hs, if any.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::diagnostics::{Side, Violation};
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality);
+/// // the congruence modulo {0,2} is strongly regular
+/// let mut rho = z4.gamma_relation();
+/// rho.rel = vec![(0,0),(0,2),(1,1),(1,3),(2,0),(2,2),(3,1),(3,3)];
+/// assert!(rho.check_regularity(&z4).is_ok());
+/// assert!(rho.is_strongly_regular(&z4));
+/// // the classes {0,1} and {2,3} are not compatible with the sum
+/// rho.rel = vec![(0,0),(0,1),(1,0),(1,1),(2,2),(2,3),(3,2),(3,3)];
+/// assert!(!rho.is_regular(&z4));
+/// assert_eq!(rho.check_strong_regularity(&z4).unwrap_err(),
+///     Violation::StrongRegularity { x: 0, y: 1, z: 1, side: Side::Left, product_x: 2, product_y: 4 });
+/// ```
+pub fn check_regularity(&self,hs:&HyperGroupoid)->Result<(),Violation>{
+    let rows = self.related_subsets(&hs.n);
+    //every element of a is related to some element of b
+    let covers = |a:u64,b:u64|elements_of(a).all(|u|rows[u]&b!=0);
+    self.products_of_related_pairs(hs)
+        .find(|(_,_,_,_,product_x,product_y)|!covers(*product_x,*product_y)||!covers(*product_y,*product_x))
+        .map_or(Ok(()), |(x,y,z,side,product_x,product_y)|Err(Violation::Regularity { x, y, z, side, product_x, product_y }))
+}
+pub fn is_regular(&self,hs:&HyperGroupoid)->bool{
+    self.check_regularity(hs).is_ok()
+}
+/// Returns the first counterexample to the strong regularity of the relation with respect to hs, if any.
+pub fn check_strong_regularity(&self,hs:&HyperGroupoid)->Result<(),Violation>{
+    let rows = self.related_subsets(&hs.n);
+    self.products_of_related_pairs(hs)
+        .find(|(_,_,_,_,product_x,product_y)|elements_of(*product_x).any(|u|rows[u]&product_y!=*product_y))
+        .map_or(Ok(()), |(x,y,z,side,product_x,product_y)|Err(Violation::StrongRegularity { x, y, z, side, product_x, product_y }))
+}
+pub fn is_strongly_regular(&self,hs:&HyperGroupoid)->bool{
+    self.check_strong_regularity(hs).is_ok()
+}
+}
+impl HyperGroupoid {
+/// Returns `H/ρ` over `{0,1,...,m-1}`, where the `i`-th element is the `i`-th class of `ρ` ordered by least element, as in
+/// [`Relation::quotient_set`]. It returns `HyperStructureError::NotEquivalence` if `ρ` is not an equivalence on H.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality);
+/// let mut rho = z4.gamma_relation();
+/// rho.rel = vec![(0,0),(0,1),(1,0),(1,1),(2,2),(2,3),(3,2),(3,3)];
+/// // {0,1}+{0,1} = {0,1,2} meets both classes
+/// let quotient = z4.get_quotient(&rho).unwrap();
+/// assert_eq!(quotient.hyper_composition[(0,0)],3);
+/// assert!(z4.get_strongly_regular_quotient(&rho).is_err());
+/// rho.rel = vec![(0,0),(0,2),(2,0),(2,2),(1,1),(1,3),(3,1),(3,3)];
+/// let z2 = z4.get_strongly_regular_quotient(&rho).unwrap();
+/// assert_eq!(z2,HyperGroupoid::new_from_function(|a,b|1<<((a+b)%2), &2));
+/// ```
+pub fn get_quotient(&self,rho:&Relation)->Result<HyperGroupoid,HyperStructureError>{
+    if rho.a!=self.h||rho.b!=self.h||!rho.is_equivalence() {
+        return Err(HyperStructureError::NotEquivalence);
+    }
+    let classes = rho.quotient_set().iter().map(|(_,class)|class.iter().fold(0, |acc,x|acc|1<<x)).collect_vec();
+    Ok(quotient_by_classes(self, &classes))
+}
+/// Returns `H/ρ` as in [`HyperGroupoid::get_quotient`], where every product is a single class. It returns
+/// `HyperStructureError::NotStronglyRegular` with a counterexample if `ρ` is not strongly regular.
+pub fn get_strongly_regular_quotient(&self,rho:&Relation)->Result<HyperGroupoid,HyperStructureError>{
+    let quotient = self.get_quotient(rho)?;
+    rho.check_strong_regularity(self).map_err(HyperStructureError::NotStronglyRegular)?;
+    Ok(quotient)
+}
+}
+impl HyperGroup {
+/// Returns the group `H/ρ`, for a strongly regular equivalence `ρ`. See [`HyperGroupoid::get_strongly_regular_quotient`].
+pub fn get_quotient_group(&self,rho:&Relation)->Result<HyperGroup,HyperStructureError>{
+    Ok(HyperGroup::new_from_hypergroupiod(&self.0.get_strongly_regular_quotient(rho)?))
+}
+}
@@ -0,0 +1,201 @@
+//!
+//! Homomorphisms between hypergroupoids.
+//!
+//! A map `f:H -> K` is
+//! - an inclusion homomorphism if `f(xy) ⊆ f(x)f(y)`,
+//! - a reverse inclusion homomorphism if `f(x)f(y) ⊆ f(xy)`,
+//! - a good (or strong) homomorphism if `f(xy) = f(x)f(y)`,
+//!
+//! for all `x,y` in H. Maps are given by the images of the elements, i.e., `map[x]` is `f(x)` as an integer in `[0,m-1]`,
+//! where `m` is the order of K. Homomorphisms of small orders are found by backtracking: elements are mapped in increasing
+//! order and the condition on `(x,y)` is checked as soon as `x`, `y` and all the elements of `xy` are mapped.
+//!
+use itertools::Itertools;
+use crate::{canonical::relabel_subset, diagnostics::Violation, hs::HyperGroupoid, hypergroups::HyperGroup};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HomomorphismKind {
+    /// `f(xy) ⊆ f(x)f(y)`.
+    Inclusion,
+    /// `f(x)f(y) ⊆ f(xy)`.
+    ReverseInclusion,
+    /// `f(xy) = f(x)f(y)`.
+    Good,
+}
+impl HomomorphismKind {
+    /// Return true if the image `f(xy)` and the product `f(x)f(y)` satisfy the condition.
+    fn holds(&self,image_of_product:u64,product_of_images:u64)->bool{
+        match self {
+            HomomorphismKind::Inclusion => image_of_product&!product_of_images==0,
+            HomomorphismKind::ReverseInclusion => product_of_images&!image_of_product==0,
+            HomomorphismKind::Good => image_of_product==product_of_images,
+        }
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Homomorphism {
+    pub kind:HomomorphismKind,
+    /// `map[x]` is the image of `x`.
+    pub map:Vec<u64>,
+    /// The image `f(H)`, as a subset of K.
+    pub image:u64,
+    /// The preimage of the identities of K, as a subset of H. It is empty if K has no identity.
+    pub kernel:u64,
+}
+impl Homomorphism {
+    fn new(kind:HomomorphismKind,map:Vec<u64>,target:&HyperGroupoid)->Self{
+        let image = map.iter().fold(0, |acc,y|acc|1<<y);
+        let identities = target.collect_identities().iter().fold(0, |acc,e|acc|e);
+        let kernel = map.iter().positions(|y|(identities>>y)&1==1).fold(0, |acc,x|acc|1<<x);
+        Homomorphism { kind, map, image, kernel }
+    }
+    /// Returns the image `f(H)` as a hypergroupoid, if it is closed in K. See [`HyperGroupoid::sub_hypergroupoid`].
+    pub fn image_hypergroupoid(&self,target:&HyperGroupoid)->Option<HyperGroupoid>{
+        target.sub_hypergroupoid(&self.image)
+    }
+    /// Returns the kernel as a hypergroupoid, if it is closed in H. See [`HyperGroupoid::sub_hypergroupoid`].
+    pub fn kernel_hypergroupoid(&self,source:&HyperGroupoid)->Option<HyperGroupoid>{
+        if self.kernel==0 {return None;}
+        source.sub_hypergroupoid(&self.kernel)
+    }
+}
+impl HyperGroupoid {
+/// Returns the restriction of the hypercomposition to a nonempty subset `A` such that `ab ⊆ A` for all `a,b` in `A`.
+/// Elements of `A` are relabelled as `0,1,...,|A|-1`, preserving their order. It returns `None` if `A` is not closed.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality);
+/// let z2 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%2), &2);
+/// assert_eq!(z4.sub_hypergroupoid(&0b0101),Some(z2));
+/// assert_eq!(z4.sub_hypergroupoid(&0b0011),None);
+/// ```
+pub fn sub_hypergroupoid(&self,subset:&u64)->Option<HyperGroupoid>{
+    assert!(*subset>>self.n==0,"{} does not represent a subset of H",subset);
+    if *subset==0||self.mul_by_representation(subset, subset)&!subset!=0 {return None;}
+    let elements = (0..self.n as usize).filter(|x|(subset>>x)&1==1).collect_vec();
+    let mut labelling = vec![0usize;self.n as usize];
+    for (i,x) in elements.iter().enumerate() {
+        labelling[*x] = i;
+    }
+    let function = |a:u64,b:u64|relabel_subset(self.hyper_composition[(elements[a as usize],elements[b as usize])], &labelling);
+    Some(HyperGroupoid::new_from_function(function, &(elements.len() as u64)))
+}
+/// Returns the first pair `(x,y)` for which `map` does not satisfy the condition of `kind`, if any.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::homomorphisms::HomomorphismKind;
+/// use hyperstruc::diagnostics::Violation;
+///
+/// let z4 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%4), &4);
+/// let z2 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%2), &2);
+/// assert!(z4.check_homomorphism(&z2, &[0,1,0,1], HomomorphismKind::Good).is_ok());
+/// let violation = z4.check_homomorphism(&z2, &[0,0,1,1], HomomorphismKind::Good).unwrap_err();
+/// // f(1+1) = f(2) = 1, while f(1)+f(1) = 0
+/// assert_eq!(violation,Violation::Homomorphism { x: 1, y: 1, image_of_product: 2, product_of_images: 1 });
+/// ```
+pub fn check_homomorphism(&self,target:&HyperGroupoid,map:&[u64],kind:HomomorphismKind)->Result<(),Violation>{
+    assert_eq!(map.len(),self.n as usize,"The map must have {} images",self.n);
+    assert!(map.iter().all(|y|*y<target.n),"Images must be elements of the target");
+    let labelling = map.iter().map(|y|*y as usize).collect_vec();
+    (0..self.n).cartesian_product(0..self.n)
+        .find_map(|(x,y)|{
+            let image_of_product = relabel_subset(self.hyper_composition[(x as usize,y as usize)], &labelling);
+            let product_of_images = target.hyper_composition[(map[x as usize] as usize,map[y as usize] as usize)];
+            (!kind.holds(image_of_product, product_of_images)).then_some(Violation::Homomorphism { x, y, image_of_product, product_of_images })
+        })
+        .map_or(Ok(()), Err)
+}
+pub fn is_homomorphism(&self,target:&HyperGroupoid,map:&[u64],kind:HomomorphismKind)->bool{
+    self.check_homomorphism(target, map, kind).is_ok()
+}
+/// Returns all the homomorphisms of the given kind from hs to `target`, with maps in lexicographic order.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::homomorphisms::HomomorphismKind;
+///
+/// let z4 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%4), &4);
+/// let z2 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%2), &2);
+/// let homomorphisms = z4.collect_homomorphisms(&z2, HomomorphismKind::Good);
+/// assert_eq!(homomorphisms.len(),2);
+/// assert_eq!(homomorphisms[1].map,vec![0,1,0,1]);
+/// assert_eq!(homomorphisms[1].kernel,0b0101);
+/// assert_eq!(homomorphisms[1].kernel_hypergroupoid(&z4),Some(z2));
+/// // Every map into the total hypergroup is an inclusion homomorphism.
+/// let total = HyperGroupoid::new_from_function(|_,_|3, &2);
+/// assert_eq!(z4.collect_homomorphisms(&total, HomomorphismKind::Inclusion).len(),16);
+/// ```
+pub fn collect_homomorphisms(&self,target:&HyperGroupoid,kind:HomomorphismKind)->Vec<Homomorphism>{
+    let mut search = HomomorphismSearch::new(self, target, kind);
+    let mut maps = Vec::new();
+    search.extend(0, &mut |map|{maps.push(map.to_vec()); true});
+    maps.into_iter().map(|map|Homomorphism::new(kind, map, target)).collect()
+}
+/// Returns the first homomorphism of the given kind from hs to `target`, in lexicographic order, if any.
+pub fn find_homomorphism(&self,target:&HyperGroupoid,kind:HomomorphismKind)->Option<Homomorphism>{
+    let mut search = HomomorphismSearch::new(self, target, kind);
+    let mut found = None;
+    search.extend(0, &mut |map|{found = Some(map.to_vec()); false});
+    found.map(|map|Homomorphism::new(kind, map, target))
+}
+}
+impl HyperGroup {
+pub fn is_homomorphism(&self,target:&HyperGroup,map:&[u64],kind:HomomorphismKind)->bool{
+    self.0.is_homomorphism(&target.0, map, kind)
+}
+pub fn collect_homomorphisms(&self,target:&HyperGroup,kind:HomomorphismKind)->Vec<Homomorphism>{
+    self.0.collect_homomorphisms(&target.0, kind)
+}
+pub fn find_homomorphism(&self,target:&HyperGroup,kind:HomomorphismKind)->Option<Homomorphism>{
+    self.0.find_homomorphism(&target.0, kind)
+}
+}
+/// Backtracking search of homomorphisms, mapping `0,1,...,n-1` in this order.
+struct HomomorphismSearch<'a> {
+    source:&'a HyperGroupoid,
+    target:&'a HyperGroupoid,
+    kind:HomomorphismKind,
+    /// `pairs[p]` are the pairs `(x,y)` that can be checked as soon as `p` is mapped.
+    pairs:Vec<Vec<(usize,usize)>>,
+    map:Vec<u64>,
+}
+impl<'a> HomomorphismSearch<'a> {
+    fn new(source:&'a HyperGroupoid,target:&'a HyperGroupoid,kind:HomomorphismKind)->Self{
+        let n = source.n as usize;
+        let mut pairs = vec![Vec::new();n];
+        for (x,y) in (0..n).cartesian_product(0..n) {
+            let last = x.max(y).max(63-source.hyper_composition[(x,y)].leading_zeros() as usize);
+            pairs[last].push((x,y));
+        }
+        HomomorphismSearch { source, target, kind, pairs, map: Vec::with_capacity(n) }
+    }
+    fn holds(&self,x:usize,y:usize)->bool{
+        let image_of_product = (0..self.source.n as usize)
+            .filter(|z|(self.source.hyper_composition[(x,y)]>>z)&1==1)
+            .fold(0, |acc,z|acc|1<<self.map[z]);
+        let product_of_images = self.target.hyper_composition[(self.map[x] as usize,self.map[y] as usize)];
+        self.kind.holds(image_of_product, product_of_images)
+    }
+    /// Extends the map on `0,...,p-1` in all possible ways, calling `visitor` on every homomorphism.
+    /// It stops as soon as `visitor` returns false, and then returns false.
+    fn extend(&mut self,p:usize,visitor:&mut dyn FnMut(&[u64])->bool)->bool{
+        if p==self.source.n as usize {
+            return visitor(&self.map);
+        }
+        for q in 0..self.target.n {
+            self.map.push(q);
+            let compatible = self.pairs[p].iter().all(|(x,y)|self.holds(*x, *y));
+            let go_on = !compatible||self.extend(p+1, visitor);
+            self.map.pop();
+            if !go_on {return false;}
+        }
+        true
+    }
+}
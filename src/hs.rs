@@ -1,12 +1,12 @@
 use core::panic;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::{collections::HashSet, fmt::Display, vec};
 extern crate nalgebra as na;
 use itertools::Itertools;
 use nalgebra::DMatrix;
 use permutation::Permutation;
 use rand::Rng;
-use crate::{fuzzy::FuzzySubset, binary_relations::relations::Relation, utilities::{binary_to_n, cartesian_product, from_tag_to_vec, from_tag_u1024_to_vec, get_min_max_u1024, get_subset, n_to_binary_vec, support, permutaton_matrix_from_permutation, representation_permutation_subset, representing_hypergroupoid_u1024, subset_as_u64, vec_to_set, U1024}};
+use crate::{fuzzy::FuzzySubset, binary_relations::relations::Relation, hypergroups::HyperStructureError, utilities::{binary_to_n, cartesian_product, from_tag_to_vec, from_tag_u1024_to_vec, try_from_tag_u1024_to_vec, get_subset, n_to_binary_vec, support, permutaton_matrix_from_permutation, representation_permutation_subset, subset_as_u64, vec_to_set, U1024}};
+use crate::{associativity::filter_hypergroups_u1024, incremental::MutableHyperGroupoid, product_table::{elements_of, ProductTable}};
 #[derive(Debug, Clone,PartialEq)]
 pub struct HyperGroupoid{
     pub h:HashSet<u64>,
@@ -105,15 +105,49 @@ pub fn new_from_function<F>(function:F,cardinality:&u64)->Self
 /// 
 /// 
    pub fn new_from_matrix(matrix:&DMatrix<u64>)->Self{
-    if !matrix.is_square(){panic!("Matrix must be a square matrix!")}
-    if matrix.iter().any(|a|*a==0){panic!("In order to have a hypergroupoid, matrix can't contain zeroes!")}
-    let h:HashSet<u64>= (0..matrix.ncols() as u64).into_iter().collect();
+    HyperGroupoid::try_new_from_matrix(matrix).unwrap_or_else(|error|panic!("{error}"))
+}
+/// Fallible version of [`HyperGroupoid::new_from_matrix`]. It returns an error if the matrix is not square,
+/// if it has more than 64 rows, or if some entry is empty or does not represent a subset of H.
+/// 
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::hypergroups::HyperStructureError;
+/// use nalgebra::DMatrix;
+/// 
+/// let matrix = DMatrix::from_row_slice(2usize, 2usize, &[1,2,0,3]);
+/// match HyperGroupoid::try_new_from_matrix(&matrix) {
+///     Err(HyperStructureError::EmptyEntry { a, b }) => assert_eq!((a,b),(1,0)),
+///     _ => panic!("Entry (1,0) is empty!")
+/// }
+/// let matrix = DMatrix::from_row_slice(2usize, 2usize, &[1,2,4,3]);
+/// assert!(HyperGroupoid::try_new_from_matrix(&matrix).is_err());
+/// let matrix = DMatrix::from_row_slice(2usize, 2usize, &[1,2,1,3]);
+/// assert!(HyperGroupoid::try_new_from_matrix(&matrix).is_ok());
+/// let matrix = DMatrix::from_element(65usize, 65usize, 1u64);
+/// assert!(matches!(HyperGroupoid::try_new_from_matrix(&matrix),
+///     Err(HyperStructureError::TooManyElements { found: 65 })));
+/// ```
+pub fn try_new_from_matrix(matrix:&DMatrix<u64>)->Result<Self,HyperStructureError>{
+    if !matrix.is_square(){
+        return Err(HyperStructureError::NonSquareMatrix { rows: matrix.nrows(), columns: matrix.ncols() });
+    }
     let n=matrix.ncols();
-    HyperGroupoid{
+    if n>64 {
+        return Err(HyperStructureError::TooManyElements { found: n as u64 });
+    }
+    let power_set_cardinality = 1u64.checked_shl(n as u32).unwrap_or(0);
+    for ((a,b),entry) in (0..n).cartesian_product(0..n).map(|(a,b)|((a as u64,b as u64),matrix[(a,b)])){
+        if entry==0 {return Err(HyperStructureError::EmptyEntry { a, b });}
+        if power_set_cardinality!=0&&entry>=power_set_cardinality {return Err(HyperStructureError::EntryOutOfRange { a, b, entry });}
+    }
+    let h:HashSet<u64>= (0..n as u64).collect();
+    Ok(HyperGroupoid{
         h,
         hyper_composition:matrix.clone(),
         n:n as u64
-   }
+   })
 }
 /// Constructs a `HyperGroupoid` from a flattened Cayley table.
 ///
@@ -154,6 +188,37 @@ pub fn new_from_elements(input_array: &Vec<Vec<u64>>, cardinality:&u64)->Self{
     HyperGroupoid::new_from_function(function, &cardinality)
     
 }
+/// Fallible version of [`HyperGroupoid::new_from_elements`]. It returns an error if `input_array` has not length `n^2`,
+/// or if some entry is empty or contains an element outside of H.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::hypergroups::HyperStructureError;
+///
+/// let cardinality = 2u64;
+/// assert!(matches!(HyperGroupoid::try_new_from_elements(&[vec![0],vec![1],vec![1]], &cardinality),
+///     Err(HyperStructureError::CardinalityMismatch { expected: 4, found: 3 })));
+/// assert!(matches!(HyperGroupoid::try_new_from_elements(&[vec![0],vec![1],vec![1],vec![0,70]], &cardinality),
+///     Err(HyperStructureError::ElementOutOfRange { a: 1, b: 1, x: 70 })));
+/// ```
+pub fn try_new_from_elements(input_array: &[Vec<u64>], cardinality:&u64)->Result<Self,HyperStructureError>{
+    if *cardinality>64 {
+        return Err(HyperStructureError::TooManyElements { found: *cardinality });
+    }
+    if input_array.len() as u64!=cardinality.pow(2u32) {
+        return Err(HyperStructureError::CardinalityMismatch { expected: cardinality.pow(2u32), found: input_array.len() as u64 });
+    }
+    for (index,entry) in input_array.iter().enumerate() {
+        let (a,b) = (index as u64/cardinality,index as u64%cardinality);
+        if entry.is_empty() {return Err(HyperStructureError::EmptyEntry { a, b });}
+        if let Some(x) = entry.iter().find(|x|*x>=cardinality) {
+            return Err(HyperStructureError::ElementOutOfRange { a, b, x: *x });
+        }
+    }
+    let function = |a:u64,b:u64| input_array[(cardinality*a+b) as usize].iter().fold(0u64, |acc,x|acc|1<<x);
+    Ok(HyperGroupoid::new_from_function(function, cardinality))
+}
 /// Generate a new hyperstructure given a tag and the cardinality of the set H. If n is the cardinality, then tag is a u128 less than or equal to n^3. 
 /// Its binary representation, divided in groups of n-bits, provide the table of hyperoperation; each group of n-bits corresponds to a subset of H. 
 /// For example, if n=2, then a tag must be less or equal to 2^8. The tag t=185 has binary representation 10111000, divided in groups of 2-bits it is
@@ -183,6 +248,21 @@ pub fn new_from_tag_u128(tag:&u128,cardinality:&u64)->Self{
     let hyper_composition_matrix = DMatrix::from_row_slice(*cardinality as usize, *cardinality as usize, &vector_of_subsets_as_integers);
         HyperGroupoid::new_from_matrix(&hyper_composition_matrix)
 }
+/// Fallible version of [`HyperGroupoid::new_from_tag_u128`]. It returns `HyperStructureError::InvalidTag` if the tag does not
+/// represent a hypergroupoid of the given cardinality.
+/// 
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// 
+/// let cardinality = 2u64;
+/// assert!(HyperGroupoid::try_new_from_tag_u128(&185u128, &cardinality).is_ok());
+/// assert!(HyperGroupoid::try_new_from_tag_u128(&184u128, &cardinality).is_err());
+/// assert!(HyperGroupoid::try_new_from_tag_u128(&1850u128, &cardinality).is_err());
+/// ```
+pub fn try_new_from_tag_u128(tag:&u128,cardinality:&u64)->Result<Self,HyperStructureError>{
+    HyperGroupoid::try_new_from_tag_u1024(&U1024::from(*tag), cardinality)
+}
 /// Generate a new hyperstructure given a tag and the cardinality of the set H. If n is the cardinality, then tag is a u128 less than or equal to n^3. 
 /// Its binary representation, divided in groups of n-bits, provide the table of hyperoperation; each group of n-bits corresponds to a subset of H. 
 /// For example, if n=2, then a tag must be less or equal to 2^8. The tag t=185 has binary representation 10111000, divided in groups of 2-bits it is
@@ -208,6 +288,15 @@ pub fn new_from_tag_u1024(mut tag:&U1024,cardinality:&u64)->Self{
     let hyper_composition_matrix = DMatrix::from_row_slice(*cardinality as usize, *cardinality as usize, &vector_of_subsets_as_integers);
         
     HyperGroupoid::new_from_matrix(&hyper_composition_matrix)
+}
+/// Fallible version of [`HyperGroupoid::new_from_tag_u1024`]. It returns `HyperStructureError::InvalidTag` if the tag does not
+/// represent a hypergroupoid of the given cardinality.
+pub fn try_new_from_tag_u1024(tag:&U1024,cardinality:&u64)->Result<Self,HyperStructureError>{
+    let vector_of_subsets_as_integers=try_from_tag_u1024_to_vec(tag, cardinality)?.iter().map(binary_to_n).collect_vec();
+    if vector_of_subsets_as_integers.len()!=cardinality.pow(2u32) as usize {
+        return Err(HyperStructureError::InvalidTag { cardinality: *cardinality });
+    }
+    HyperGroupoid::try_new_from_matrix(&DMatrix::from_row_slice(*cardinality as usize, *cardinality as usize, &vector_of_subsets_as_integers))
 }
  /// # Example
 /// 
@@ -287,26 +376,21 @@ pub fn isomorphic_hypergroup_from_permutation(&self, sigma:&Permutation)->Self{
 /// The representant of the class is chosen to be the smaller among the tags in the class.
 /// It returns a tuple (representant, class), where class is a vector of tags.
 /// 
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::utilities::U1024;
+/// 
+/// let cardinality = 3u64;
+/// let hs = HyperGroupoid::new_from_tag_u128(&22097724u128,&cardinality);
+/// let (representant,class) = hs.collect_isomorphism_class();
+/// 
+/// assert_eq!(representant,U1024::from(20752868u128));
+/// assert_eq!(class,vec![U1024::from(20752868u128),U1024::from(22097724u128),U1024::from(31958100u128)]);
+/// ```
 pub fn collect_isomorphism_class(&self)->(U1024,Vec<U1024>){
-    let cardinality = self.n as usize;
-    let permutation_vec:Vec<Vec<usize>> = (0..cardinality).permutations(cardinality ).collect();
-    let permutation:Vec<Permutation> = permutation_vec.par_iter()
-        .map(|sigma| 
-                Permutation::oneline(sigma.clone())
-            ).collect();
-    let mut isomorphism_classes:Vec<U1024>=permutation.par_iter()
-        .map(|sigma|
-                self.isomorphic_hypergroup_from_permutation(&sigma).get_integer_tag_u1024()
-            ).collect();
-   isomorphism_classes.sort();
-   isomorphism_classes.dedup();
-   let representant_of_class= isomorphism_classes.clone()
-        .into_iter()
-        .sorted().dedup()
-            .into_iter().min().unwrap();
-        
-       (representant_of_class,isomorphism_classes)
-
+    let isomorphism_class = self.isomorphism_orbit();
+    (isomorphism_class[0],isomorphism_class)
 }
 ///
 /// Return true if hyperstructure is both associative and reproductive.
@@ -338,32 +422,14 @@ pub fn is_hypergroup(&self)->bool{
     self.is_associative()&self.is_reproductive()
 }
 pub fn is_weak_associative(&self)->bool{
-    self.get_singleton().iter().all(|a|
-        self.get_singleton().iter().all(|b|
-            self.get_singleton().iter()
-                .all(|c| {
-                    self.mul_by_representation(
-                    &self.mul_by_representation(a, b),
-                    c)
-                    &
-                    self.mul_by_representation(
-                    a,
-                    &self.mul_by_representation(b, c))!=0
-                })  
-        )
-    )
-
+    ProductTable::new_without_subset_table(self).is_weak_associative()
 }
+///
+/// Return true if the two hypergroupoids are isomorphic, i.e., if they have the same canonical form.
+/// See [`HyperGroupoid::canonical_labelling`].
+/// 
 pub fn is_isomorphic_to(&self,other: &Self)->bool{
-    let total_tag = get_min_max_u1024(&self.n).1;
-    let total_hg = &HyperGroupoid::new_from_tag_u1024(&total_tag, &self.n);
-    if self.hamming_distance_u1024(total_hg)==other.hamming_distance_u1024(total_hg){
-        self.collect_isomorphism_class().1.contains(&other.get_integer_tag_u1024())
-    }
-    else{
-
-        false
-    }
+    self.n==other.n&&self.canonical_labelling().0==other.canonical_labelling().0
 }
 /// Checks whether the structure is commutative.
 /// 
@@ -576,17 +642,7 @@ pub fn collect_inverses_of_x(&self,x:&u64)->Vec<(u64,u64)>{
     .collect_vec()
 }
 pub fn collect_ph(&self)->Vec<u64>{
-    let mut a_current = Vec::new();
-    let  mut a_next=self.get_singleton();
-
-    while a_next!=a_current {
-        a_current = a_next;
-        a_next=a_current.iter().cartesian_product(a_current.iter()).map(|(x,y)|self.mul_by_representation(x, y)).unique().collect();
-        a_next.extend(a_current.iter());
-        a_next.sort();
-        a_next.dedup();
-    }
-    a_current
+    self.product_table().collect_ph()
     /* loop {
         let ph=a;
         a=ph.iter().cartesian_product(ph.iter()).map(|(x,y)|self.mul_by_representation(x, y)).unique().collect();
@@ -712,13 +768,15 @@ pub fn get_subset_from_k(&self,k:&u64)->HashSet<u64>{
 /// let mul=hyperstructure.mul_by_representation(&a,&b);
 /// assert_eq!(ab,mul);
 pub fn mul_by_representation(&self,subset_a:&u64,subset_b:&u64)->u64{
-    support(subset_a, &(self.n)).iter()
-        .cartesian_product(support(subset_b, &(self.n)))
-            .into_iter()
-            .fold(
-            0u64, 
-            |acc,(x,y)|
-                acc|self.hyper_composition[(*x ,y)])
+    let h = if self.n==64 {u64::MAX} else {(1<<self.n)-1};
+    if subset_a&!h!=0||subset_b&!h!=0 {panic!("Subsets {} and {} are not both subsets of H",subset_a,subset_b)}
+    let mut product = 0u64;
+    for x in elements_of(*subset_a) {
+        for y in elements_of(*subset_b) {
+            product|=self.hyper_composition[(x,y)];
+        }
+    }
+    product
 }
 /// # Example
 /// ```
@@ -819,6 +877,7 @@ pub fn is_reproductive(&self)->bool{
                 acc|element)==h)
 }
 /// Return true if hyperstructure is associative, i.e., (xy)z = x(zy) holds for all x in H.
+/// Otherwise it panics, reporting the first failing triple. See [`HyperGroupoid::check_associativity`].
 /// # Example
 /// ```
 /// use hyperstruc::hs::HyperGroupoid;
@@ -832,16 +891,9 @@ pub fn is_reproductive(&self)->bool{
 /// assert!(hyperstructure.assert_associativity())
 ///
 pub fn assert_associativity(&self)->bool{
-    for a in &self.get_singleton(){
-        for b in &self.get_singleton(){
-            for c in &self.get_singleton(){
-                let ab_c=self.mul_by_representation(
-                    &self.mul_by_representation(&a, &b),&c);
-                let a_bc = self.mul_by_representation(&a, &self.mul_by_representation(&b, &c));
-                assert_eq!(ab_c,a_bc,"{a}{b}_{c},{a}_{b}{c}")
-            }
-        }
-    } 
+    if let Err(violation) = self.check_associativity() {
+        panic!("{violation}")
+    }
 true
 }
 /// Return true if hyperstructure is associative, i.e., (xy)z = x(zy) holds for all x in H.
@@ -858,20 +910,7 @@ true
 /// assert!(hyperstructure.is_associative())
 ///
 pub fn is_associative(&self)->bool{
-    self.get_singleton().iter().all(|a|
-        self.get_singleton().iter().all(|b|
-            self.get_singleton().iter()
-                .all(|c| {
-                    self.mul_by_representation(
-                    &self.mul_by_representation(a, b),
-                    c)
-                    ==
-                    self.mul_by_representation(
-                    a,
-                    &self.mul_by_representation(b, c))
-                })  
-        )
-    )
+    ProductTable::new_without_subset_table(self).is_associative()
 }
 pub fn get_singleton(&self)->Vec<u64>{
     (0..self.n).into_iter().map(|i|1<<i).collect()
@@ -957,17 +996,19 @@ pub fn circumference_radius_d(tag:&U1024,d:&usize,cardinality:&u64)->Vec<U1024>{
 /// 
 /// 
 pub fn circumference_radius_d_filtered(tag:&U1024,d:&usize,cardinality:&u64)->Vec<U1024>{
-    circumference_radius_d(tag, d, cardinality).par_iter().filter(|x|representing_hypergroupoid_u1024(&x, cardinality)&&HyperGroupoid::new_from_tag_u1024(x, cardinality).is_hypergroup())
-    .map(|x|*x).collect::<Vec<U1024>>()
+    filter_hypergroups_u1024(&circumference_radius_d(tag, d, cardinality), cardinality)
 }
 ///
 /// Collects all binary strings that differ by 1 bits from the tag binary string and 
 /// filter them to take only those representing hypergroups.
 /// The distance is intended to be the Hamming's distance. 
-/// 
+/// Bits are flipped one at a time on a [`MutableHyperGroupoid`], which only re-checks the triples reading the edited entry.
 /// 
 pub fn hg_in_circumference_radius_one(tag:&U1024,cardinality:&u64)->Vec<U1024>{
-    circumference_radius_d_filtered(tag, &1usize, cardinality)
+    match MutableHyperGroupoid::new_from_tag_u1024(tag, cardinality) {
+        Ok(hs) => hs.collect_hypergroups_at_distance_one(),
+        Err(_) => circumference_radius_d_filtered(tag, &1usize, cardinality)
+    }
 }
 
 /*QUOTIENT HYPERGROUPOIDS */
@@ -979,15 +1020,26 @@ pub struct QuotientHyperGroupoid{
     pub n:u64
 }
 impl QuotientHyperGroupoid {
+    /// Fallible version of [`QuotientHyperGroupoid::new_from_equivalence_relation`]. It returns `HyperStructureError::NotEquivalence`
+    /// if the relation is not an equivalence on H.
+    pub fn try_new_from_equivalence_relation(base_hypergroupoid:&HyperGroupoid,equivalence:&Relation)->Result<Self,HyperStructureError>{
+        if equivalence.a!=base_hypergroupoid.h||equivalence.b!=base_hypergroupoid.h||!equivalence.is_equivalence() {
+            return Err(HyperStructureError::NotEquivalence);
+        }
+        Ok(QuotientHyperGroupoid::new_from_equivalence_relation(base_hypergroupoid, equivalence))
+    }
+    /// Builds `H/ρ`, where the product of the classes `ρ(a)` and `ρ(b)` is the set of the classes `ρ(z)` with `z` in `ρ(a)ρ(b)`.
+    /// It panics if the relation is not an equivalence.
     pub fn new_from_equivalence_relation(base_hypergroupoid:&HyperGroupoid,equivalence:&Relation)->Self{
         assert!(equivalence.is_equivalence(),"The input relation is not an equivalence! The quotinet is not defined!");
-        let classes = base_hypergroupoid.beta_relation().quotient_set();
+        let classes = equivalence.quotient_set();
         let n = classes.len() as u64;
-        let representants:Vec<u64> = classes.iter().map(|x|x.0).collect();
+        let subsets:Vec<u64> = classes.iter().map(|(_,class)|class.iter().fold(0, |acc,x|acc|1<<x)).collect();
+        //ρ(a)ρ(b) is the set of classes of the elements of the product of the classes, not only of their representants
         let function  = |a:usize,b:usize| 
             support(
                 &base_hypergroupoid.mul_by_representation(
-                    &(1<<representants[a]), &(1<<representants[b])),&base_hypergroupoid.n).iter()
+                    &subsets[a], &subsets[b]),&base_hypergroupoid.n).iter()
                     .map(|x|equivalence.get_class(&(*x as u64)).1)
                     .sorted()
                     .unique()
@@ -1009,7 +1061,7 @@ impl QuotientHyperGroupoid {
 }
 impl Display for QuotientHyperGroupoid{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let representants = self.base_hypergroup.collect_beta_classes().iter().map(|x|x.0).collect_vec();
+        let representants = self.equivalence_relation.quotient_set().iter().map(|x|x.0).collect_vec();
         let table:DMatrix<String>=DMatrix::from_iterator(self.n as usize, self.n as usize, 
             self.hyper_composition.iter().map(|x| format!("{:?}", x.concat())));
         
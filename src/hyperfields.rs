@@ -0,0 +1,11 @@
+//!
+//! Hyperfields of orders 2 to 5 up to isomorphism, as written by [`crate::enumeration::write_hyperfield_catalogue`]
+//! with fields of order at most 64. Every entry is `(additive tag, multiplicative tag, quotient_of)`, see
+//! [`crate::enumeration::HyperfieldClass`].
+//!
+use crate::enumeration::HyperfieldEntry;
+
+pub const HYPERFIELDS_2:[HyperfieldEntry;2] = [(105, 86, Some((2, 1))), (107, 86, Some((3, 2)))];
+pub const HYPERFIELDS_3:[HyperfieldEntry;5] = [(22097724, 19179618, None), (22102794, 19179618, Some((3, 1))), (22109491, 19179618, Some((5, 2))), (22114110, 19179618, Some((7, 3))), (22117687, 19179618, Some((9, 4)))];
+pub const HYPERFIELDS_4:[HyperfieldEntry;7] = [(1317339743034442785, 1229784274039871524, Some((4, 1))), (1317344312921853027, 1229784274039871524, Some((7, 2))), (1317346511949302891, 1229784274039871524, None), (1317352825482414791, 1229784274039871524, None), (1317353392552447719, 1229784274039871524, Some((13, 4))), (1317355024509864655, 1229784274039871524, None), (1317355591579897583, 1229784274039871524, Some((19, 6)))];
+pub const HYPERFIELDS_5:[HyperfieldEntry;27] = [(1417841925292691725749839532764680976, 1372106319592616773546688075483586690, None), (1417841925317588041175388494854979344, 1372106319592616780457461703683606664, None), (1417841927238345160380081664713697489, 1372106319592616773546688075483586690, None), (1417841927238345160380081664713697489, 1372106319592616780457461703683606664, Some((9, 2))), (1417841928143679191268231137896694914, 1372106319592616780457461703683606664, Some((5, 1))), (1417841930268630401600777231549856536, 1372106319592616773546688075483586690, None), (1417841930888621049005743464826896274, 1372106319592616780457461703683606664, None), (1417841931211102336427344444934149112, 1372106319592616773546688075483586690, None), (1417841935221108373905174769905008404, 1372106319592616780457461703683606664, None), (1417841938304172040334407878061141894, 1372106319592616780457461703683606664, Some((13, 3))), (1417841939707810623807225003970034119, 1372106319592616780457461703683606664, Some((17, 4))), (1417841940792141381735529739876925334, 1372106319592616780457461703683606664, None), (1417841941100606649238314251034033116, 1372106319592616773546688075483586690, None), (1417841942183690706989671580785605079, 1372106319592616780457461703683606664, Some((25, 6))), (1417841951005298101150834255572467674, 1372106319592616780457461703683606664, Some((29, 7))), (1417841958238301109271650919393491182, 1372106319592616773546688075483586690, None), (1417841958420849092479498668806713294, 1372106319592616780457461703683606664, None), (1417841958716581686173535337806648079, 1372106319592616773546688075483586690, None), (1417841958716581686173535337806648079, 1372106319592616780457461703683606664, None), (1417841959669669465315734105793461199, 1372106319592616773546688075483586690, None), (1417841959669669465315734105793461199, 1372106319592616780457461703683606664, Some((41, 10))), (1417841960907647287027770690569763806, 1372106319592616773546688075483586690, None), (1417841960908818433880620530622496734, 1372106319592616780457461703683606664, Some((37, 9))), (1417841961192461769355981914622219039, 1372106319592616773546688075483586690, None), (1417841961192461769355981914622219039, 1372106319592616780457461703683606664, None), (1417841962145549548498180682609032159, 1372106319592616773546688075483586690, None), (1417841962145549548498180682609032159, 1372106319592616780457461703683606664, Some((49, 12)))];
@@ -5,22 +5,130 @@ use nalgebra::DMatrix;
 use num_rational::Rational64;
 use permutation::Permutation;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use crate::{fuzzy::FuzzySubset, hs::{circumference_radius_d_filtered, hg_in_circumference_radius_one, HyperGroupoid}, quotient_hg::QuotientHyperGroup, binary_relations::relations::Relation, utilities::{support, get_complement_subset, vec_to_set, U1024}};
+use crate::{diagnostics::Violation, fuzzy::FuzzySubset, hs::{circumference_radius_d_filtered, hg_in_circumference_radius_one, HyperGroupoid}, quotient_hg::QuotientHyperGroup, binary_relations::relations::Relation, utilities::{get_complement_subset, vec_to_set, U1024}};
 #[derive(Debug, Clone)]
 pub enum HyperStructureError {
     NotHypergroup,
+    /// The hypercomposition table has `rows` rows and `columns` columns.
+    NonSquareMatrix{rows:usize,columns:usize},
+    /// The entry `ab` of the table is the empty set.
+    EmptyEntry{a:u64,b:u64},
+    /// The entry `ab` of the table is an integer that does not represent a subset of H.
+    EntryOutOfRange{a:u64,b:u64,entry:u64},
+    /// The entry `ab` of the table lists `x`, which is not an element of H.
+    ElementOutOfRange{a:u64,b:u64,x:u64},
+    /// The structure would have `found` elements, more than the 64 whose subsets are represented by a `u64`.
+    TooManyElements{found:u64},
+    /// The tag does not represent a hypergroupoid of the given cardinality.
+    InvalidTag{cardinality:u64},
+    NotAssociative(Violation),
+    NotReproductive(Violation),
+    NotEquivalence,
+    /// The equivalence is not strongly regular with respect to the hypergroupoid.
+    NotStronglyRegular(Violation),
+    /// The structure has `identities` scalar identities instead of exactly one.
+    NoUniqueIdentity{identities:usize},
+    /// A structure of order `expected` was required, but one of order `found` was given.
+    CardinalityMismatch{expected:u64,found:u64},
+    /// The subset represented by `subset` is not a closed subhypergroup.
+    NotClosedSubHypergroup{subset:u64},
+    /// The double cosets of the subhypergroup represented by `subset` do not partition H.
+    OverlappingDoubleCosets{subset:u64},
+    /// The subset represented by `subset` is not a normal subhypergroup.
+    NotNormalSubHypergroup{subset:u64},
+    /// The element `x` has not exactly one two-sided inverse: `inverses` is the subset of its inverses.
+    NoUniqueInverse{x:u64,inverses:u64},
+    NotReversible(Violation),
+    /// The subset represented by `subset` is not a subpolygroup.
+    NotSubPolygroup{subset:u64},
+    /// The additive hypergroup is not canonical.
+    NotCanonical,
+    /// The product `ab` is the subset `product`, which is not a singleton.
+    NotSingleValued{a:u64,b:u64,product:u64},
+    /// The product of `x` and zero is not zero.
+    NotAbsorbing{x:u64},
+    /// The product of the zero of the ring and the element `m` of the module is not zero.
+    NotAnnihilatedByZero{m:u64},
+    NotDistributive(Violation),
+    NotPrimePower{order:u64},
+    NotMultiplicativeSubgroup,
+    NotHyperfield,
+    /// The coefficient of degree `degree` is `coefficient`, which is not an element of the hyperfield.
+    CoefficientOutOfRange{degree:usize,coefficient:u64},
+    /// The subset represented by `subset` is not a submodule.
+    NotSubModule{subset:u64},
 }
 impl fmt::Display for HyperStructureError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             HyperStructureError::NotHypergroup => write!(f, "The structure is not a valid hypergroup."),
+            HyperStructureError::NonSquareMatrix { rows, columns } => write!(f, "Matrix must be a square matrix! It has {rows} rows and {columns} columns."),
+            HyperStructureError::EmptyEntry { a, b } => write!(f, "In order to have a hypergroupoid, matrix can't contain zeroes! Entry ({a},{b}) is empty."),
+            HyperStructureError::EntryOutOfRange { a, b, entry } => write!(f, "Entry ({a},{b}) is {entry}, which does not represent a subset of H."),
+            HyperStructureError::ElementOutOfRange { a, b, x } => write!(f, "Entry ({a},{b}) contains {x}, which is not an element of H."),
+            HyperStructureError::TooManyElements { found } => write!(f, "The structure has {found} elements, more than 64."),
+            HyperStructureError::InvalidTag { cardinality } => write!(f, "Tag does not represent a hypergroupoid of cardinality {cardinality}."),
+            HyperStructureError::NotAssociative(violation) => write!(f, "The structure is not a valid hypergroup. {violation}"),
+            HyperStructureError::NotReproductive(violation) => write!(f, "The structure is not a valid hypergroup. {violation}"),
+            HyperStructureError::NotEquivalence => write!(f, "The input relation is not an equivalence!"),
+            HyperStructureError::NotStronglyRegular(violation) => write!(f, "The equivalence is not strongly regular. {violation}"),
+            HyperStructureError::NoUniqueIdentity { identities } => write!(f, "Not representing a unital magma. {identities} scalar identities found!"),
+            HyperStructureError::CardinalityMismatch { expected, found } => write!(f, "Expected a structure of order {expected}, found one of order {found}."),
+            HyperStructureError::NotClosedSubHypergroup { subset } => write!(f, "The subset {subset} is not a closed subhypergroup."),
+            HyperStructureError::OverlappingDoubleCosets { subset } => write!(f, "The double cosets of {subset} do not partition H."),
+            HyperStructureError::NotNormalSubHypergroup { subset } => write!(f, "The subset {subset} is not a normal subhypergroup."),
+            HyperStructureError::NoUniqueInverse { x, inverses } => write!(f, "Not representing a polygroup. {x} has {} inverses!",inverses.count_ones()),
+            HyperStructureError::NotReversible(violation) => write!(f, "Not representing a polygroup. {violation}"),
+            HyperStructureError::NotSubPolygroup { subset } => write!(f, "The subset {subset} is not a subpolygroup."),
+            HyperStructureError::NotCanonical => write!(f, "The additive hypergroup is not canonical."),
+            HyperStructureError::NotSingleValued { a, b, product } => write!(f, "The product ({a},{b}) is {product}, which is not a singleton."),
+            HyperStructureError::NotAbsorbing { x } => write!(f, "Zero is not absorbing: the product of {x} and zero is not zero."),
+            HyperStructureError::NotAnnihilatedByZero { m } => write!(f, "Zero is not absorbing: the product of zero and {m} is not zero."),
+            HyperStructureError::NotDistributive(violation) => write!(f, "Not representing a hyperring. {violation}"),
+            HyperStructureError::NotPrimePower { order } => write!(f, "There is no field of order {order}, which is not a prime power."),
+            HyperStructureError::NotMultiplicativeSubgroup => write!(f, "The input subset is not a subgroup of the multiplicative group of the field."),
+            HyperStructureError::NotHyperfield => write!(f, "The hyperring is not a hyperfield."),
+            HyperStructureError::CoefficientOutOfRange { degree, coefficient } => write!(f, "The coefficient of degree {degree} is {coefficient}, which is not an element of the hyperfield."),
+            HyperStructureError::NotSubModule { subset } => write!(f, "The subset {subset} is not a submodule."),
         }
     }
 }
+impl std::error::Error for HyperStructureError {}
 #[derive(Debug, Clone,PartialEq)]
 pub struct HyperGroup(pub HyperGroupoid);
 
 impl HyperGroup {
+/// Returns the hypergroup defined by `h`, or the first reason why `h` is not a hypergroup.
+/// 
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::hypergroups::{HyperGroup, HyperStructureError};
+/// use nalgebra::DMatrix;
+/// 
+/// let hs = HyperGroupoid::new_from_matrix(&DMatrix::from_row_slice(2, 2, &[1,1,2,2]));
+/// match HyperGroup::try_new_from_hypergroupoid(&hs) {
+///     Err(HyperStructureError::NotReproductive(violation)) => println!("{violation}"),
+///     _ => panic!("It is not reproductive!")
+/// }
+/// ```
+    pub fn try_new_from_hypergroupoid(h:&HyperGroupoid)->Result<Self,HyperStructureError>{
+        h.check_associativity().map_err(HyperStructureError::NotAssociative)?;
+        h.check_reproductivity().map_err(HyperStructureError::NotReproductive)?;
+        Ok(HyperGroup(h.clone()))
+    }
+    pub fn try_new_from_matrix(matrix:&DMatrix<u64>)->Result<Self,HyperStructureError>{
+        HyperGroup::try_new_from_hypergroupoid(&HyperGroupoid::try_new_from_matrix(matrix)?)
+    }
+    pub fn try_new_from_tag_u128(tag:&u128,cardinality:&u64)->Result<Self,HyperStructureError>{
+        HyperGroup::try_new_from_hypergroupoid(&HyperGroupoid::try_new_from_tag_u128(tag, cardinality)?)
+    }
+    pub fn try_new_from_tag_u1024(tag:&U1024,cardinality:&u64)->Result<Self,HyperStructureError>{
+        HyperGroup::try_new_from_hypergroupoid(&HyperGroupoid::try_new_from_tag_u1024(tag, cardinality)?)
+    }
+    pub fn try_new_from_elements(input_array: &[Vec<u64>], cardinality:&u64)->Result<Self,HyperStructureError>{
+        HyperGroup::try_new_from_hypergroupoid(&HyperGroupoid::try_new_from_elements(input_array, cardinality)?)
+    }
     pub fn new_from_hypergroupiod(h:&HyperGroupoid)-> Self {
         assert!(h.is_hypergroup());
         HyperGroup(h.clone())
@@ -145,26 +253,13 @@ pub fn get_integer_tag_u1024(&self)->U1024{
 /// let function = |a:u64,b:u64| 1<<a|1<<b;
 /// let hg = match HyperGroup::new_from_function(function, &cardinality) {
 /// Ok(hg)=>hg,
-/// Err(HyperStructureError::NotHypergroup)=> panic!("Not hg")
+/// Err(error)=> panic!("{error}")
 /// };
 /// assert!(hg.is_transposition())
 /// 
 /// 
 pub fn is_transposition(&self)->bool {
-    for a in self.get_singleton(){
-        for b in self.get_singleton(){
-            for c in self.get_singleton(){
-                for d in self.get_singleton(){
-                    if self.left_division(&a, &b)&self.right_division(&c, &d)!=0{
-                        if self.mul_by_representation(&a, &d)&self.mul_by_representation(&b, &c)==0{
-                            return  false;
-                        }
-                    }
-                }
-            }
-        }
-    }
-    true
+    self.product_table().is_transposition()
 }
 pub fn is_commutative(&self)->bool{
     self.0.is_commutative()
@@ -179,23 +274,22 @@ pub fn is_join_space(&self)->bool{
 pub fn is_canonical(&self)->bool{
     self.is_commutative()&&self.is_quasicanonical()
 }
+/// Return true if the nonempty subset `K`, represented by `k`, is a subhypergroup, i.e., if `xK = K = Kx` for all `x` in `K`.
+/// See [`HyperGroup::check_sub_hypergroup`] for the first failing element.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperGroup::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality).unwrap();
+/// assert!(z4.is_sub_hypergroup(&0b0101));
+/// assert!(!z4.is_sub_hypergroup(&0b0011));
+/// assert!(!z4.is_sub_hypergroup(&0));
+/// ```
 pub fn is_sub_hypergroup(&self,k:&u64)->bool{
-    let power_set_cardinality = 1<<self.cardinality();
-    assert!(*k<power_set_cardinality,"K is not a subset of H!");
-    let ones:Vec<usize> = support(k, &self.0.n).iter().map(|x|*x as usize).collect();
-    let sub_matrix = self.0.hyper_composition.select_columns(&ones).select_rows(&ones);
-        sub_matrix.row_iter().all(
-            |row|
-                row
-                    .iter()
-                    .fold(0,|acc,x|acc|x)==*k)
-        &&
-        sub_matrix.row_iter().all(
-            |row|
-                row
-                    .iter()
-                    .fold(0,|acc,x|acc|x)==*k)
-    }
+    *k!=0&&self.check_sub_hypergroup(k).is_ok()
+}
 /// 
 /// 
 /// Returns the vector of all identities of the hypergroup. They are elements in H and are represented as integers in [0,2^n-1].
@@ -254,8 +348,8 @@ pub fn get_corsini_fuzzysubset(&self)->FuzzySubset{
         self.0.get_corsini_fuzzysubset()
     }
 pub fn collect_proper_subhypergroups(&self)->Vec<u64> {
-    let power_set_cardinality: u64 =1<<self.cardinality()-1;
-    (1..power_set_cardinality).into_iter().filter(|x|self.is_sub_hypergroup(x)).collect_vec()
+    let h: u64 =(1<<self.cardinality())-1;
+    (1..h).filter(|x|self.is_sub_hypergroup(x)).collect_vec()
     }
 pub fn subhypergroup_is_closed(&self,subset_k:&u64)->bool {
     if !self.is_sub_hypergroup(&subset_k) {return false;}
@@ -290,7 +384,7 @@ pub fn subhypergroup_is_reflexive(&self,subset_k:&u64)->bool {
 /// let function = |a:u64,b:u64| 1<<a|1<<b;
 /// let hg = match HyperGroup::new_from_function(function, &cardinality) {
 /// Ok(hg)=>hg,
-/// Err(HyperStructureError::NotHypergroup)=> panic!("Not hg")
+/// Err(error)=> panic!("{error}")
 /// };
 /// let subhypergroup = 10u64; //This is the subset K = {1,3} of H = {0,1,2,3,4}
 /// assert!(hg.is_sub_hypergroup(&subhypergroup));
@@ -302,9 +396,9 @@ pub fn subhypergroup_is_normal(&self,subset_k:&u64)->bool {
     self.get_singleton()
         .iter()
         .all(|x|
-            self.mul_by_representation(&subset_k, x)
+            self.mul_by_representation(x, subset_k)
             ==
-            self.mul_by_representation(&subset_k, x))
+            self.mul_by_representation(subset_k, x))
 }
 pub fn subhypergroup_is_right_invertible(&self,subset_k:&u64)->bool {
     if !self.is_sub_hypergroup(&subset_k) {return false;}
@@ -506,8 +600,8 @@ pub fn heart(&self)->HashSet<u64>{
     assert!(identity.len()==1);
     let identity  = identity[0];//This is the integer which identifies the singleton {identity}. 
                                     //The corresponding element is given by identity.trailing_zeros().
-    let identity=identity.trailing_zeros() as u64;
-    let beta_identity = self.beta_relation().get_class(&identity);
+    //classes of the isomorphic fundamental group are ordered as the β-classes
+    let beta_identity = &self.collect_beta_classes()[identity.trailing_zeros() as usize];
     vec_to_set(&beta_identity.1)
 
 
@@ -617,12 +711,12 @@ pub fn find_nodes_not_enumerated(leafs:&Vec<U1024>,enumeration:&Vec<U1024>,radiu
     
 }
 pub fn collect_classes_with_respect_to_cardinality(classes:&Vec<(U1024,Vec<U1024>)>,cardinality:&u64)->(Vec<Vec<(U1024,Vec<U1024>)>>,Vec<usize>){
-    let permut_vec:Vec<Vec<usize>> = (0..*cardinality as usize).permutations(*cardinality as usize).collect();
+    let permutations_number:usize = (1..=*cardinality as usize).product();
     let mut enumeration_classes:Vec<usize>=Vec::new();
     let mut c_k:Vec<(U1024,Vec<U1024>)>;
     let mut collected_classes:Vec<Vec<(U1024,Vec<U1024>)>>=Vec::new();
 
-    for k in 1..=permut_vec.len(){
+    for k in 1..=permutations_number{
         c_k=classes.iter().filter(|y|(*y.1).len()==k).into_iter().map(|x|x.clone()).collect_vec();
         enumeration_classes.push(c_k.len());
         collected_classes.push(c_k);
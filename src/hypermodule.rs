@@ -0,0 +1,290 @@
+//!
+//! Hypermodules and hypervector spaces over finite hyperrings.
+//!
+//! A hypermodule over a hyperring R is a canonical hypergroup `(M,+)` with zero `0` and an action `R × M -> P*(M)`,
+//! extended to subsets by union, such that for all `r,s` in R and `m,n` in M:
+//! - `r(m+n) = rm+rn` and `(r+s)m = rm+sm`;
+//! - `(rs)m = r(sm)`;
+//! - `0m = {0}` and `r0 = {0}`.
+//!
+//! It is unital if R has a unit `1` with `1m = {m}`, and a hypervector space if moreover R is a hyperfield.
+//!
+//! An equivalence `ρ` on M is strongly regular if it is strongly regular with respect to `+` and if `x ρ y` implies that
+//! all the elements of `rx ∪ ry` are related, for all `r`. Then `M/ρ` is a hypermodule over R whose addition and action
+//! are single-valued. The fundamental relation `ε*` is the smallest strongly regular equivalence such that `rm ∪ sm` lies
+//! in a single class whenever `r Γ* s`, so that the action of R on `M/ε*` factors through the fundamental ring `R/Γ*`.
+//! It is computed by [`strongly_regular_closure_of_operations`], seeing the action of every `r` as the hypercomposition
+//! `x∘y = rx` on M.
+//!
+use std::fmt::{self, Display};
+use itertools::Itertools;
+use nalgebra::DMatrix;
+use crate::{binary_relations::relations::Relation, canonical::relabel_subset, diagnostics::{Side, Violation}, fundamental_relations::strongly_regular_closure_of_operations, hs::HyperGroupoid, hypergroups::{HyperGroup, HyperStructureError}, hyperring::HyperRing, polygroup::Polygroup, product_table::elements_of};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyperModule {
+    pub ring:HyperRing,
+    pub addition:HyperGroup,
+    /// The entry `(r,m)` is the subset `rm` of M.
+    pub action:DMatrix<u64>,
+    /// The zero of M, as a singleton.
+    pub zero:u64,
+    /// `negation[m]` is the opposite of `m`, as an integer in `[0,n-1]`.
+    pub negation:Vec<u64>,
+}
+impl HyperModule {
+/// Returns the hypermodule with the given addition and action, or the first reason why they do not define one. The
+/// violations of the axioms are reported as `NotAnnihilatedByZero { m }` if `0m` is not zero, as `NotAbsorbing { x }` if
+/// `x0` is not zero, for `x` in R, as `NotDistributive`
+/// and as `NotAssociative`, see [`HyperModule::check_distributivity`] and [`HyperModule::check_action_associativity`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperStructureError;
+/// use hyperstruc::hypermodule::HyperModule;
+/// use hyperstruc::hyperring::HyperRing;
+/// use nalgebra::DMatrix;
+///
+/// // The Krasner hyperfield acts on itself with 1·1 = {0,1}.
+/// let krasner = HyperRing::krasner();
+/// let action = DMatrix::from_row_slice(2, 2, &[1,1,1,3]);
+/// let module = HyperModule::new(&krasner, &krasner.addition, &action).unwrap();
+/// assert!(!module.is_unital());
+/// let action = DMatrix::from_row_slice(2, 2, &[1,2,1,2]);
+/// match HyperModule::new(&krasner, &krasner.addition, &action) {
+///     Err(HyperStructureError::NotAnnihilatedByZero { m }) => assert_eq!(m,1),
+///     _ => panic!("0·1 is not zero!")
+/// }
+/// let action = DMatrix::from_row_slice(2, 2, &[1,1,2,2]);
+/// match HyperModule::new(&krasner, &krasner.addition, &action) {
+///     Err(HyperStructureError::NotAbsorbing { x }) => assert_eq!(x,1),
+///     _ => panic!("1·0 is not zero!")
+/// }
+/// ```
+pub fn new(ring:&HyperRing,addition:&HyperGroup,action:&DMatrix<u64>)->Result<Self,HyperStructureError>{
+    if action.nrows() as u64!=ring.cardinality() {
+        return Err(HyperStructureError::CardinalityMismatch { expected: ring.cardinality(), found: action.nrows() as u64 });
+    }
+    if action.ncols() as u64!=addition.cardinality() {
+        return Err(HyperStructureError::CardinalityMismatch { expected: addition.cardinality(), found: action.ncols() as u64 });
+    }
+    let n = addition.cardinality();
+    for ((r,m),entry) in (0..action.nrows() as u64).cartesian_product(0..n).map(|(r,m)|((r,m),action[(r as usize,m as usize)])) {
+        if entry==0 {
+            return Err(HyperStructureError::EmptyEntry { a: r, b: m });
+        }
+        if entry>>n!=0 {
+            return Err(HyperStructureError::EntryOutOfRange { a: r, b: m, entry });
+        }
+    }
+    if !addition.is_canonical() {
+        return Err(HyperStructureError::NotCanonical);
+    }
+    let Polygroup { identity: zero, inversion: negation, .. } = Polygroup::new_from_hypergroup(addition)?;
+    let module = HyperModule { ring: ring.clone(), addition: addition.clone(), action: action.clone(), zero, negation };
+    if let Some(m) = (0..n).find(|m|module.act_by_representation(&ring.zero, &(1<<m))!=zero) {
+        return Err(HyperStructureError::NotAnnihilatedByZero { m });
+    }
+    if let Some(x) = (0..ring.cardinality()).find(|r|module.act_by_representation(&(1<<r), &zero)!=zero) {
+        return Err(HyperStructureError::NotAbsorbing { x });
+    }
+    module.check_distributivity().map_err(HyperStructureError::NotDistributive)?;
+    module.check_action_associativity().map_err(HyperStructureError::NotAssociative)?;
+    Ok(module)
+}
+/// The addition and the action are given as functions, both returning subsets. See [`HyperModule::new`].
+pub fn new_from_functions<F,G>(ring:&HyperRing,addition:F,action:G,cardinality:&u64)->Result<Self,HyperStructureError>
+    where F: Fn(u64,u64) -> u64, G: Fn(u64,u64) -> u64
+{
+    let n = *cardinality as usize;
+    let addition = HyperGroup::try_new_from_matrix(&DMatrix::from_fn(n, n, |a,b|addition(a as u64, b as u64)))?;
+    let action = DMatrix::from_fn(ring.cardinality() as usize, n, |r,m|action(r as u64, m as u64));
+    HyperModule::new(ring, &addition, &action)
+}
+/// Returns R as a hypermodule over itself, acting by left multiplication. Its submodules are the left hyperideals.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypermodule::HyperModule;
+/// use hyperstruc::hyperring::HyperRing;
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperRing::new_from_functions(|a,b|1<<((a+b)%cardinality), |a,b|1<<((a*b)%cardinality), &cardinality).unwrap();
+/// let module = HyperModule::new_regular(&z4);
+/// assert!(module.is_unital()&&!module.is_hypervector_space());
+/// assert_eq!(module.collect_submodules(),z4.collect_hyperideals());
+/// assert!(HyperModule::new_regular(&HyperRing::sign()).is_hypervector_space());
+/// ```
+pub fn new_regular(ring:&HyperRing)->Self{
+    HyperModule::new(ring, &ring.addition, &ring.multiplication.hyper_composition).expect("A hyperring is a hypermodule over itself")
+}
+pub fn cardinality(&self)->u64{
+    self.addition.cardinality()
+}
+pub fn add_by_representation(&self,subset_a:&u64,subset_b:&u64)->u64{
+    self.addition.mul_by_representation(subset_a, subset_b)
+}
+/// Returns `AB`, the union of `rm` for `r` in the subset `A` of R and `m` in the subset `B` of M.
+pub fn act_by_representation(&self,scalars:&u64,subset:&u64)->u64{
+    elements_of(*scalars)
+        .cartesian_product(elements_of(*subset).collect_vec())
+        .fold(0, |acc,(r,m)|acc|self.action[(r,m)])
+}
+/// Returns the first triple for which distributivity fails, if any: `(r,m,n)` with side `Left` if `r(m+n) ≠ rm+rn`, and
+/// `(m,r,s)` with side `Right` if `(r+s)m ≠ rm+sm`.
+pub fn check_distributivity(&self)->Result<(),Violation>{
+    let (k,n) = (self.ring.cardinality(),self.cardinality());
+    let left = (0..k).cartesian_product(0..n).cartesian_product(0..n)
+        .find_map(|((r,m),p)|{
+            let left = self.act_by_representation(&(1<<r), &self.add_by_representation(&(1<<m), &(1<<p)));
+            let right = self.add_by_representation(&self.act_by_representation(&(1<<r), &(1<<m)), &self.act_by_representation(&(1<<r), &(1<<p)));
+            (left!=right).then_some(Violation::Distributivity { x: r, y: m, z: p, side: Side::Left, left, right })
+        });
+    let right = || (0..n).cartesian_product(0..k).cartesian_product(0..k)
+        .find_map(|((m,r),s)|{
+            let left = self.act_by_representation(&self.ring.add_by_representation(&(1<<r), &(1<<s)), &(1<<m));
+            let right = self.add_by_representation(&self.act_by_representation(&(1<<r), &(1<<m)), &self.act_by_representation(&(1<<s), &(1<<m)));
+            (left!=right).then_some(Violation::Distributivity { x: m, y: r, z: s, side: Side::Right, left, right })
+        });
+    left.or_else(right).map_or(Ok(()), Err)
+}
+/// Returns the first triple `(r,s,m)` such that `(rs)m ≠ r(sm)`, if any.
+pub fn check_action_associativity(&self)->Result<(),Violation>{
+    let (k,n) = (self.ring.cardinality(),self.cardinality());
+    (0..k).cartesian_product(0..k).cartesian_product(0..n)
+        .find_map(|((r,s),m)|{
+            let left = self.act_by_representation(&self.ring.mul_by_representation(&(1<<r), &(1<<s)), &(1<<m));
+            let right = self.act_by_representation(&(1<<r), &self.act_by_representation(&(1<<s), &(1<<m)));
+            (left!=right).then_some(Violation::Associativity { a: r, b: s, c: m, left, right })
+        })
+        .map_or(Ok(()), Err)
+}
+/// Return true if R has a unit `1` such that `1m = {m}` for all `m`.
+pub fn is_unital(&self)->bool{
+    self.ring.one().is_some_and(|one|(0..self.cardinality()).all(|m|self.act_by_representation(&one, &(1<<m))==1<<m))
+}
+/// Return true if the hypermodule is unital over a hyperfield.
+pub fn is_hypervector_space(&self)->bool{
+    self.ring.is_hyperfield()&&self.is_unital()
+}
+/// Return true if `N` contains `0`, `a-b ⊆ N` for all `a,b` in `N`, and `rN ⊆ N` for all `r` in R.
+pub fn is_submodule(&self,subset:&u64)->bool{
+    let scalars = (1u64<<self.ring.cardinality())-1;
+    subset>>self.cardinality()==0
+    &&
+    subset&self.zero!=0
+    &&
+    self.add_by_representation(subset, &elements_of(*subset).fold(0, |acc,x|acc|1<<self.negation[x]))&!subset==0
+    &&
+    self.act_by_representation(&scalars, subset)&!subset==0
+}
+/// Returns all the submodules, `{0}` and M included, sorted by representation.
+pub fn collect_submodules(&self)->Vec<u64>{
+    (1..1u64<<self.cardinality()).filter(|subset|self.is_submodule(subset)).collect()
+}
+/// Returns the submodule `N` as a hypermodule, with elements relabelled as in [`HyperGroupoid::sub_hypergroupoid`].
+pub fn get_submodule(&self,subset:&u64)->Result<Self,HyperStructureError>{
+    if !self.is_submodule(subset) {
+        return Err(HyperStructureError::NotSubModule { subset: *subset });
+    }
+    let elements = elements_of(*subset).collect_vec();
+    let mut labelling = vec![0usize;self.cardinality() as usize];
+    for (i,x) in elements.iter().enumerate() {
+        labelling[*x] = i;
+    }
+    let addition = HyperGroup::try_new_from_hypergroupoid(&self.addition.0.sub_hypergroupoid(subset).unwrap())?;
+    let action = DMatrix::from_fn(self.ring.cardinality() as usize, elements.len(), |r,m|relabel_subset(self.action[(r,elements[m])], &labelling));
+    HyperModule::new(&self.ring, &addition, &action)
+}
+/// The hypercompositions `x∘y = rx` on M, one for every `r` in R.
+fn action_hypergroupoids(&self)->Vec<HyperGroupoid>{
+    let n = self.cardinality() as usize;
+    (0..self.ring.cardinality() as usize)
+        .map(|r|HyperGroupoid::try_new_from_matrix(&DMatrix::from_fn(n, n, |x,_|self.action[(r,x)])).unwrap())
+        .collect()
+}
+/// Returns the first counterexample to the strong regularity of `ρ`, if any. For the action, `x ρ y` and `z` in R are
+/// reported with side `Right` if not all the elements of `zx ∪ zy` are related.
+pub fn check_strong_regularity(&self,rho:&Relation)->Result<(),Violation>{
+    rho.check_strong_regularity(&self.addition.0)?;
+    let mut rows = vec![0u64;self.cardinality() as usize];
+    for (x,y) in &rho.rel {
+        rows[*x as usize]|=1<<y;
+    }
+    rho.rel.iter()
+        .cartesian_product(0..self.ring.cardinality())
+        .map(|((x,y),z)|(*x,*y,z,self.action[(z as usize,*x as usize)],self.action[(z as usize,*y as usize)]))
+        .find(|(_,_,_,product_x,product_y)|elements_of(*product_x).any(|u|rows[u]&(product_x|product_y)!=product_x|product_y))
+        .map_or(Ok(()), |(x,y,z,product_x,product_y)|Err(Violation::StrongRegularity { x, y, z, side: Side::Right, product_x, product_y }))
+}
+/// Returns `M/ρ` over `{0,1,...,m-1}`, where the `i`-th element is the `i`-th class of `ρ` ordered by least element, as in
+/// [`Relation::quotient_set`]. It fails if `ρ` is not an equivalence on M or if it is not strongly regular.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypermodule::HyperModule;
+/// use hyperstruc::hyperring::HyperRing;
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperRing::new_from_functions(|a,b|1<<((a+b)%cardinality), |a,b|1<<((a*b)%cardinality), &cardinality).unwrap();
+/// let module = HyperModule::new_regular(&z4);
+/// let mut rho = z4.addition.0.gamma_relation();
+/// rho.rel = vec![(0,0),(0,2),(2,0),(2,2),(1,1),(1,3),(3,1),(3,3)];
+/// // Z_4/2Z_4 has two elements, and 2 acts as zero.
+/// let quotient = module.get_quotient(&rho).unwrap();
+/// assert_eq!(quotient.cardinality(),2);
+/// assert_eq!(quotient.act_by_representation(&0b0100, &0b10),0b01);
+/// rho.rel = vec![(0,0),(0,1),(1,0),(1,1),(2,2),(2,3),(3,2),(3,3)];
+/// assert!(module.get_quotient(&rho).is_err());
+/// ```
+pub fn get_quotient(&self,rho:&Relation)->Result<Self,HyperStructureError>{
+    if rho.a!=self.addition.0.h||rho.b!=self.addition.0.h||!rho.is_equivalence() {
+        return Err(HyperStructureError::NotEquivalence);
+    }
+    self.check_strong_regularity(rho).map_err(HyperStructureError::NotStronglyRegular)?;
+    let classes = rho.quotient_set().iter().map(|(_,class)|class.iter().fold(0, |acc,x|acc|1<<x)).collect_vec();
+    Ok(self.quotient_by_classes(&classes))
+}
+/// The quotient by the classes of a strongly regular equivalence, given as subsets sorted by least element.
+fn quotient_by_classes(&self,classes:&[u64])->Self{
+    let class_of = |subset:u64|classes.iter().positions(|class|class&subset!=0).fold(0u64, |acc,i|acc|1<<i);
+    let m = classes.len();
+    let addition = DMatrix::from_fn(m, m, |a,b|class_of(self.add_by_representation(&classes[a], &classes[b])));
+    let action = DMatrix::from_fn(self.ring.cardinality() as usize, m, |r,a|class_of(self.act_by_representation(&(1<<r), &classes[a])));
+    let addition = HyperGroup::try_new_from_matrix(&addition).expect("The quotient by a strongly regular equivalence is a group");
+    HyperModule::new(&self.ring, &addition, &action).expect("The quotient by a strongly regular equivalence is a module")
+}
+/// Returns the classes of `ε*`, as subsets sorted by least element. See the module documentation.
+pub fn collect_fundamental_classes(&self)->Vec<u64>{
+    let scalars = self.action_hypergroupoids();
+    let operations = std::iter::once(&self.addition.0).chain(scalars.iter()).collect_vec();
+    let blocks = self.ring.collect_fundamental_classes().into_iter()
+        .cartesian_product(0..self.cardinality())
+        .map(|(class,m)|self.act_by_representation(&class, &(1<<m)))
+        .collect_vec();
+    strongly_regular_closure_of_operations(&operations, &blocks)
+}
+/// Returns the fundamental module `M/ε*`, whose elements are the classes of [`HyperModule::collect_fundamental_classes`].
+/// Its addition and action are single-valued.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypermodule::HyperModule;
+/// use hyperstruc::hyperring::HyperRing;
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperRing::new_from_functions(|a,b|1<<((a+b)%cardinality), |a,b|1<<((a*b)%cardinality), &cardinality).unwrap();
+/// let module = HyperModule::new_regular(&z4);
+/// assert_eq!(module.get_fundamental_module(),module);
+/// // Since 1-1 = S, the fundamental module of the sign hyperfield is trivial.
+/// assert_eq!(HyperModule::new_regular(&HyperRing::sign()).get_fundamental_module().cardinality(),1);
+/// ```
+pub fn get_fundamental_module(&self)->Self{
+    self.quotient_by_classes(&self.collect_fundamental_classes())
+}
+}
+impl Display for HyperModule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Addition:\n{}\nAction:\n{}", self.addition, self.action)
+    }
+}
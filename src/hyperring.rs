@@ -0,0 +1,209 @@
+//!
+//! Krasner hyperrings and hyperfields.
+//!
+//! A (Krasner) hyperring is a set R with a hyperaddition `+` such that `(R,+)` is a canonical hypergroup with zero `0`,
+//! and a single-valued associative multiplication, for which `0` is absorbing and which distributes over `+` on both
+//! sides: `x(y+z) = xy+xz` and `(y+z)x = yx+zx`. It is a hyperfield if `R-{0}` is a multiplicative group.
+//!
+//! Both operations are stored as hypergroupoids on the same set, the multiplication having singletons as entries.
+//! The Krasner hyperfield and the sign hyperfield are available as [`HyperRing::krasner`] and [`HyperRing::sign`].
+//! The phase-free tropical hyperfield and the triangle hyperfield are defined on `R ∪ {-∞}` and on the nonnegative reals,
+//! and their only finite subhyperring is `{0}`, so they cannot be given as tables. Sending all their nonzero elements to
+//! `1` is a homomorphism onto [`HyperRing::krasner`].
+//!
+use std::fmt::{self, Display};
+use itertools::Itertools;
+use nalgebra::DMatrix;
+use crate::{diagnostics::{Side, Violation}, fundamental_relations::{quotient_by_classes, strongly_regular_closure_of_operations}, hs::HyperGroupoid, hypergroups::{HyperGroup, HyperStructureError}, polygroup::Polygroup, product_table::elements_of};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyperRing {
+    pub addition:HyperGroup,
+    pub multiplication:HyperGroupoid,
+    /// The zero, i.e., the scalar identity of the addition, as a singleton.
+    pub zero:u64,
+    /// `negation[x]` is the opposite of `x`, as an integer in `[0,n-1]`.
+    pub negation:Vec<u64>,
+}
+impl HyperRing {
+/// Returns the hyperring with the given addition and multiplication, or the first reason why they do not define one.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::hypergroups::{HyperGroup, HyperStructureError};
+/// use hyperstruc::hyperring::HyperRing;
+///
+/// let cardinality = 3u64;
+/// let addition = HyperGroup::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality).unwrap();
+/// let multiplication = HyperGroupoid::new_from_function(|a,b|1<<((a*b)%cardinality), &cardinality);
+/// let f3 = HyperRing::new(&addition, &multiplication).unwrap();
+/// assert!(f3.is_hyperfield());
+/// let multiplication = HyperGroupoid::new_from_function(|a,b|1<<(a.max(b)), &cardinality);
+/// match HyperRing::new(&addition, &multiplication) {
+///     Err(HyperStructureError::NotAbsorbing { x }) => assert_eq!(x,1),
+///     _ => panic!("Zero is not absorbing!")
+/// }
+/// ```
+pub fn new(addition:&HyperGroup,multiplication:&HyperGroupoid)->Result<Self,HyperStructureError>{
+    if multiplication.n!=addition.cardinality() {
+        return Err(HyperStructureError::CardinalityMismatch { expected: addition.cardinality(), found: multiplication.n });
+    }
+    if !addition.is_canonical() {
+        return Err(HyperStructureError::NotCanonical);
+    }
+    let n = addition.cardinality();
+    if let Some((a,b)) = (0..n).cartesian_product(0..n).find(|(a,b)|!multiplication.hyper_composition[(*a as usize,*b as usize)].is_power_of_two()) {
+        return Err(HyperStructureError::NotSingleValued { a, b, product: multiplication.hyper_composition[(a as usize,b as usize)] });
+    }
+    multiplication.check_associativity().map_err(HyperStructureError::NotAssociative)?;
+    let Polygroup { identity: zero, inversion: negation, .. } = Polygroup::new_from_hypergroup(addition)?;
+    if let Some(x) = (0..n).find(|x|multiplication.mul_by_representation(&(1<<x), &zero)!=zero||multiplication.mul_by_representation(&zero, &(1<<x))!=zero) {
+        return Err(HyperStructureError::NotAbsorbing { x });
+    }
+    let hyperring = HyperRing { addition: addition.clone(), multiplication: multiplication.clone(), zero, negation };
+    hyperring.check_distributivity().map_err(HyperStructureError::NotDistributive)?;
+    Ok(hyperring)
+}
+/// The hyperaddition and the multiplication are given as functions, both returning subsets. See [`HyperRing::new`].
+pub fn new_from_functions<F,G>(addition:F,multiplication:G,cardinality:&u64)->Result<Self,HyperStructureError>
+    where F: Fn(u64,u64) -> u64, G: Fn(u64,u64) -> u64
+{
+    let n = *cardinality as usize;
+    let addition = HyperGroup::try_new_from_matrix(&DMatrix::from_fn(n, n, |a,b|addition(a as u64, b as u64)))?;
+    let multiplication = HyperGroupoid::try_new_from_matrix(&DMatrix::from_fn(n, n, |a,b|multiplication(a as u64, b as u64)))?;
+    HyperRing::new(&addition, &multiplication)
+}
+/// The Krasner hyperfield `{0,1}`, with `1+1 = {0,1}`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hyperring::HyperRing;
+///
+/// let krasner = HyperRing::krasner();
+/// assert!(krasner.is_hyperfield());
+/// assert_eq!(krasner.add_by_representation(&0b10, &0b10),0b11);
+/// ```
+pub fn krasner()->Self{
+    HyperRing::new_from_functions(|a,b|if a==1&&b==1 {3} else {1<<(a|b)}, |a,b|1<<(a&b), &2).unwrap()
+}
+/// The sign hyperfield `{0,1,-1}`, where `-1` is represented by `2`: `1+1 = {1}`, `-1-1 = {-1}` and `1-1 = {0,1,-1}`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hyperring::HyperRing;
+///
+/// let sign = HyperRing::sign();
+/// assert!(sign.is_hyperfield());
+/// assert_eq!(sign.add_by_representation(&0b010, &0b100),0b111);
+/// assert_eq!(sign.negation,vec![0,2,1]);
+/// ```
+pub fn sign()->Self{
+    let addition = |a:u64,b:u64|match (a,b) {
+        (0,x)|(x,0) => 1<<x,
+        (x,y) if x==y => 1<<x,
+        _ => 7,
+    };
+    let multiplication = |a:u64,b:u64|match (a,b) {
+        (0,_)|(_,0) => 1,
+        (x,y) if x==y => 2,
+        _ => 4,
+    };
+    HyperRing::new_from_functions(addition, multiplication, &3).unwrap()
+}
+pub fn cardinality(&self)->u64{
+    self.addition.cardinality()
+}
+pub fn add_by_representation(&self,subset_a:&u64,subset_b:&u64)->u64{
+    self.addition.mul_by_representation(subset_a, subset_b)
+}
+pub fn mul_by_representation(&self,subset_a:&u64,subset_b:&u64)->u64{
+    self.multiplication.mul_by_representation(subset_a, subset_b)
+}
+/// Returns `-A = {-x : x in A}`.
+pub fn negation_of_subset(&self,subset:&u64)->u64{
+    elements_of(*subset).fold(0, |acc,x|acc|1<<self.negation[x])
+}
+/// Returns the first triple `(x,y,z)` for which distributivity fails, if any.
+pub fn check_distributivity(&self)->Result<(),Violation>{
+    let n = self.cardinality();
+    (0..n).cartesian_product(0..n).cartesian_product(0..n)
+        .find_map(|((x,y),z)|{
+            let y_plus_z = self.add_by_representation(&(1<<y), &(1<<z));
+            let left = self.mul_by_representation(&(1<<x), &y_plus_z);
+            let right = self.add_by_representation(&self.mul_by_representation(&(1<<x), &(1<<y)), &self.mul_by_representation(&(1<<x), &(1<<z)));
+            if left!=right {return Some(Violation::Distributivity { x, y, z, side: Side::Left, left, right });}
+            let left = self.mul_by_representation(&y_plus_z, &(1<<x));
+            let right = self.add_by_representation(&self.mul_by_representation(&(1<<y), &(1<<x)), &self.mul_by_representation(&(1<<z), &(1<<x)));
+            (left!=right).then_some(Violation::Distributivity { x, y, z, side: Side::Right, left, right })
+        })
+        .map_or(Ok(()), Err)
+}
+/// Returns the multiplicative identity, as a singleton, if any.
+pub fn one(&self)->Option<u64>{
+    self.multiplication.collect_identities().into_iter()
+        .find(|u|self.multiplication.get_singleton().iter().all(|x|self.mul_by_representation(u, x)==*x&&self.mul_by_representation(x, u)==*x))
+}
+/// Return true if `R-{0}` is a multiplicative group.
+pub fn is_hyperfield(&self)->bool{
+    let Some(one) = self.one() else {return false;};
+    let nonzero = self.multiplication.get_singleton().into_iter().filter(|x|*x!=self.zero).collect_vec();
+    one!=self.zero
+    &&
+    nonzero.iter().cartesian_product(nonzero.iter()).all(|(x,y)|self.mul_by_representation(x, y)!=self.zero)
+    &&
+    nonzero.iter().all(|x|nonzero.iter().any(|y|self.mul_by_representation(x, y)==one&&self.mul_by_representation(y, x)==one))
+}
+/// Return true if `I` contains `0`, `a-b ⊆ I` for all `a,b` in `I`, and `xI ∪ Ix ⊆ I` for all `x` in R.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hyperring::HyperRing;
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperRing::new_from_functions(|a,b|1<<((a+b)%cardinality), |a,b|1<<((a*b)%cardinality), &cardinality).unwrap();
+/// assert_eq!(z4.collect_hyperideals(),vec![0b0001,0b0101,0b1111]);
+/// ```
+pub fn is_hyperideal(&self,subset:&u64)->bool{
+    let h = (1u64<<self.cardinality())-1;
+    subset&!h==0
+    &&
+    subset&self.zero!=0
+    &&
+    self.add_by_representation(subset, &self.negation_of_subset(subset))&!subset==0
+    &&
+    (self.mul_by_representation(&h, subset)|self.mul_by_representation(subset, &h))&!subset==0
+}
+/// Returns all the hyperideals, `{0}` and R included, sorted by representation.
+pub fn collect_hyperideals(&self)->Vec<u64>{
+    (1..1u64<<self.cardinality()).filter(|subset|self.is_hyperideal(subset)).collect()
+}
+/// Returns the classes of `Γ*`, the smallest equivalence such that the quotient is a ring, as subsets sorted by least element.
+pub fn collect_fundamental_classes(&self)->Vec<u64>{
+    strongly_regular_closure_of_operations(&[&self.addition.0, &self.multiplication], &[])
+}
+/// Returns the fundamental ring `R/Γ*`, whose elements are the classes of [`HyperRing::collect_fundamental_classes`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::hyperring::HyperRing;
+///
+/// // Every element of a hyperfield with 1-1 = R is in the class of 0, so the fundamental ring is trivial.
+/// let sign = HyperRing::sign();
+/// assert_eq!(sign.get_fundamental_ring().cardinality(),1);
+/// let cardinality = 4u64;
+/// let z4 = HyperRing::new_from_functions(|a,b|1<<((a+b)%cardinality), |a,b|1<<((a*b)%cardinality), &cardinality).unwrap();
+/// assert_eq!(z4.get_fundamental_ring(),z4);
+/// ```
+pub fn get_fundamental_ring(&self)->HyperRing{
+    let classes = self.collect_fundamental_classes();
+    let addition = HyperGroup(quotient_by_classes(&self.addition.0, &classes));
+    let multiplication = quotient_by_classes(&self.multiplication, &classes);
+    HyperRing::new(&addition, &multiplication).expect("The quotient by Γ* is a ring")
+}
+}
+impl Display for HyperRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Addition:\n{}\nMultiplication:\n{}", self.addition, self.multiplication)
+    }
+}
@@ -0,0 +1,211 @@
+//!
+//! Incremental verification of the hypergroup axioms.
+//!
+//! A [`MutableHyperGroupoid`] keeps track of the associativity triples that fail, of the rows and columns that do not cover H
+//! and of the empty entries of its table. After an edit of the entry `ab` only the triples reading that entry are checked
+//! again, that is the triples `(a,b,z)`, `(x,a,b)`, `(a,y,z)` and `(x,y,b)`, together with the row `a` and the column `b`.
+//! This costs `O(n^3)` operations instead of the `O(n^4)` of [`HyperGroupoid::is_hypergroup`], which makes walks on the
+//! Hamming graph of tags, as in [`crate::hypergroups::exploring_tree`], cheaper.
+//!
+use itertools::Itertools;
+use crate::{hs::HyperGroupoid, hypergroups::HyperStructureError, product_table::elements_of, utilities::U1024};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutableHyperGroupoid{
+    pub n:u64,
+    /// Row-major hypercomposition table.
+    table:Vec<u64>,
+    /// `failing[(x*n+y)*n+z]` is true if `(xy)z` differs from `x(yz)`.
+    failing:Vec<bool>,
+    failing_count:usize,
+    row_unions:Vec<u64>,
+    column_unions:Vec<u64>,
+    empty_entries:usize,
+}
+impl MutableHyperGroupoid {
+/// Builds the handle and checks every triple once.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::incremental::MutableHyperGroupoid;
+///
+/// let cardinality = 4u64;
+/// let hs = HyperGroupoid::new_from_function(|a,b|1<<a|1<<b, &cardinality);
+/// let mut editor = MutableHyperGroupoid::new(&hs);
+/// assert!(editor.is_hypergroup());
+/// // 0*1 = {0,1,2} still gives a hypergroup, 0*1 = {3} does not.
+/// assert!(editor.set_entry(0, 1, 7));
+/// assert!(!editor.set_entry(0, 1, 8));
+/// assert_eq!(editor.is_hypergroup(),editor.to_hypergroupoid().unwrap().is_hypergroup());
+/// // Restoring the entry restores the hypergroup.
+/// assert!(editor.set_entry(0, 1, 3));
+/// assert_eq!(editor.to_hypergroupoid().unwrap(),hs);
+/// ```
+pub fn new(hs:&HyperGroupoid)->Self{
+    let n = hs.n as usize;
+    let table:Vec<u64> = (0..n*n).map(|i|hs.hyper_composition[(i/n,i%n)]).collect();
+    MutableHyperGroupoid::new_from_table(table, &hs.n)
+}
+fn new_from_table(table:Vec<u64>,cardinality:&u64)->Self{
+    let n = *cardinality as usize;
+    let mut editor = MutableHyperGroupoid {
+        n:*cardinality,
+        empty_entries: table.iter().filter(|entry|**entry==0).count(),
+        table,
+        failing: vec![false;n.pow(3)],
+        failing_count: 0,
+        row_unions: vec![0;n],
+        column_unions: vec![0;n],
+    };
+    for x in 0..n {
+        editor.update_row_and_column(x, x);
+    }
+    for (x,y,z) in (0..n).cartesian_product(0..n).cartesian_product(0..n).map(|((x,y),z)|(x,y,z)) {
+        editor.recheck(x, y, z);
+    }
+    editor
+}
+/// Builds the handle from a tag, which may contain empty entries. It returns `HyperStructureError::InvalidTag` if the tag
+/// has more than `n^3` bits.
+pub fn new_from_tag_u1024(tag:&U1024,cardinality:&u64)->Result<Self,HyperStructureError>{
+    let n = *cardinality as usize;
+    if n>10||tag.bits()>n.pow(3) {
+        return Err(HyperStructureError::InvalidTag { cardinality: *cardinality });
+    }
+    let mask = (1u64<<n)-1;
+    let table = (0..n*n).map(|i|((*tag>>(n*(n*n-1-i))).low_u64())&mask).collect();
+    Ok(MutableHyperGroupoid::new_from_table(table, cardinality))
+}
+pub fn entry(&self,a:usize,b:usize)->u64{
+    self.table[a*self.n as usize+b]
+}
+/// Return true if `(xy)z` differs from `x(yz)` in the given row-major table.
+fn triple_fails(table:&[u64],n:usize,x:usize,y:usize,z:usize)->bool{
+    let left = elements_of(table[x*n+y]).fold(0, |acc,e|acc|table[e*n+z]);
+    let right = elements_of(table[y*n+z]).fold(0, |acc,e|acc|table[x*n+e]);
+    left!=right
+}
+fn recheck(&mut self,x:usize,y:usize,z:usize){
+    let n = self.n as usize;
+    let index = (x*n+y)*n+z;
+    let fails = MutableHyperGroupoid::triple_fails(&self.table, n, x, y, z);
+    if fails!=self.failing[index] {
+        self.failing[index] = fails;
+        if fails {self.failing_count+=1;} else {self.failing_count-=1;}
+    }
+}
+fn update_row_and_column(&mut self,a:usize,b:usize){
+    let n = self.n as usize;
+    self.row_unions[a] = (0..n).fold(0, |acc,y|acc|self.entry(a, y));
+    self.column_unions[b] = (0..n).fold(0, |acc,x|acc|self.entry(x, b));
+}
+/// Replaces the entry `ab` with `entry` and re-checks only what the edit can affect. Returns true if the result is a hypergroup.
+pub fn set_entry(&mut self,a:usize,b:usize,entry:u64)->bool{
+    let n = self.n as usize;
+    assert!(a<n&&b<n,"({},{}) is not a pair of elements of H",a,b);
+    assert!(entry>>n==0,"{} does not represent a subset of H",entry);
+    let index = a*n+b;
+    let old = self.table[index];
+    if old==entry {return self.is_hypergroup();}
+    if old==0 {self.empty_entries-=1;}
+    if entry==0 {self.empty_entries+=1;}
+    self.table[index] = entry;
+    self.update_row_and_column(a, b);
+    for (x,y) in (0..n).cartesian_product(0..n) {
+        // triples (x,y,b) may read ab in (xy)b, triples (a,x,y) may read ab in a(xy)
+        self.recheck(x, y, b);
+        self.recheck(a, x, y);
+    }
+    for z in 0..n {
+        self.recheck(a, b, z);
+        self.recheck(z, a, b);
+    }
+    self.is_hypergroup()
+}
+/// Flips the bit of given position of the tag, as in [`crate::hs::circumference_radius_d`].
+/// Returns true if the result is a hypergroup.
+pub fn flip_tag_bit(&mut self,position:usize)->bool{
+    let n = self.n as usize;
+    assert!(position<n.pow(3),"The tag of a hypergroupoid of order {} has {} bits",n,n.pow(3));
+    let (cell,k) = (n*n-1-position/n,position%n);
+    self.set_entry(cell/n, cell%n, self.table[cell]^(1<<k))
+}
+/// Returns true if no entry is empty.
+pub fn is_hypergroupoid(&self)->bool{
+    self.empty_entries==0
+}
+pub fn is_associative(&self)->bool{
+    self.failing_count==0
+}
+pub fn is_reproductive(&self)->bool{
+    let h = (1<<self.n)-1;
+    self.row_unions.iter().chain(self.column_unions.iter()).all(|union|*union==h)
+}
+pub fn is_hypergroup(&self)->bool{
+    self.is_hypergroupoid()&&self.is_associative()&&self.is_reproductive()
+}
+/// The triples `(a,b,c)` such that `(ab)c` differs from `a(bc)`.
+pub fn collect_failing_triples(&self)->Vec<(u64,u64,u64)>{
+    let n = self.n as usize;
+    self.failing.iter().positions(|fails|*fails)
+        .map(|index|((index/(n*n)) as u64,((index/n)%n) as u64,(index%n) as u64))
+        .collect()
+}
+pub fn get_integer_tag_u1024(&self)->U1024{
+    let n = self.n as usize;
+    self.table.iter().fold(U1024::zero(), |tag,entry|(tag<<n)|U1024::from(*entry))
+}
+/// Returns the current table as a hypergroupoid, if it has no empty entry.
+pub fn to_hypergroupoid(&self)->Option<HyperGroupoid>{
+    self.is_hypergroupoid().then(||HyperGroupoid::new_from_function(|a,b|self.table[(a*self.n+b) as usize], &self.n))
+}
+/// Returns true if replacing the entry `ab` with `entry` gives a hypergroup, without modifying the table.
+/// It stops at the first failing triple, so it is cheaper than [`MutableHyperGroupoid::set_entry`] followed by its inverse.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::incremental::MutableHyperGroupoid;
+///
+/// let cardinality = 3u64;
+/// let hs = HyperGroupoid::new_from_function(|a,b|1<<a|1<<b, &cardinality);
+/// let editor = MutableHyperGroupoid::new(&hs);
+/// assert!(editor.probe_entry(0, 1, 7));
+/// assert!(!editor.probe_entry(0, 1, 4));
+/// ```
+pub fn probe_entry(&self,a:usize,b:usize,entry:u64)->bool{
+    let n = self.n as usize;
+    let index = a*n+b;
+    let h = (1<<self.n)-1;
+    if entry==0||self.empty_entries>usize::from(self.table[index]==0) {return false;}
+    let mut table = self.table.clone();
+    table[index] = entry;
+    let reproductive = (0..n).all(|x|
+        (x==a||self.row_unions[x]==h)
+        &&
+        (x==b||self.column_unions[x]==h))
+        &&
+        (0..n).fold(0, |acc,y|acc|table[a*n+y])==h
+        &&
+        (0..n).fold(0, |acc,x|acc|table[x*n+b])==h;
+    if !reproductive {return false;}
+    // a failing triple that does not read ab keeps failing
+    if self.failing_count>0&&self.collect_failing_triples().iter().any(|(x,_,z)|*x as usize!=a&&*z as usize!=b) {return false;}
+    (0..n).cartesian_product(0..n).all(|(x,y)|
+        !MutableHyperGroupoid::triple_fails(&table, n, x, y, b)&&!MutableHyperGroupoid::triple_fails(&table, n, a, x, y))
+}
+/// Returns the tags at Hamming distance one that represent hypergroups, probing every bit of the tag.
+/// It agrees with [`crate::hs::hg_in_circumference_radius_one`] up to the order of tags.
+pub fn collect_hypergroups_at_distance_one(&self)->Vec<U1024>{
+    let n = self.n as usize;
+    let tag = self.get_integer_tag_u1024();
+    (0..n.pow(3))
+        .filter(|position|{
+            let (cell,k) = (n*n-1-position/n,position%n);
+            self.probe_entry(cell/n, cell%n, self.table[cell]^(1<<k))
+        })
+        .map(|position|tag^(U1024::one()<<position))
+        .collect()
+}
+}
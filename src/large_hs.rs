@@ -0,0 +1,928 @@
+//!
+//! Hyperstructures on sets with more than 64 elements.
+//!
+//! [`HyperGroupoid`] stores every entry of the hypercomposition table as a `u64`, so it is limited to 64 elements, and its tag
+//! is a `U1024`, so tags are available up to 10 elements. [`LargeHyperGroupoid`] and [`LargeHyperGroup`] store entries as
+//! [`BitSet`]s, so the representation puts no bound on the cardinality. Elements are the integers `0,1,...,n-1` and subsets
+//! are bitsets. Tags are optional: they are returned only when they fit in a `U1024`.
+//!
+//! Axioms, identities, inverses, `β*` and the fundamental group are computed in polynomial time, so they are usable with
+//! hundreds of elements. Functions that list hyperproducts or subsets, i.e., [`LargeHyperGroupoid::collect_ph`],
+//! [`LargeHyperGroupoid::beta_relation`] and the search of subhypergroups, may take exponential time, and so may the
+//! isomorphism test on highly symmetric structures.
+//!
+//! Functions keep the names of their counterparts in [`crate::hs`] and [`crate::hypergroups`].
+//! Quotients and fundamental groups are returned as large hypergroupoids on the classes, subsets as bitsets and elements as
+//! `usize`. Functions built on tags, such as the exploration of isomorphism classes by tag, are not available here, and
+//! the Corsini fuzzy subset is only available through its join space [`LargeHyperGroupoid::get_corsini_join_space`] and the
+//! values [`LargeHyperGroupoid::get_mu_u`]: `get_corsini_fuzzysubset` is left out because [`crate::fuzzy::FuzzySubset`] wraps a
+//! [`HyperGroupoid`]. Up to 64 elements it is available as `to_hypergroupoid()` followed by
+//! [`HyperGroupoid::get_corsini_fuzzysubset`].
+//!
+use std::{collections::HashSet, fmt::{self, Display}};
+use itertools::Itertools;
+use nalgebra::DMatrix;
+use num_rational::Rational64;
+use permutation::Permutation;
+use rand::Rng;
+use crate::{binary_relations::relations::Relation, bitset::BitSet, canonical::canonical_table, diagnostics::{Side, Violation}, hs::HyperGroupoid, hypergroups::{HyperGroup, HyperStructureError}, utilities::U1024};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LargeHyperGroupoid{
+    pub n:usize,
+    /// Row-major hypercomposition table, i.e., `hyper_composition[a*n+b]` is `ab`.
+    pub hyper_composition:Vec<BitSet>,
+}
+impl LargeHyperGroupoid {
+/// Generates a new hyperstructure from a function returning, for every pair of elements `(a,b)`, the subset `ab`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::LargeHyperGroupoid;
+/// use hyperstruc::bitset::BitSet;
+///
+/// let cardinality = 100usize;
+/// let hs = LargeHyperGroupoid::new_from_function(|a,b|BitSet::from_elements(cardinality, &[a,b]), &cardinality);
+/// assert!(hs.is_hypergroup());
+/// assert!(hs.is_commutative());
+/// assert!(hs.get_integer_tag_u1024().is_none());
+/// ```
+/// Generates a hyperstructure on `n` elements whose entries are random non-empty subsets, as
+/// [`HyperGroupoid::new_random_from_cardinality`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::LargeHyperGroupoid;
+///
+/// let cardinality = 80usize;
+/// let hs = LargeHyperGroupoid::new_random_from_cardinality(&cardinality);
+/// assert_eq!(hs.n,cardinality);
+/// assert!(hs.hyper_composition.iter().all(|entry|!entry.is_empty()&&entry.size()==cardinality));
+/// ```
+pub fn new_random_from_cardinality(n:&usize)->Self{
+    LargeHyperGroupoid::new_from_function(|_,_|{
+        let mut rng = rand::thread_rng();
+        //every non-empty subset is equally likely
+        loop {
+            let subset = BitSet::from_elements(*n, &(0..*n).filter(|_|rng.gen_bool(0.5)).collect_vec());
+            if !subset.is_empty() {break subset;}
+        }
+    }, n)
+}
+pub fn new_from_function<F>(function:F,cardinality:&usize)->Self
+    where F: Fn(usize,usize) -> BitSet {
+    LargeHyperGroupoid::try_new_from_function(function, cardinality).unwrap_or_else(|error|panic!("{error}"))
+}
+/// Fallible version of [`LargeHyperGroupoid::new_from_function`]. It returns an error if some entry is empty, contains an
+/// element outside of H or is a subset of a set of different order.
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::LargeHyperGroupoid;
+/// use hyperstruc::bitset::BitSet;
+/// use hyperstruc::hypergroups::HyperStructureError;
+///
+/// let cardinality = 100usize;
+/// let empty_on_the_diagonal = |a,b|if a==b {BitSet::new(cardinality)} else {BitSet::from_elements(cardinality, &[a,b])};
+/// assert!(matches!(LargeHyperGroupoid::try_new_from_function(empty_on_the_diagonal, &cardinality),
+///     Err(HyperStructureError::EmptyEntry { a: 0, b: 0 })));
+/// let out_of_range = |a,b|if (a,b)==(3,4) {BitSet::singleton(cardinality+1, cardinality)} else {BitSet::singleton(cardinality, a)};
+/// assert!(matches!(LargeHyperGroupoid::try_new_from_function(out_of_range, &cardinality),
+///     Err(HyperStructureError::ElementOutOfRange { a: 3, b: 4, x: 100 })));
+/// assert!(matches!(LargeHyperGroupoid::try_new_from_function(|a,_|BitSet::singleton(cardinality+1, a), &cardinality),
+///     Err(HyperStructureError::CardinalityMismatch { expected: 100, found: 101 })));
+/// ```
+pub fn try_new_from_function<F>(function:F,cardinality:&usize)->Result<Self,HyperStructureError>
+    where F: Fn(usize,usize) -> BitSet {
+    let n = *cardinality;
+    let hyper_composition:Vec<BitSet> = (0..n).cartesian_product(0..n)
+        .map(|(a,b)|function(a,b))
+        .collect();
+    for (index,entry) in hyper_composition.iter().enumerate() {
+        let (a,b) = ((index/n) as u64,(index%n) as u64);
+        if entry.is_empty() {return Err(HyperStructureError::EmptyEntry { a, b });}
+        if let Some(x) = entry.iter().find(|x|*x>=n) {
+            return Err(HyperStructureError::ElementOutOfRange { a, b, x: x as u64 });
+        }
+        if entry.size()!=n {
+            return Err(HyperStructureError::CardinalityMismatch { expected: n as u64, found: entry.size() as u64 });
+        }
+    }
+    Ok(LargeHyperGroupoid { n, hyper_composition })
+}
+/// Constructs a hypergroupoid from a flattened Cayley table, where `input_array[n*a+b]` lists the elements of `ab`.
+pub fn new_from_elements(input_array:&[Vec<usize>],cardinality:&usize)->Self{
+    LargeHyperGroupoid::try_new_from_elements(input_array, cardinality).unwrap_or_else(|error|panic!("{error}"))
+}
+/// Fallible version of [`LargeHyperGroupoid::new_from_elements`]. It returns an error if `input_array` has not length `n^2`,
+/// or if some entry is empty or contains an element outside of H.
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::LargeHyperGroupoid;
+/// use hyperstruc::hypergroups::HyperStructureError;
+///
+/// let cardinality = 2usize;
+/// assert!(matches!(LargeHyperGroupoid::try_new_from_elements(&[vec![0],vec![1],vec![1]], &cardinality),
+///     Err(HyperStructureError::CardinalityMismatch { expected: 4, found: 3 })));
+/// assert!(matches!(LargeHyperGroupoid::try_new_from_elements(&[vec![0],vec![1],vec![1],vec![0,70]], &cardinality),
+///     Err(HyperStructureError::ElementOutOfRange { a: 1, b: 1, x: 70 })));
+/// assert!(LargeHyperGroupoid::try_new_from_elements(&[vec![0],vec![1],vec![1],vec![0,1]], &cardinality).is_ok());
+/// ```
+pub fn try_new_from_elements(input_array:&[Vec<usize>],cardinality:&usize)->Result<Self,HyperStructureError>{
+    let n = *cardinality;
+    if input_array.len()!=n.pow(2) {
+        return Err(HyperStructureError::CardinalityMismatch { expected: n.pow(2) as u64, found: input_array.len() as u64 });
+    }
+    for (index,entry) in input_array.iter().enumerate() {
+        let (a,b) = ((index/n) as u64,(index%n) as u64);
+        if entry.is_empty() {return Err(HyperStructureError::EmptyEntry { a, b });}
+        if let Some(x) = entry.iter().find(|x|**x>=n) {
+            return Err(HyperStructureError::ElementOutOfRange { a, b, x: *x as u64 });
+        }
+    }
+    LargeHyperGroupoid::try_new_from_function(|a,b|BitSet::from_elements(n, &input_array[n*a+b]), cardinality)
+}
+/// Constructs a hypergroupoid from its Cayley table, where the entry `(a,b)` is the subset `ab`.
+pub fn new_from_matrix(matrix:&DMatrix<BitSet>)->Self{
+    LargeHyperGroupoid::try_new_from_matrix(matrix).unwrap_or_else(|error|panic!("{error}"))
+}
+/// Fallible version of [`LargeHyperGroupoid::new_from_matrix`]. It returns an error if the matrix is not square, or if
+/// some entry is empty or is not a subset of H.
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::LargeHyperGroupoid;
+/// use hyperstruc::bitset::BitSet;
+/// use hyperstruc::hypergroups::HyperStructureError;
+/// use nalgebra::DMatrix;
+///
+/// let matrix = DMatrix::from_fn(2, 3, |a,b|BitSet::singleton(3, (a+b)%3));
+/// assert!(matches!(LargeHyperGroupoid::try_new_from_matrix(&matrix),
+///     Err(HyperStructureError::NonSquareMatrix { rows: 2, columns: 3 })));
+/// let matrix = DMatrix::from_fn(70, 70, |a,b|BitSet::singleton(70, (a+b)%70));
+/// assert!(LargeHyperGroupoid::try_new_from_matrix(&matrix).unwrap().is_hypergroup());
+/// ```
+pub fn try_new_from_matrix(matrix:&DMatrix<BitSet>)->Result<Self,HyperStructureError>{
+    if !matrix.is_square() {
+        return Err(HyperStructureError::NonSquareMatrix { rows: matrix.nrows(), columns: matrix.ncols() });
+    }
+    LargeHyperGroupoid::try_new_from_function(|a,b|matrix[(a,b)].clone(), &matrix.ncols())
+}
+pub fn new_from_hypergroupoid(hs:&HyperGroupoid)->Self{
+    let n = hs.n as usize;
+    LargeHyperGroupoid::new_from_function(|a,b|BitSet::from_u64(n, hs.hyper_composition[(a,b)]), &n)
+}
+pub fn new_from_tag_u1024(tag:&U1024,cardinality:&usize)->Self{
+    LargeHyperGroupoid::new_from_hypergroupoid(&HyperGroupoid::new_from_tag_u1024(tag, &(*cardinality as u64)))
+}
+/// Returns the same hypergroupoid with `u64` entries, if `n` is at most 64.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::large_hs::LargeHyperGroupoid;
+///
+/// let cardinality = 5u64;
+/// let hs = HyperGroupoid::new_random_from_cardinality(&cardinality);
+/// let large = LargeHyperGroupoid::new_from_hypergroupoid(&hs);
+/// assert_eq!(large.to_hypergroupoid(),Some(hs.clone()));
+/// assert_eq!(large.get_integer_tag_u1024(),Some(hs.get_integer_tag_u1024()));
+/// ```
+pub fn to_hypergroupoid(&self)->Option<HyperGroupoid>{
+    if self.n>64 {return None;}
+    let entries:Option<Vec<u64>> = self.hyper_composition.iter().map(|entry|entry.to_u64()).collect();
+    let n = self.n as u64;
+    let entries = entries?;
+    Some(HyperGroupoid::new_from_function(|a,b|entries[(n*a+b) as usize], &n))
+}
+/// Returns the tag of the hypergroupoid, if `n^3` bits fit in a `U1024`, i.e., if `n` is at most 10.
+pub fn get_integer_tag_u1024(&self)->Option<U1024>{
+    if self.n.pow(3)>1024 {return None;}
+    self.to_hypergroupoid().map(|hs|hs.get_integer_tag_u1024())
+}
+pub fn get_singleton(&self)->Vec<BitSet>{
+    (0..self.n).map(|x|BitSet::singleton(self.n, x)).collect()
+}
+/// The entry `ab` of the hypercomposition table.
+pub fn entry(&self,a:usize,b:usize)->&BitSet{
+    &self.hyper_composition[a*self.n+b]
+}
+pub fn mul_by_representation(&self,subset_a:&BitSet,subset_b:&BitSet)->BitSet{
+    let mut product = BitSet::new(self.n);
+    for a in subset_a.iter() {
+        for b in subset_b.iter() {
+            product|=self.entry(a, b);
+        }
+    }
+    product
+}
+/// Compute `b\a={x in H : a meets bx}`, as [`HyperGroupoid::left_division`].
+pub fn left_division(&self,a:&BitSet,b:&BitSet)->BitSet{
+    let elements:Vec<usize> = (0..self.n)
+        .filter(|x|b.iter().any(|y|self.entry(y, *x).intersects(a)))
+        .collect();
+    BitSet::from_elements(self.n, &elements)
+}
+/// Compute `a/b={x in H : a meets xb}`, as [`HyperGroupoid::right_division`].
+pub fn right_division(&self,a:&BitSet,b:&BitSet)->BitSet{
+    let elements:Vec<usize> = (0..self.n)
+        .filter(|x|b.iter().any(|y|self.entry(*x, y).intersects(a)))
+        .collect();
+    BitSet::from_elements(self.n, &elements)
+}
+fn product_with_element(&self,subset:&BitSet,c:usize,left:bool)->BitSet{
+    let mut product = BitSet::new(self.n);
+    for x in subset.iter() {
+        product|= if left {self.entry(x, c)} else {self.entry(c, x)};
+    }
+    product
+}
+pub fn is_associative(&self)->bool{
+    self.check_associativity().is_ok()
+}
+/// Returns the first triple `(a,b,c)` such that `(ab)c` differs from `a(bc)`, if any, as [`HyperGroupoid::check_associativity`].
+pub fn check_associativity(&self)->Result<(),Violation>{
+    (0..self.n).cartesian_product(0..self.n).cartesian_product(0..self.n)
+        .find_map(|((a,b),c)|{
+            let left = self.product_with_element(self.entry(a, b), c, true);
+            let right = self.product_with_element(self.entry(b, c), a, false);
+            (left!=right).then_some(Violation::LargeAssociativity { a: a as u64, b: b as u64, c: c as u64, left, right })
+        })
+        .map_or(Ok(()), Err)
+}
+pub fn is_weak_associative(&self)->bool{
+    (0..self.n).cartesian_product(0..self.n).cartesian_product(0..self.n)
+        .all(|((a,b),c)|
+            self.product_with_element(self.entry(a, b), c, true)
+                .intersects(&self.product_with_element(self.entry(b, c), a, false)))
+}
+pub fn is_reproductive(&self)->bool{
+    self.check_reproductivity().is_ok()
+}
+/// Returns the first element `x` such that `xH` or `Hx` differs from `H`, if any. Left products are checked first, as in
+/// [`HyperGroupoid::check_reproductivity`].
+pub fn check_reproductivity(&self)->Result<(),Violation>{
+    let product = |x:usize,side:Side|{
+        let mut product = BitSet::new(self.n);
+        for y in 0..self.n {
+            product|= match side {
+                Side::Left => self.entry(x, y),
+                Side::Right => self.entry(y, x),
+            };
+        }
+        product
+    };
+    [Side::Left,Side::Right].into_iter()
+        .cartesian_product(0..self.n)
+        .map(|(side,x)|(x,side,product(x,side)))
+        .find(|(_,_,product)|!product.is_full())
+        .map_or(Ok(()), |(x,side,product)|Err(Violation::LargeReproductivity { x: x as u64, side, product }))
+}
+pub fn is_hypergroup(&self)->bool{
+    self.is_reproductive()&&self.is_associative()
+}
+pub fn is_commutative(&self)->bool{
+    (0..self.n).tuple_combinations().all(|(a,b)|self.entry(a, b)==self.entry(b, a))
+}
+pub fn is_left_identity(&self,e:&usize)->bool{
+    (0..self.n).all(|x|self.entry(*e, x).contains(x))
+}
+pub fn is_right_identity(&self,e:&usize)->bool{
+    (0..self.n).all(|x|self.entry(x, *e).contains(x))
+}
+pub fn is_identity(&self,e:&usize)->bool{
+    self.is_left_identity(e)&&self.is_right_identity(e)
+}
+pub fn collect_left_identities(&self)->Vec<usize>{
+    (0..self.n).filter(|e|self.is_left_identity(e)).collect()
+}
+pub fn collect_right_identities(&self)->Vec<usize>{
+    (0..self.n).filter(|e|self.is_right_identity(e)).collect()
+}
+pub fn collect_identities(&self)->Vec<usize>{
+    (0..self.n).filter(|e|self.is_identity(e)).collect()
+}
+pub fn is_left_scalar(&self,s:&usize)->bool{
+    (0..self.n).all(|x|self.entry(*s, x).is_singleton())
+}
+pub fn is_right_scalar(&self,s:&usize)->bool{
+    (0..self.n).all(|x|self.entry(x, *s).is_singleton())
+}
+pub fn is_scalar(&self,s:&usize)->bool{
+    self.is_left_scalar(s)&&self.is_right_scalar(s)
+}
+pub fn collect_left_scalars(&self)->Vec<usize>{
+    (0..self.n).filter(|s|self.is_left_scalar(s)).collect()
+}
+pub fn collect_right_scalars(&self)->Vec<usize>{
+    (0..self.n).filter(|s|self.is_right_scalar(s)).collect()
+}
+pub fn collect_scalars(&self)->Vec<usize>{
+    (0..self.n).filter(|s|self.is_scalar(s)).collect()
+}
+pub fn collect_scalar_identities(&self)->Vec<usize>{
+    (0..self.n).filter(|s|self.is_scalar(s)&&self.is_identity(s)).collect()
+}
+/// Return the subset `x\e-{e}` of right inverses of `x` with respect to the identity `e`.
+pub fn right_inverses_of_x(&self,x:&usize,e:&usize)->BitSet{
+    assert!(self.is_identity(e),"{} is not an identity in H",e);
+    let mut right_inverses = self.left_division(&BitSet::singleton(self.n, *e), &BitSet::singleton(self.n, *x));
+    right_inverses.remove(*e);
+    right_inverses
+}
+/// Return the subset `e/x-{e}` of left inverses of `x` with respect to the identity `e`.
+pub fn left_inverses_of_x(&self,x:&usize,e:&usize)->BitSet{
+    assert!(self.is_identity(e),"{} is not an identity in H",e);
+    let mut left_inverses = self.right_division(&BitSet::singleton(self.n, *e), &BitSet::singleton(self.n, *x));
+    left_inverses.remove(*e);
+    left_inverses
+}
+pub fn collect_left_inverses_of_x(&self,x:&usize)->Vec<(usize,BitSet)>{
+    self.collect_identities().into_iter().map(|u|(u,self.left_inverses_of_x(x, &u))).collect()
+}
+pub fn collect_right_inverses_of_x(&self,x:&usize)->Vec<(usize,BitSet)>{
+    self.collect_identities().into_iter().map(|u|(u,self.right_inverses_of_x(x, &u))).collect()
+}
+pub fn collect_inverses_of_x(&self,x:&usize)->Vec<(usize,BitSet)>{
+    let right_inverses = self.collect_right_inverses_of_x(x);
+    self.collect_left_inverses_of_x(x).into_iter()
+        .filter(|inverses|right_inverses.contains(inverses))
+        .collect()
+}
+/// The set `P(H)` of all finite hyperproducts, as in [`HyperGroupoid::collect_ph`].
+pub fn collect_ph(&self)->Vec<BitSet>{
+    let mut a_current = Vec::new();
+    let mut a_next = self.get_singleton();
+    while a_next!=a_current {
+        a_current = a_next;
+        a_next = a_current.iter().cartesian_product(a_current.iter()).map(|(x,y)|self.mul_by_representation(x, y)).collect();
+        a_next.extend(a_current.iter().cloned());
+        a_next.sort();
+        a_next.dedup();
+    }
+    a_current
+}
+/// The relation `β = ⋃ q × q`, where `q` runs over `P(H)`. See [`HyperGroupoid::beta_relation`].
+/// Since it lists `P(H)`, whose size may grow exponentially with the order, prefer [`LargeHyperGroupoid::collect_beta_classes`]
+/// when only `β*` is needed.
+pub fn beta_relation(&self)->Relation{
+    let beta:Vec<(u64,u64)> = self.collect_ph().iter()
+        .flat_map(|q|q.iter().cartesian_product(q.iter()).map(|(x,y)|(x as u64,y as u64)).collect_vec())
+        .sorted()
+        .dedup()
+        .collect();
+    let h:HashSet<u64> = (0..self.n as u64).collect();
+    Relation { a: h.clone(), b: h, rel: beta }
+}
+/// Classes of the transitive closure `β*` of `β`. Two elements are in the same class if they are linked by a chain of
+/// hyperproducts, each one meeting the next. For hypergroups `β=β*`, so these are the classes of `β`.
+///
+/// `β*` is the smallest strongly regular equivalence on H, so the classes are computed with a union-find structure, merging
+/// the products `Cz` and `zC` of every class `C` with every element `z` until nothing changes, without listing `P(H)`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::LargeHyperGroupoid;
+/// use hyperstruc::bitset::BitSet;
+///
+/// // The group Z_70, seen as a hypergroup.
+/// let cardinality = 70usize;
+/// let z70 = LargeHyperGroupoid::new_from_function(|a,b|BitSet::singleton(cardinality, (a+b)%cardinality), &cardinality);
+/// assert!(z70.is_hypergroup());
+/// assert_eq!(z70.collect_beta_classes().len(),70);
+/// ```
+pub fn collect_beta_classes(&self)->Vec<BitSet>{
+    let mut representative:Vec<usize> = (0..self.n).collect();
+    fn find(representative:&mut [usize],x:usize)->usize{
+        let mut root = x;
+        while representative[root]!=root {root=representative[root];}
+        representative[x]=root;
+        root
+    }
+    // puts the elements of the subset in the same class, returns true if two classes were merged
+    fn merge(representative:&mut [usize],subset:&BitSet)->bool{
+        let mut elements = subset.iter();
+        let Some(first) = elements.next() else {return false;};
+        let mut merged = false;
+        for x in elements {
+            let (r,s) = (find(representative, first),find(representative, x));
+            if r!=s {
+                representative[r.max(s)]=r.min(s);
+                merged = true;
+            }
+        }
+        merged
+    }
+    let classes = |representative:&mut [usize]|(0..self.n)
+        .into_group_map_by(|x|find(representative, *x))
+        .into_values()
+        .map(|class|BitSet::from_elements(self.n, &class))
+        .sorted()
+        .collect_vec();
+    loop {
+        let mut merged = false;
+        for class in classes(&mut representative) {
+            for z in 0..self.n {
+                merged|=merge(&mut representative, &self.product_with_element(&class, z, true));
+                merged|=merge(&mut representative, &self.product_with_element(&class, z, false));
+            }
+        }
+        if !merged {break classes(&mut representative);}
+    }
+}
+/// The hypergroupoid `T'` such that `T'[σa,σb]=σ(T[a,b])`, as [`HyperGroupoid::isomorphic_hypergroup_from_permutation`].
+pub fn isomorphic_hypergroup_from_permutation(&self,sigma:&Permutation)->Self{
+    let inverse:Vec<usize> = {
+        let mut inverse = vec![0usize;self.n];
+        (0..self.n).for_each(|x|inverse[sigma.apply_idx(x)]=x);
+        inverse
+    };
+    LargeHyperGroupoid::new_from_function(|a,b|
+        BitSet::from_elements(self.n, &self.entry(inverse[a], inverse[b]).iter().map(|x|sigma.apply_idx(x)).collect_vec()),
+        &self.n)
+}
+pub fn is_left_partial_identity_of_x(&self,e:&usize,x:&usize)->bool{
+    self.entry(*e, *x).contains(*x)
+}
+pub fn is_right_partial_identity_of_x(&self,e:&usize,x:&usize)->bool{
+    self.entry(*x, *e).contains(*x)
+}
+pub fn collect_partial_left_identities_of_x(&self,x:&usize)->Vec<usize>{
+    (0..self.n).filter(|e|self.is_left_partial_identity_of_x(e, x)).collect()
+}
+pub fn collect_partial_right_identities_of_x(&self,x:&usize)->Vec<usize>{
+    (0..self.n).filter(|e|self.is_right_partial_identity_of_x(e, x)).collect()
+}
+pub fn collect_partial_left_identities(&self)->Vec<usize>{
+    (0..self.n).filter(|e|(0..self.n).any(|x|self.is_left_partial_identity_of_x(e, &x))).collect()
+}
+pub fn collect_partial_right_identities(&self)->Vec<usize>{
+    (0..self.n).filter(|e|(0..self.n).any(|x|self.is_right_partial_identity_of_x(e, &x))).collect()
+}
+pub fn collect_partial_identities(&self)->Vec<usize>{
+    (0..self.n).filter(|e|(0..self.n).any(|x|self.is_left_partial_identity_of_x(e, &x)||self.is_right_partial_identity_of_x(e, &x))).collect()
+}
+/// Compute the canonical form of the hypergroupoid together with the permutation which reaches it, as
+/// [`HyperGroupoid::canonical_labelling`]. Canonical forms of large hypergroupoids are compared as tables of bitsets,
+/// so they need not agree with the canonical forms of [`HyperGroupoid`] when `n` is at most 64. The search explores
+/// more relabellings when the table has many automorphisms, e.g., it is slow on the total hypergroup of order 70.
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::LargeHyperGroupoid;
+/// use hyperstruc::bitset::BitSet;
+/// use permutation::Permutation;
+///
+/// let cardinality = 70usize;
+/// let hs = LargeHyperGroupoid::new_from_function(|a,b|BitSet::from_elements(cardinality, &[a,a.max(b)]), &cardinality);
+/// let (canonical,sigma) = hs.canonical_labelling();
+/// assert_eq!(hs.isomorphic_hypergroup_from_permutation(&sigma),canonical);
+/// let tau = Permutation::oneline((0..cardinality).map(|x|(3*x+7)%cardinality).collect::<Vec<usize>>());
+/// let isomorphic_hs = hs.isomorphic_hypergroup_from_permutation(&tau);
+/// assert!(hs.is_isomorphic_to(&isomorphic_hs));
+/// assert_eq!(hs.find_isomorphism(&isomorphic_hs).map(|sigma|hs.isomorphic_hypergroup_from_permutation(&sigma)),Some(isomorphic_hs));
+/// ```
+pub fn canonical_labelling(&self)->(Self,Permutation){
+    let (hyper_composition,labelling) = canonical_table(&self.hyper_composition, self.n);
+    (LargeHyperGroupoid { n: self.n, hyper_composition },Permutation::oneline(labelling))
+}
+/// Return a permutation `sigma` of `H` such that `self.isomorphic_hypergroup_from_permutation(&sigma) == *other`,
+/// if the two hypergroupoids are isomorphic.
+pub fn find_isomorphism(&self,other:&Self)->Option<Permutation>{
+    if self.n!=other.n {return None;}
+    let (canonical_self,sigma) = self.canonical_labelling();
+    let (canonical_other,tau) = other.canonical_labelling();
+    if canonical_self!=canonical_other {return None;}
+    let tau_inverse = tau.inverse();
+    Some(Permutation::oneline((0..self.n).map(|x|tau_inverse.apply_idx(sigma.apply_idx(x))).collect_vec()))
+}
+/// Return true if the two hypergroupoids are isomorphic. Equal tables and tables whose entries have different sizes
+/// are recognized before the canonical forms are computed, since the search for them is slow on very symmetric tables.
+pub fn is_isomorphic_to(&self,other:&Self)->bool{
+    let entry_sizes = |hs:&Self|hs.hyper_composition.iter().map(|entry|entry.len()).sorted().collect_vec();
+    self==other
+    ||
+    self.n==other.n&&entry_sizes(self)==entry_sizes(other)&&self.canonical_labelling().0==other.canonical_labelling().0
+}
+/// Returns `H/ρ` over `{0,1,...,m-1}`, where the `i`-th element is the `i`-th class of `ρ` ordered by least element, as
+/// [`HyperGroupoid::get_quotient`]. It returns `HyperStructureError::NotEquivalence` if `ρ` is not an equivalence on H.
+///
+/// # Example
+/// ```
+/// use std::collections::HashSet;
+/// use hyperstruc::large_hs::LargeHyperGroupoid;
+/// use hyperstruc::binary_relations::relations::Relation;
+/// use hyperstruc::bitset::BitSet;
+///
+/// // Z_100 modulo the subgroup of multiples of 25
+/// let cardinality = 100usize;
+/// let z100 = LargeHyperGroupoid::new_from_function(|a,b|BitSet::singleton(cardinality, (a+b)%cardinality), &cardinality);
+/// let h:HashSet<u64> = (0..100).collect();
+/// let rel = (0..100u64).flat_map(|x|(0..100u64).filter(move |y|(x+100-y)%25==0).map(move |y|(x,y))).collect();
+/// let z25 = z100.get_quotient(&Relation { a: h.clone(), b: h, rel }).unwrap();
+/// assert_eq!(z25,LargeHyperGroupoid::new_from_function(|a,b|BitSet::singleton(25, (a+b)%25), &25));
+/// ```
+pub fn get_quotient(&self,rho:&Relation)->Result<LargeHyperGroupoid,HyperStructureError>{
+    let h:HashSet<u64> = (0..self.n as u64).collect();
+    if rho.a!=h||rho.b!=h||!rho.is_equivalence() {
+        return Err(HyperStructureError::NotEquivalence);
+    }
+    let classes = rho.quotient_set().iter()
+        .map(|(_,class)|BitSet::from_elements(self.n, &class.iter().map(|x|*x as usize).collect_vec()))
+        .collect_vec();
+    Ok(self.quotient_by_classes(&classes))
+}
+/// The hypergroupoid on the given classes, which must partition H, where `i∘j` is the set of classes meeting the
+/// product of the `i`-th and the `j`-th class.
+fn quotient_by_classes(&self,classes:&[BitSet])->LargeHyperGroupoid{
+    let mut class_of = vec![0usize;self.n];
+    for (i,class) in classes.iter().enumerate() {
+        class.iter().for_each(|x|class_of[x]=i);
+    }
+    let m = classes.len();
+    LargeHyperGroupoid::new_from_function(|i,j|
+        BitSet::from_elements(m, &self.mul_by_representation(&classes[i], &classes[j]).iter().map(|z|class_of[z]).collect_vec()),
+        &m)
+}
+/// The set `Q(u)={(a,b) ∈ HxH | u ∈ ab}`, as [`HyperGroupoid::get_qq_u`].
+pub fn get_qq_u(&self,u:&usize)->Vec<(usize,usize)>{
+    (0..self.n).cartesian_product(0..self.n)
+        .filter(|(x,y)|self.entry(*x, *y).contains(*u))
+        .collect()
+}
+pub fn get_q_u(&self,u:&usize)->usize{
+    self.get_qq_u(u).len()
+}
+pub fn get_alpha_u(&self,u:&usize)->Rational64{
+    self.get_qq_u(u).into_iter()
+        .map(|(x,y)|Rational64::new(1, self.entry(x, y).len() as i64))
+        .sum()
+}
+pub fn get_mu_u(&self,u:&usize)->Rational64{
+    self.get_alpha_u(u)/Rational64::from(self.get_q_u(u) as i64)
+}
+/// The values `μ(u)` of the Corsini membership function of all the elements, computed in a single pass over the table.
+fn corsini_membership_values(&self)->Vec<Rational64>{
+    let mut q = vec![0i64;self.n];
+    let mut alpha = vec![Rational64::from(0);self.n];
+    for entry in &self.hyper_composition {
+        let weight = Rational64::new(1, entry.len() as i64);
+        for u in entry.iter() {
+            q[u]+=1;
+            alpha[u]+=weight;
+        }
+    }
+    alpha.into_iter().zip(q).map(|(alpha,q)|alpha/Rational64::from(q)).collect()
+}
+/// The join space associated with the Corsini fuzzy subset `μ`, i.e., `ab={z | min(μ(a),μ(b)) <= μ(z) <= max(μ(a),μ(b))}`,
+/// as `get_corsini_fuzzysubset().get_corsini_join_space()` for [`HyperGroupoid`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::LargeHyperGroupoid;
+/// use hyperstruc::bitset::BitSet;
+///
+/// // Z_70 has a constant membership function, so its join space is the total hypergroup.
+/// let cardinality = 70usize;
+/// let z70 = LargeHyperGroupoid::new_from_function(|a,b|BitSet::singleton(cardinality, (a+b)%cardinality), &cardinality);
+/// let join_space = z70.get_corsini_join_space();
+/// assert!(join_space.0.hyper_composition.iter().all(|entry|entry.is_full()));
+/// assert_eq!(z70.get_fuzzy_grade(),1);
+/// ```
+pub fn get_corsini_join_space(&self)->LargeHyperGroup{
+    let mu = self.corsini_membership_values();
+    let n = self.n;
+    //a and b belong to ab, so the join space is reproductive, and it is associative since ab is an interval of values
+    LargeHyperGroup(LargeHyperGroupoid::new_from_function(|a,b|{
+        let (low,high) = (mu[a].min(mu[b]),mu[a].max(mu[b]));
+        BitSet::from_elements(n, &(0..n).filter(|z|low<=mu[*z]&&mu[*z]<=high).collect_vec())
+    }, &n))
+}
+/// The fuzzy grade, i.e., the number of Corsini join spaces computed before two consecutive ones are isomorphic, as
+/// [`HyperGroupoid::get_fuzzy_grade`].
+pub fn get_fuzzy_grade(&self)->usize{
+    let (mut h_0,mut grade) = match self.is_hypergroup() {
+        true => (LargeHyperGroup(self.clone()),0),
+        false => (self.get_corsini_join_space(),1),
+    };
+    let mut h_1 = h_0.get_next_corsini_joinspace();
+    while !h_0.is_isomorphic_to(&h_1) {
+        h_0 = h_1;
+        h_1 = h_0.get_next_corsini_joinspace();
+        grade+=1;
+    }
+    grade
+}
+/// As [`LargeHyperGroupoid::get_fuzzy_grade`], but consecutive join spaces must be equal.
+pub fn get_strong_fuzzy_grade(&self)->usize{
+    let (mut h_0,mut grade) = match self.is_hypergroup() {
+        true => (LargeHyperGroup(self.clone()),0),
+        false => (self.get_corsini_join_space(),1),
+    };
+    let mut h_1 = h_0.get_next_corsini_joinspace();
+    while h_0!=h_1 {
+        h_0 = h_1;
+        h_1 = h_0.get_next_corsini_joinspace();
+        grade+=1;
+    }
+    grade
+}
+/// The smallest subset containing `subset` and closed under products.
+fn product_closure(&self,subset:BitSet)->BitSet{
+    let mut closure = subset;
+    let mut pending = closure.iter().collect_vec();
+    while let Some(x) = pending.pop() {
+        for y in closure.iter().collect_vec() {
+            for z in (self.entry(x, y)|self.entry(y, x)).iter() {
+                if !closure.contains(z) {
+                    closure.insert(z);
+                    pending.push(z);
+                }
+            }
+        }
+    }
+    closure
+}
+}
+impl Display for LargeHyperGroupoid{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\nH: {{0,...,{}}},\nHypercomposition table:", self.n-1)?;
+        for a in 0..self.n {
+            writeln!(f, "{}", (0..self.n).map(|b|self.entry(a, b).to_string()).join(" "))?;
+        }
+        writeln!(f, "Size:{}", self.n)
+    }
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct LargeHyperGroup(pub LargeHyperGroupoid);
+
+impl LargeHyperGroup {
+/// Returns the hypergroup defined by `h`, or the first reason why `h` is not a hypergroup, as
+/// [`HyperGroup::try_new_from_hypergroupoid`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::{LargeHyperGroup, LargeHyperGroupoid};
+/// use hyperstruc::bitset::BitSet;
+/// use hyperstruc::diagnostics::{Side, Violation};
+/// use hyperstruc::hypergroups::HyperStructureError;
+///
+/// // a,b -> {a} is associative, but aH={a}
+/// let cardinality = 100usize;
+/// let hs = LargeHyperGroupoid::new_from_function(|a,_|BitSet::singleton(cardinality, a), &cardinality);
+/// match LargeHyperGroup::try_new_from_hypergroupoid(&hs) {
+///     Err(HyperStructureError::NotReproductive(Violation::LargeReproductivity { x, side, product })) => {
+///         assert_eq!((x,side),(0,Side::Left));
+///         assert_eq!(product,BitSet::singleton(cardinality, 0));
+///     },
+///     _ => panic!("It is not reproductive!")
+/// }
+/// let hs = LargeHyperGroupoid::new_from_function(|a,b|BitSet::singleton(cardinality, (a+2*b)%cardinality), &cardinality);
+/// assert!(matches!(LargeHyperGroup::try_new_from_hypergroupoid(&hs),Err(HyperStructureError::NotAssociative(_))));
+/// ```
+pub fn try_new_from_hypergroupoid(h:&LargeHyperGroupoid)->Result<Self,HyperStructureError>{
+    h.check_associativity().map_err(HyperStructureError::NotAssociative)?;
+    h.check_reproductivity().map_err(HyperStructureError::NotReproductive)?;
+    Ok(LargeHyperGroup(h.clone()))
+}
+pub fn new_from_hypergroupoid(h:&LargeHyperGroupoid)->Self{
+    assert!(h.is_hypergroup(),"Not an hypergroup!");
+    LargeHyperGroup(h.clone())
+}
+pub fn new_from_function<F>(function:F,cardinality:&usize)->Result<Self,HyperStructureError>
+    where F: Fn(usize,usize) -> BitSet {
+    LargeHyperGroup::try_new_from_hypergroupoid(&LargeHyperGroupoid::try_new_from_function(function, cardinality)?)
+}
+pub fn try_new_from_elements(input_array:&[Vec<usize>],cardinality:&usize)->Result<Self,HyperStructureError>{
+    LargeHyperGroup::try_new_from_hypergroupoid(&LargeHyperGroupoid::try_new_from_elements(input_array, cardinality)?)
+}
+pub fn try_new_from_matrix(matrix:&DMatrix<BitSet>)->Result<Self,HyperStructureError>{
+    LargeHyperGroup::try_new_from_hypergroupoid(&LargeHyperGroupoid::try_new_from_matrix(matrix)?)
+}
+pub fn new_from_hypergroup(hg:&HyperGroup)->Self{
+    LargeHyperGroup(LargeHyperGroupoid::new_from_hypergroupoid(&hg.0))
+}
+pub fn cardinality(&self)->usize{
+    self.0.n
+}
+pub fn get_integer_tag_u1024(&self)->Option<U1024>{
+    self.0.get_integer_tag_u1024()
+}
+pub fn mul_by_representation(&self,subset_a:&BitSet,subset_b:&BitSet)->BitSet{
+    self.0.mul_by_representation(subset_a, subset_b)
+}
+pub fn left_division(&self,a:&BitSet,b:&BitSet)->BitSet{
+    self.0.left_division(a, b)
+}
+pub fn right_division(&self,a:&BitSet,b:&BitSet)->BitSet{
+    self.0.right_division(a, b)
+}
+pub fn is_commutative(&self)->bool{
+    self.0.is_commutative()
+}
+/// Return true if the hypergroup is transposition, i.e., if `b\a meets c/d implies ad meets bc`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::LargeHyperGroup;
+/// use hyperstruc::bitset::BitSet;
+///
+/// let cardinality = 66usize;
+/// let hg = LargeHyperGroup::new_from_function(|a,b|BitSet::from_elements(cardinality, &[a,b]), &cardinality).unwrap();
+/// assert!(hg.is_join_space());
+/// ```
+pub fn is_transposition(&self)->bool{
+    let n = self.cardinality();
+    let singletons = self.0.get_singleton();
+    let divisions:Vec<(BitSet,BitSet)> = (0..n).cartesian_product(0..n)
+        .map(|(a,b)|(self.left_division(&singletons[a], &singletons[b]),self.right_division(&singletons[a], &singletons[b])))
+        .collect();
+    (0..n).cartesian_product(0..n).cartesian_product((0..n).cartesian_product(0..n))
+        .all(|((a,b),(c,d))|
+            !divisions[a*n+b].0.intersects(&divisions[c*n+d].1)
+            ||
+            self.0.entry(a, d).intersects(self.0.entry(b, c)))
+}
+pub fn is_quasicanonical(&self)->bool{
+    self.is_transposition()&&!self.0.collect_scalar_identities().is_empty()
+}
+pub fn is_join_space(&self)->bool{
+    self.is_commutative()&&self.is_transposition()
+}
+pub fn is_canonical(&self)->bool{
+    self.is_commutative()&&self.is_quasicanonical()
+}
+pub fn collect_identities(&self)->Vec<usize>{
+    self.0.collect_identities()
+}
+pub fn collect_scalars(&self)->Vec<usize>{
+    self.0.collect_scalars()
+}
+pub fn collect_left_scalars(&self)->Vec<usize>{
+    self.0.collect_left_scalars()
+}
+pub fn collect_right_scalars(&self)->Vec<usize>{
+    self.0.collect_right_scalars()
+}
+pub fn collect_scalars_identities(&self)->Vec<usize>{
+    self.0.collect_scalar_identities()
+}
+pub fn collect_inverses_of_x(&self,x:&usize)->Vec<(usize,BitSet)>{
+    self.0.collect_inverses_of_x(x)
+}
+/// Return true if `K` is a subhypergroup, i.e., if `xK = K = Kx` for all `x` in `K`.
+pub fn is_sub_hypergroup(&self,k:&BitSet)->bool{
+    !k.is_empty()
+    &&
+    k.iter().all(|x|
+        self.0.product_with_element(k, x, true)==*k
+        &&
+        self.0.product_with_element(k, x, false)==*k)
+}
+pub fn subhypergroup_is_closed(&self,subset_k:&BitSet)->bool{
+    if !self.is_sub_hypergroup(subset_k) {return false;}
+    let kc = subset_k.complement();
+    self.mul_by_representation(&kc, subset_k)==kc&&self.mul_by_representation(subset_k, &kc)==kc
+}
+pub fn subhypergroup_is_reflexive(&self,subset_k:&BitSet)->bool{
+    if !self.is_sub_hypergroup(subset_k) {return false;}
+    self.0.get_singleton().iter()
+        .all(|x|self.right_division(subset_k, x)==self.left_division(subset_k, x))
+}
+/// Return true if the subhypergroup is normal, i.e., if `xK = Kx` for all `x` in `H`.
+pub fn subhypergroup_is_normal(&self,subset_k:&BitSet)->bool{
+    if !self.is_sub_hypergroup(subset_k) {return false;}
+    (0..self.cardinality())
+        .all(|x|self.0.product_with_element(subset_k, x, false)==self.0.product_with_element(subset_k, x, true))
+}
+pub fn subhypergroup_is_right_invertible(&self,subset_k:&BitSet)->bool{
+    if !self.is_sub_hypergroup(subset_k) {return false;}
+    let products:Vec<BitSet> = (0..self.cardinality()).map(|x|self.0.product_with_element(subset_k, x, true)).collect();
+    (0..self.cardinality()).cartesian_product(0..self.cardinality())
+        .all(|(x,y)|!products[y].contains(x)||products[x].contains(y))
+}
+pub fn subhypergroup_is_left_invertible(&self,subset_k:&BitSet)->bool{
+    if !self.is_sub_hypergroup(subset_k) {return false;}
+    let products:Vec<BitSet> = (0..self.cardinality()).map(|x|self.0.product_with_element(subset_k, x, false)).collect();
+    (0..self.cardinality()).cartesian_product(0..self.cardinality())
+        .all(|(x,y)|!products[y].contains(x)||products[x].contains(y))
+}
+pub fn subhypergroup_is_invertible(&self,subset_k:&BitSet)->bool{
+    self.subhypergroup_is_left_invertible(subset_k)&&self.subhypergroup_is_right_invertible(subset_k)
+}
+/// The relation `β`, which coincides with `β*` on hypergroups, so it is built from
+/// [`LargeHyperGroupoid::collect_beta_classes`].
+pub fn beta_relation(&self)->Relation{
+    let rel:Vec<(u64,u64)> = self.collect_beta_classes().iter()
+        .flat_map(|class|class.iter().cartesian_product(class.iter()).map(|(x,y)|(x as u64,y as u64)).collect_vec())
+        .sorted()
+        .collect();
+    let h:HashSet<u64> = (0..self.cardinality() as u64).collect();
+    Relation { a: h.clone(), b: h, rel }
+}
+pub fn collect_beta_classes(&self)->Vec<BitSet>{
+    self.0.collect_beta_classes()
+}
+pub fn collect_partial_identities(&self)->Vec<usize>{
+    self.0.collect_partial_identities()
+}
+pub fn is_isomorphic_to(&self,other:&Self)->bool{
+    self.0.is_isomorphic_to(&other.0)
+}
+pub fn find_isomorphism(&self,other:&Self)->Option<Permutation>{
+    self.0.find_isomorphism(&other.0)
+}
+pub fn get_next_corsini_joinspace(&self)->Self{
+    self.0.get_corsini_join_space()
+}
+pub fn get_fuzzy_grade(&self)->usize{
+    self.0.get_fuzzy_grade()
+}
+pub fn get_strong_fuzzy_grade(&self)->usize{
+    self.0.get_strong_fuzzy_grade()
+}
+/// Collect the proper subhypergroups, sorted. Subhypergroups are closed under products, so the search branches on
+/// the elements of H and only visits subsets closed under products. Its cost is proportional to their number, which
+/// can be exponential: every non-empty subset of `a,b -> {a,b}` is a subhypergroup.
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::LargeHyperGroup;
+/// use hyperstruc::bitset::BitSet;
+///
+/// // The subgroups of Z_72 are the multiples of its divisors.
+/// let cardinality = 72usize;
+/// let z72 = LargeHyperGroup::new_from_function(|a,b|BitSet::singleton(cardinality, (a+b)%cardinality), &cardinality).unwrap();
+/// let subhypergroups = z72.collect_proper_subhypergroups();
+/// assert_eq!(subhypergroups.len(),11);
+/// assert!(subhypergroups.contains(&BitSet::from_elements(cardinality, &[0,24,48])));
+/// assert_eq!(z72.collect_proper_normal_subhypergroups(),subhypergroups);
+/// ```
+pub fn collect_proper_subhypergroups(&self)->Vec<BitSet>{
+    let n = self.cardinality();
+    let mut subhypergroups = Vec::new();
+    self.collect_subhypergroups_between(BitSet::new(n), BitSet::new(n), 0, &mut subhypergroups);
+    subhypergroups.into_iter().filter(|k|!k.is_full()).sorted().collect()
+}
+/// Collects the subhypergroups containing `included`, which is closed under products, and not meeting `excluded`,
+/// deciding the elements from `next` on.
+fn collect_subhypergroups_between(&self,included:BitSet,mut excluded:BitSet,next:usize,found:&mut Vec<BitSet>){
+    let Some(x) = (next..self.cardinality()).find(|x|!included.contains(*x)&&!excluded.contains(*x)) else {
+        if self.is_sub_hypergroup(&included) {
+            found.push(included);
+        }
+        return;
+    };
+    let mut with_x = included.clone();
+    with_x.insert(x);
+    let closure = self.0.product_closure(with_x);
+    if !closure.intersects(&excluded) {
+        self.collect_subhypergroups_between(closure, excluded.clone(), x+1, found);
+    }
+    excluded.insert(x);
+    self.collect_subhypergroups_between(included, excluded, x+1, found);
+}
+pub fn collect_proper_invertible_subhypergroups(&self)->Vec<BitSet>{
+    self.collect_proper_subhypergroups().into_iter().filter(|k|self.subhypergroup_is_invertible(k)).collect()
+}
+pub fn collect_proper_closed_subhypergroups(&self)->Vec<BitSet>{
+    self.collect_proper_subhypergroups().into_iter().filter(|k|self.subhypergroup_is_closed(k)).collect()
+}
+pub fn collect_proper_reflexive_subhypergroups(&self)->Vec<BitSet>{
+    self.collect_proper_subhypergroups().into_iter().filter(|k|self.subhypergroup_is_reflexive(k)).collect()
+}
+pub fn collect_proper_normal_subhypergroups(&self)->Vec<BitSet>{
+    self.collect_proper_subhypergroups().into_iter().filter(|k|self.subhypergroup_is_normal(k)).collect()
+}
+/// The fundamental group `H/β*` together with the `β*`-classes, the `i`-th element of the group being the `i`-th
+/// class of [`LargeHyperGroupoid::collect_beta_classes`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::large_hs::LargeHyperGroup;
+/// use hyperstruc::bitset::BitSet;
+///
+/// // a+b -> {a+b, a+b+35} on Z_70, whose fundamental group is Z_35 and whose heart is {0,35}
+/// let cardinality = 70usize;
+/// let hg = LargeHyperGroup::new_from_function(|a,b|BitSet::from_elements(cardinality, &[(a+b)%70,(a+b+35)%70]), &cardinality).unwrap();
+/// let (fundamental_group,classes) = hg.get_fundamental_group();
+/// assert_eq!(classes.len(),35);
+/// assert!(fundamental_group.0.hyper_composition.iter().all(|entry|entry.is_singleton()));
+/// assert_eq!(hg.heart(),BitSet::from_elements(cardinality, &[0,35]));
+/// ```
+pub fn get_fundamental_group(&self)->(LargeHyperGroup,Vec<BitSet>){
+    let classes = self.collect_beta_classes();
+    (LargeHyperGroup(self.0.quotient_by_classes(&classes)),classes)
+}
+pub fn get_isomorphic_fundamental_group(&self)->LargeHyperGroup{
+    self.get_fundamental_group().0
+}
+/// The heart of the hypergroup, i.e., the `β*`-class of the identity of the fundamental group.
+pub fn heart(&self)->BitSet{
+    let (fundamental_group,mut classes) = self.get_fundamental_group();
+    let identity = fundamental_group.collect_identities();
+    assert!(identity.len()==1);
+    classes.swap_remove(identity[0])
+}
+}
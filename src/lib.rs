@@ -13,6 +13,28 @@ pub mod unital_magma;
 pub mod fuzzy;
 pub mod generating_functions;
 pub mod quotient_hg;
+pub mod canonical;
+pub mod automorphisms;
+pub mod diagnostics;
+pub mod bitset;
+pub mod large_hs;
+pub mod small_hs;
+pub mod product_table;
+pub mod associativity;
+pub mod incremental;
+pub mod fundamental_relations;
+pub mod complete_parts;
+pub mod homomorphisms;
+pub mod products;
+pub mod subhypergroup_lattice;
+pub mod cosets;
+pub mod conjugation;
+pub mod polygroup;
+pub mod hyperring;
+pub mod finite_fields;
+pub mod hyperfields;
+pub mod polynomials;
+pub mod hypermodule;
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -348,7 +370,559 @@ let cardinality  =8u64;
     assert_eq!(degree,2usize);
 
     }
+    #[test]
+    fn automorphism_groups_of_order_3(){
+        use crate::{hg_3::representants_3::TAG_3_REPRESENTANTS, utilities::U1024};
+        let cardinality = 3u64;
+        let hypergroups:usize = TAG_3_REPRESENTANTS.iter()
+            .map(|tag|{
+                let hs = HyperGroupoid::new_from_tag_u1024(&U1024::from(*tag), &cardinality);
+                let group = hs.automorphism_group();
+                assert!(group.generators.iter().all(|sigma|hs.is_automorphism(sigma)));
+                6/group.order
+            })
+            .sum();
+        assert_eq!(hypergroups,23192);
+    }
+    #[test]
+    fn large_backend_agrees_with_u64_backend(){
+        use crate::{bitset::BitSet, large_hs::{LargeHyperGroup, LargeHyperGroupoid}};
+        let cardinality = 4u64;
+        for _ in 0..50 {
+            let hs = HyperGroupoid::new_random_from_cardinality(&cardinality);
+            let large = LargeHyperGroupoid::new_from_hypergroupoid(&hs);
+            assert_eq!(large.is_associative(),hs.is_associative());
+            assert_eq!(large.is_reproductive(),hs.is_reproductive());
+            assert_eq!(large.is_commutative(),hs.is_commutative());
+            assert_eq!(large.collect_identities().iter().map(|e|1u64<<e).collect::<Vec<u64>>(),hs.collect_identities());
+            let ph:Vec<u64> = large.collect_ph().iter().map(|q|q.to_u64().unwrap()).collect();
+            assert_eq!(ph,hs.collect_ph());
+            assert_eq!(large.to_hypergroupoid(),Some(hs.clone()));
+            assert_eq!(large.collect_partial_identities().iter().map(|e|1u64<<e).collect::<Vec<u64>>(),hs.collect_partial_identities());
+            assert_eq!(large.collect_left_scalars().iter().map(|s|1u64<<s).collect::<Vec<u64>>(),hs.collect_left_scalars());
+            assert_eq!(large.collect_right_scalars().iter().map(|s|1u64<<s).collect::<Vec<u64>>(),hs.collect_right_scalars());
+            for u in 0..4 {
+                assert_eq!(large.get_q_u(&(u as usize)),hs.get_q_u(&u));
+                assert_eq!(large.get_alpha_u(&(u as usize)),hs.get_alpha_u(&u));
+            }
+            let other = HyperGroupoid::new_random_from_cardinality(&cardinality);
+            let sigma = permutation::Permutation::oneline(vec![2,0,3,1]);
+            let permuted = LargeHyperGroupoid::new_from_hypergroupoid(&hs.isomorphic_hypergroup_from_permutation(&sigma));
+            assert!(large.is_isomorphic_to(&permuted));
+            assert_eq!(large.is_isomorphic_to(&LargeHyperGroupoid::new_from_hypergroupoid(&other)),hs.is_isomorphic_to(&other));
+        }
+        use crate::{hg_3::representants_3::TAG_3_REPRESENTANTS, utilities::U1024};
+        for tag in TAG_3_REPRESENTANTS.iter().step_by(50) {
+            let hg = HyperGroup::new_from_tag_u1024(&U1024::from(*tag), &3u64);
+            let large = LargeHyperGroup::new_from_hypergroup(&hg);
+            assert_eq!(large.is_transposition(),hg.is_transposition());
+            let beta_classes:Vec<Vec<u64>> = large.collect_beta_classes().iter().map(|class|class.iter().map(|x|x as u64).collect()).collect();
+            let expected:Vec<Vec<u64>> = hg.collect_beta_classes().into_iter().map(|(_,class)|class).collect();
+            assert_eq!(beta_classes,expected);
+            for k in 1..8u64 {
+                assert_eq!(large.is_sub_hypergroup(&BitSet::from_u64(3, k)),hg.is_sub_hypergroup(&k));
+            }
+            let subhypergroups:Vec<u64> = large.collect_proper_subhypergroups().iter().map(|k|k.to_u64().unwrap()).collect();
+            assert_eq!(subhypergroups,hg.collect_proper_subhypergroups());
+            let closed:Vec<u64> = large.collect_proper_closed_subhypergroups().iter().map(|k|k.to_u64().unwrap()).collect();
+            assert_eq!(closed,hg.collect_proper_closed_subhypergroups());
+            assert_eq!(large.heart().iter().map(|x|x as u64).collect::<HashSet<u64>>(),hg.heart());
+            assert_eq!(large.get_isomorphic_fundamental_group().cardinality() as u64,hg.get_isomorphic_fundamental_group().cardinality());
+            assert_eq!(large.get_fuzzy_grade(),hg.get_fuzzy_grade());
+            assert_eq!(large.get_strong_fuzzy_grade(),hg.get_strong_fuzzy_grade());
+        }
+    }
+    #[test]
+    fn fundamental_group_of_large_hypergroups(){
+        use crate::{bitset::BitSet, large_hs::{LargeHyperGroup, LargeHyperGroupoid}};
+        // both tables are hypergroups, the O(n^3) check of the axioms is skipped to keep the test fast
+        // a,b -> {a,b} is not a group, and P(H) contains every non empty subset
+        let cardinality = 120usize;
+        let hg = LargeHyperGroup(LargeHyperGroupoid::new_from_function(|a,b|BitSet::from_elements(cardinality, &[a,b]), &cardinality));
+        assert_eq!(hg.collect_beta_classes(),vec![BitSet::full(cardinality)]);
+        assert_eq!(hg.get_isomorphic_fundamental_group().cardinality(),1);
+        assert!(hg.heart().is_full());
+        assert_eq!(hg.beta_relation().rel.len(),cardinality*cardinality);
+        // a,b -> {a+b,a+b+40,a+b+80} on Z_120, whose fundamental group is Z_40 and whose heart is {0,40,80}
+        let hg = LargeHyperGroup(LargeHyperGroupoid::new_from_function(|a,b|BitSet::from_elements(cardinality, &[(a+b)%120,(a+b+40)%120,(a+b+80)%120]), &cardinality));
+        let (fundamental_group,classes) = hg.get_fundamental_group();
+        assert_eq!(classes.len(),40);
+        assert!(classes.iter().all(|class|class.len()==3));
+        assert!(fundamental_group.0.hyper_composition.iter().all(|entry|entry.is_singleton()));
+        assert_eq!(hg.heart(),BitSet::from_elements(cardinality, &[0,40,80]));
+    }
+    #[test]
+    fn small_backend_agrees_with_hypergroupoid(){
+        use crate::{small_hs::SmallHyperGroupoid, utilities::{get_min_max, representing_hypergroupoid}};
+        let cardinality = 2u64;
+        let (min,max) = get_min_max(&cardinality);
+        for tag in min..=max {
+            let small = SmallHyperGroupoid::<2>::try_new_from_tag_u128(&tag);
+            assert_eq!(small.is_ok(),representing_hypergroupoid(&tag, &cardinality));
+            if let Ok(small) = small {
+                let hs = HyperGroupoid::new_from_tag_u128(&tag, &cardinality);
+                assert_eq!(small.get_integer_tag(),tag);
+                assert_eq!(small.is_hypergroup(),hs.is_hypergroup());
+                assert_eq!(small.is_weak_associative(),hs.is_weak_associative());
+                assert_eq!(small.collect_identities(),hs.collect_identities());
+                assert_eq!(small.collect_scalar_identities(),hs.collect_scalar_identities());
+                assert_eq!(small.collect_ph(),hs.collect_ph());
+                assert_eq!(small.beta_relation(),hs.beta_relation());
+            }
+        }
+        for _ in 0..20 {
+            let hs = HyperGroupoid::new_random_from_cardinality(&7u64);
+            let small = SmallHyperGroupoid::<7>::try_from(&hs).unwrap();
+            assert_eq!(small.is_associative(),hs.is_associative());
+            assert_eq!(small.is_reproductive(),hs.is_reproductive());
+            assert_eq!(small.collect_scalars(),hs.collect_scalars());
+            assert_eq!(HyperGroupoid::from(small),hs);
+        }
+        assert!(SmallHyperGroupoid::<3>::try_from(&HyperGroupoid::new_random_from_cardinality(&4u64)).is_err());
+    }
+    #[test]
+    fn product_table_agrees_with_hypergroupoid(){
+        use itertools::Itertools;
+        use crate::utilities::support;
+        for cardinality in [5u64,9] {
+            let hs = HyperGroupoid::new_random_from_cardinality(&cardinality);
+            let table = hs.product_table();
+            assert_eq!(table.has_subset_table(),cardinality<=8);
+            for (a,b) in (1u64..1<<cardinality).step_by(7).cartesian_product((1u64..1<<cardinality).step_by(5)) {
+                let expected = support(&a, &cardinality).into_iter()
+                    .cartesian_product(support(&b, &cardinality))
+                    .fold(0, |acc,(x,y)|acc|hs.hyper_composition[(x,y)]);
+                assert_eq!(table.mul_by_representation(&a, &b),expected);
+                assert_eq!(hs.mul_by_representation(&a, &b),expected);
+            }
+            assert_eq!(table.is_associative(),hs.check_associativity().is_ok());
+        }
+    }
+    #[test]
+    fn light_and_bit_sliced_associativity_agree_with_naive_test(){
+        use crate::{associativity::BitSlicedTables, hg_3::representants_3::TAG_3_REPRESENTANTS, utilities::U1024};
+        let cardinality = 3u64;
+        let hypergroupoids:Vec<HyperGroupoid> = TAG_3_REPRESENTANTS.iter().take(32)
+            .map(|tag|HyperGroupoid::new_from_tag_u1024(&U1024::from(*tag), &cardinality))
+            .chain((0..32).map(|_|HyperGroupoid::new_random_from_cardinality(&cardinality)))
+            .collect();
+        let tables = BitSlicedTables::from_hypergroupoids(&hypergroupoids, &cardinality);
+        let (associative,reproductive) = (tables.associative_mask(),tables.reproductive_mask());
+        assert_eq!(tables.valid_mask(),u64::MAX);
+        for (lane,hs) in hypergroupoids.iter().enumerate() {
+            assert_eq!(hs.is_associative_light(),hs.is_associative());
+            assert_eq!((associative>>lane)&1==1,hs.is_associative());
+            assert_eq!((reproductive>>lane)&1==1,hs.is_reproductive());
+        }
+        let tags:Vec<u128> = hypergroupoids.iter().map(|hs|hs.get_integer_tag()).collect();
+        assert_eq!(BitSlicedTables::from_tags_u128(&tags, &cardinality),tables);
+    }
+    #[test]
+    fn incremental_checks_agree_with_full_checks_along_a_walk(){
+        use rand::Rng;
+        use crate::incremental::MutableHyperGroupoid;
+        let cardinality = 4u64;
+        let hs = HyperGroupoid::new_from_function(|a,b|1<<a|1<<b, &cardinality);
+        let mut editor = MutableHyperGroupoid::new(&hs);
+        let mut rng = rand::thread_rng();
+        for _ in 0..300 {
+            let position = rng.gen_range(0..64usize);
+            // the cell and the bit of the entry that flip_tag_bit changes
+            let n = cardinality as usize;
+            let (cell,k) = (n*n-1-position/n,position%n);
+            let (a,b) = (cell/n,cell%n);
+            let probed = editor.probe_entry(a, b, editor.entry(a, b)^(1<<k));
+            let is_hypergroup = editor.flip_tag_bit(position);
+            assert_eq!(probed,is_hypergroup);
+            assert_eq!(probed,editor.to_hypergroupoid().is_some_and(|current|current.is_hypergroup()));
+            let tag = editor.get_integer_tag_u1024();
+            assert_eq!(editor,MutableHyperGroupoid::new_from_tag_u1024(&tag, &cardinality).unwrap());
+            match editor.to_hypergroupoid() {
+                Some(current) => {
+                    assert_eq!(current.get_integer_tag_u1024(),tag);
+                    assert_eq!(is_hypergroup,current.is_hypergroup());
+                    assert_eq!(editor.collect_failing_triples().len(),current.collect_associativity_violations().len());
+                    let mut neighbours = editor.collect_hypergroups_at_distance_one();
+                    let mut expected = crate::hs::circumference_radius_d_filtered(&tag, &1, &cardinality);
+                    neighbours.sort();
+                    expected.sort();
+                    assert_eq!(neighbours,expected);
+                }
+                None => assert!(!is_hypergroup),
+            }
+        }
+    }
+    #[test]
+    fn heart_is_the_identity_class_of_the_fundamental_group(){
+        use itertools::Itertools;
+        use crate::{hg_3::representants_3::TAG_3_REPRESENTANTS, hypergroups::HyperGroup, utilities::U1024};
+        let cardinality = 3u64;
+        for tag in TAG_3_REPRESENTANTS.iter().step_by(3) {
+            let hg = HyperGroup::new_from_tag_u1024(&U1024::from(*tag), &cardinality);
+            let beta = hg.beta_relation();
+            let heart = hg.heart();
+            assert!(heart.iter().all(|x|beta.get_class(x).1.iter().all(|y|heart.contains(y))));
+            for (x,y) in heart.iter().cartesian_product(0..cardinality) {
+                let product = hg.mul_by_representation(&(1<<x), &(1<<y));
+                assert!((0..cardinality).filter(|z|product>>z&1==1).all(|z|beta.rel.contains(&(z,y))));
+            }
+        }
+        // Z_2 with 1 inflated to {0,1} and 0 to {2,3}: the heart {2,3} is the second β-class.
+        let parity = |x:u64|if x<2 {1} else {0};
+        let hg = HyperGroup::new_from_function(|a,b|if (parity(a)+parity(b))%2==1 {0b0011} else {0b1100}, &4).unwrap();
+        assert_eq!(hg.heart(),HashSet::from([2,3]));
+    }
+    #[test]
+    fn gamma_classes_are_unions_of_beta_classes(){
+        use crate::{hg_3::representants_3::TAG_3_REPRESENTANTS, hypergroups::HyperGroup, utilities::U1024};
+        let cardinality = 3u64;
+        for tag in TAG_3_REPRESENTANTS.iter().step_by(7) {
+            let hg = HyperGroup::new_from_tag_u1024(&U1024::from(*tag), &cardinality);
+            let beta_classes = hg.collect_beta_classes();
+            let gamma_classes = hg.collect_gamma_classes();
+            assert!(beta_classes.iter().all(|(_,beta)|
+                gamma_classes.iter().any(|(_,gamma)|beta.iter().all(|x|gamma.contains(x)))));
+            assert!(hg.heart().is_subset(&hg.gamma_heart()));
+            let group = hg.get_isomorphic_abelian_fundamental_group();
+            assert!(group.is_commutative());
+            assert!(group.0.hyper_composition.iter().all(|entry|entry.count_ones()==1));
+            let quotient = hg.get_abelian_fundamental_group().0;
+            assert_eq!(quotient.n as usize,gamma_classes.len());
+            assert!(quotient.hyper_composition.iter().all(|entry|entry.len()==1&&gamma_classes.iter().any(|(_,class)|*class==entry[0])));
+            if hg.is_commutative() {
+                assert_eq!(gamma_classes,beta_classes);
+            }
+        }
+    }
+    #[test]
+    fn alpha_closure_is_gamma_and_contains_epsilon(){
+        use crate::{hg_3::representants_3::TAG_3_REPRESENTANTS, utilities::U1024};
+        let cardinality = 3u64;
+        for tag in TAG_3_REPRESENTANTS.iter().step_by(11) {
+            let hs = HyperGroupoid::new_from_tag_u1024(&U1024::from(*tag), &cardinality);
+            let gamma = hs.gamma_relation();
+            assert_eq!(hs.alpha_star_relation(),gamma);
+            assert!(hs.epsilon_star_relation().rel.iter().all(|pair|gamma.rel.contains(pair)));
+            // β* = γ* = ε* on every hypergroup of order 3
+            assert!(hs.fundamental_relations_report().all_equal());
+        }
+        // the hypergroupoids of order 2 where β* is the equality while γ* = ε* has a single class
+        let failing:Vec<u128> = (0u128..256)
+            .filter(|tag|HyperGroupoid::try_new_from_tag_u128(tag, &2).is_ok_and(|hs|!hs.fundamental_relations_report().all_equal()))
+            .collect();
+        assert_eq!(failing,vec![89,90,101,102,153,154,165,166]);
+        for tag in failing {
+            let report = HyperGroupoid::new_from_tag_u128(&tag, &2).fundamental_relations_report();
+            assert_eq!(report.beta_classes,vec![(0,vec![0]),(1,vec![1])]);
+            assert_eq!(report.gamma_classes,vec![(0,vec![0,1])]);
+            assert!(report.gamma_equals_epsilon());
+        }
+        // in S_3, β* is the equality and ε* is the conjugacy
+        let s3 = [[0,1,2,3,4,5],[1,0,4,5,2,3],[2,3,0,1,5,4],[3,2,5,4,0,1],[4,5,1,0,3,2],[5,4,3,2,1,0]];
+        let report = HyperGroupoid::new_from_function(|a,b|1<<s3[a as usize][b as usize], &6).fundamental_relations_report();
+        assert_eq!(report.beta_classes,(0..6).map(|x|(x,vec![x])).collect::<Vec<(u64,Vec<u64>)>>());
+        assert_eq!(report.gamma_classes,vec![(0,vec![0,3,4]),(1,vec![1,2,5])]);
+        assert_eq!(report.epsilon_classes,vec![(0,vec![0]),(1,vec![1,2,5]),(3,vec![3,4])]);
+        assert!(!report.all_equal());
+        // β is not transitive here, the report holds the classes of its transitive closure β*
+        let hs = HyperGroupoid::new_from_matrix(&DMatrix::from_row_slice(4, 4, &[6,10,10,10,10,10,10,10,10,10,10,10,10,10,10,10]));
+        assert!(!hs.beta_relation().is_transitive());
+        let report = hs.fundamental_relations_report();
+        assert_eq!(report.beta_classes,vec![(0,vec![0]),(1,vec![1,2,3])]);
+        assert!(report.all_equal());
+    }
+    #[test]
+    fn complete_hypergroups_of_order_three(){
+        use itertools::Itertools;
+        use crate::{enumeration::filter_complete_hypergroups, hg_3::representants_3::TAG_3_REPRESENTANTS, hypergroups::HyperGroup};
+        let cardinality = 3u64;
+        // the total hypergroup, Z_3, and Z_2 with a class of size 1 or 2 as identity class
+        let complete = filter_complete_hypergroups(&TAG_3_REPRESENTANTS, &cardinality);
+        assert_eq!(complete.len(),4);
+        for tag in TAG_3_REPRESENTANTS.iter().step_by(13) {
+            let hg = HyperGroup::new_from_tag_u128(tag, &cardinality);
+            for (x,class) in hg.collect_beta_classes() {
+                assert_eq!(hg.complete_closure(&(1<<x)),class.iter().fold(0, |acc,y|acc|1<<y));
+            }
+            let parts = hg.collect_complete_parts();
+            assert!((1u64..8).all(|subset|hg.is_complete_part(&subset)==parts.contains(&subset)));
+            assert_eq!(hg.is_complete(),(0..3).cartesian_product(0..3).all(|(a,b)|hg.0.is_complete_part(&hg.0.hyper_composition[(a,b)])));
+        }
+    }
+    #[test]
+    fn fundamental_relations_are_strongly_regular(){
+        use crate::{hg_3::representants_3::TAG_3_REPRESENTANTS, hypergroups::HyperGroup};
+        let cardinality = 3u64;
+        for tag in TAG_3_REPRESENTANTS.iter().step_by(17) {
+            let hg = HyperGroup::new_from_tag_u128(tag, &cardinality);
+            let (beta,gamma) = (hg.beta_relation(),hg.gamma_relation());
+            assert!(beta.is_strongly_regular(&hg.0)&&gamma.is_strongly_regular(&hg.0));
+            assert!(beta.is_regular(&hg.0));
+            assert_eq!(hg.get_quotient_group(&beta).unwrap(),hg.get_isomorphic_fundamental_group());
+            assert_eq!(hg.get_quotient_group(&gamma).unwrap(),hg.get_isomorphic_abelian_fundamental_group());
+            let epsilon = hg.epsilon_star_relation();
+            assert_eq!(epsilon.is_strongly_regular(&hg.0),hg.0.get_strongly_regular_quotient(&epsilon).is_ok());
+        }
+    }
+    #[test]
+    fn quotients_use_the_classes_of_the_given_relation(){
+        use crate::hs::QuotientHyperGroupoid;
+        let cardinality = 4u64;
+        let z4 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality);
+        // β* is the equality on Z_4, while ρ is the congruence modulo {0,2}.
+        assert_eq!(z4.collect_beta_classes().len(),4);
+        let mut rho = z4.beta_relation();
+        rho.rel = vec![(0,0),(0,2),(2,0),(2,2),(1,1),(1,3),(3,1),(3,3)];
+        let quotient = QuotientHyperGroupoid::new_from_equivalence_relation(&z4, &rho);
+        assert_eq!(quotient.n,2);
+        assert_eq!(quotient.hyper_composition[(0,0)],vec![vec![0,2]]);
+        assert_eq!(quotient.hyper_composition[(0,1)],vec![vec![1,3]]);
+        assert_eq!(quotient.hyper_composition[(1,1)],vec![vec![0,2]]);
+        assert!(quotient.to_string().contains("H: [0, 1]"));
+        // {0,1}+{0,1} = {0,1,2} meets both classes of {0,1},{2,3}.
+        rho.rel = vec![(0,0),(0,1),(1,0),(1,1),(2,2),(2,3),(3,2),(3,3)];
+        let quotient = QuotientHyperGroupoid::new_from_equivalence_relation(&z4, &rho);
+        assert_eq!(quotient.n,2);
+        assert_eq!(quotient.hyper_composition[(0,0)],vec![vec![0,1],vec![2,3]]);
+    }
+    #[test]
+    fn homomorphism_search_agrees_with_brute_force(){
+        use itertools::Itertools;
+        use crate::{hg_3::representants_3::TAG_3_REPRESENTANTS, homomorphisms::HomomorphismKind};
+        let cardinality = 3u64;
+        let hypergroupoids:Vec<HyperGroupoid> = TAG_3_REPRESENTANTS.iter().step_by(400)
+            .map(|tag|HyperGroupoid::new_from_tag_u128(tag, &cardinality))
+            .collect();
+        for (source,target) in hypergroupoids.iter().cartesian_product(hypergroupoids.iter()) {
+            for kind in [HomomorphismKind::Inclusion,HomomorphismKind::ReverseInclusion,HomomorphismKind::Good] {
+                let expected:Vec<Vec<u64>> = (0..3).map(|_|0..3u64).multi_cartesian_product()
+                    .filter(|map|source.is_homomorphism(target, map, kind))
+                    .collect();
+                let found:Vec<Vec<u64>> = source.collect_homomorphisms(target, kind).into_iter().map(|f|f.map).collect();
+                assert_eq!(found,expected);
+                assert_eq!(source.find_homomorphism(target, kind).map(|f|f.map),expected.first().cloned());
+            }
+            if source==target {
+                let automorphisms = source.collect_homomorphisms(target, HomomorphismKind::Good).into_iter()
+                    .filter(|f|f.image==7)
+                    .count();
+                assert_eq!(automorphisms,source.automorphism_group().order);
+            }
+        }
+    }
+    #[test]
+    fn products_of_small_hypergroups(){
+        use itertools::Itertools;
+        use crate::{enumeration::collect_hypergroups, homomorphisms::HomomorphismKind};
+        let cardinality = 2u64;
+        let hypergroups:Vec<HyperGroupoid> = collect_hypergroups(&cardinality).iter()
+            .map(|tag|HyperGroupoid::new_from_tag_u128(tag, &cardinality))
+            .collect();
+        for (first,second) in hypergroups.iter().tuple_combinations() {
+            let product = first.direct_product(second);
+            assert!(product.product.is_hypergroup());
+            assert!(product.product.is_homomorphism(second, &product.second_projection(), HomomorphismKind::Good));
+            let (h1,h2) = product.product.direct_product_decomposition().unwrap();
+            assert!(h1.direct_product(&h2).product.is_isomorphic_to(&product.product));
+            assert!(first.lexicographic_product(second).product.is_hypergroup());
+            let sum = first.ordinal_sum(second);
+            assert!(sum.sum.is_associative());
+            assert_eq!(sum.sum.sub_hypergroupoid(&0b1100).unwrap(),*second);
+        }
+        let z3 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%3), &3);
+        assert!(!z3.is_direct_product());
+        // Z_2×Z_6 and Z_12 ≅ Z_3×Z_4 are both direct products, with factors of different orders
+        let cyclic = |n:u64|HyperGroupoid::new_from_function(|a,b|1<<((a+b)%n), &n);
+        let z2_z6 = cyclic(2).direct_product(&cyclic(6)).product;
+        let (h1,h2) = z2_z6.direct_product_decomposition().unwrap();
+        assert_eq!((h1.n,h2.n),(2,6));
+        assert!(h1.direct_product(&h2).product.is_isomorphic_to(&z2_z6));
+        let (h1,h2) = cyclic(12).direct_product_decomposition().unwrap();
+        assert!(h1.is_isomorphic_to(&cyclic(3))&&h2.is_isomorphic_to(&cyclic(4)));
+        assert!(!z2_z6.is_isomorphic_to(&cyclic(12)));
+    }
+    #[test]
+    fn generated_subhypergroups_are_least_closed_subhypergroups(){
+        use crate::{hg_3::representants_3::TAG_3_REPRESENTANTS, utilities::U1024};
+        let cardinality = 3u64;
+        for tag in TAG_3_REPRESENTANTS.iter().step_by(20) {
+            let hg = HyperGroup::new_from_tag_u1024(&U1024::from(*tag), &cardinality);
+            let lattice = hg.subhypergroup_lattice();
+            let closed:Vec<u64> = lattice.members.iter().filter(|member|member.closed).map(|member|member.subset).collect();
+            for mask in 1..8u64 {
+                let generated = hg.generated_subhypergroup(&mask);
+                assert!(closed.contains(&generated));
+                assert!(closed.iter().filter(|k|mask&!*k==0).all(|k|generated&!k==0));
+            }
+            for (i,j) in &lattice.hasse_edges {
+                assert_eq!(lattice.join(&lattice.members[*i].subset, &lattice.members[*j].subset),Some(lattice.members[*j].subset));
+            }
+        }
+    }
+    #[test]
+    fn quotients_by_closed_subhypergroups_are_hypergroups(){
+        use crate::{hg_3::representants_3::TAG_3_REPRESENTANTS, utilities::U1024};
+        let cardinality = 3u64;
+        for tag in TAG_3_REPRESENTANTS.iter() {
+            let hg = HyperGroup::new_from_tag_u1024(&U1024::from(*tag), &cardinality);
+            for k in hg.collect_proper_closed_subhypergroups() {
+                if hg.subhypergroup_is_invertible(&k) {
+                    assert!(hg.left_cosets_partition(&k)&&hg.double_cosets_partition(&k));
+                }
+                if !hg.double_cosets_partition(&k) {
+                    assert!(matches!(hg.get_double_coset_quotient(&k),Err(crate::hypergroups::HyperStructureError::OverlappingDoubleCosets { .. })));
+                    continue;
+                }
+                let (quotient,map) = hg.get_double_coset_quotient(&k).unwrap();
+                assert!(map.iter().all(|c|*c<quotient.cardinality()));
+                assert!(hg.0.is_homomorphism(&quotient.0, &map, crate::homomorphisms::HomomorphismKind::Inclusion));
+            }
+        }
+    }
+    #[test]
+    fn normality_agrees_with_large_backend_and_cosets(){
+        use crate::{bitset::BitSet, large_hs::LargeHyperGroup, hg_3::representants_3::TAG_3_REPRESENTANTS, utilities::U1024};
+        let cardinality = 3u64;
+        for tag in TAG_3_REPRESENTANTS.iter() {
+            let hg = HyperGroup::new_from_tag_u1024(&U1024::from(*tag), &cardinality);
+            let large = LargeHyperGroup::new_from_hypergroup(&hg);
+            for k in hg.collect_proper_subhypergroups() {
+                assert_eq!(hg.subhypergroup_is_normal(&k),large.subhypergroup_is_normal(&BitSet::from_u64(3, k)));
+                if hg.subhypergroup_is_invertible(&k) {
+                    assert!(hg.get_singleton().iter().all(|x|hg.left_coset(&k, x)&x!=0&&hg.right_coset(&k, x)&x!=0));
+                    assert_eq!(hg.subhypergroup_is_invertible_normal(&k),hg.subhypergroup_is_normal(&k));
+                }
+                if hg.subhypergroup_is_normal(&k) {
+                    assert_eq!(hg.collect_left_cosets(&k),hg.collect_double_cosets(&k));
+                }
+            }
+        }
+    }
+    #[test]
+    fn polygroups_of_order_three(){
+        use crate::{enumeration::{filter_polygroups, isomorphism_classes_u1024}, hg_3::representants_3::TAG_HG_3, polygroup::Polygroup, utilities::U1024};
+        let cardinality = 3u64;
+        let tags = filter_polygroups(&TAG_HG_3, &cardinality);
+        let quasicanonical = TAG_HG_3.iter().filter(|tag|HyperGroup::new_from_tag_u128(tag, &cardinality).is_quasicanonical()).count();
+        assert_eq!(quasicanonical,tags.len());
+        let tags:Vec<U1024> = tags.iter().map(|tag|U1024::from(*tag)).collect();
+        assert_eq!(tags.len(),45);
+        assert_eq!(isomorphism_classes_u1024(&tags, &cardinality).len(),10);
+        for tag in tags {
+            let polygroup = Polygroup::new_from_hypergroup(&HyperGroup::new_from_tag_u1024(&tag, &cardinality)).unwrap();
+            for k in polygroup.collect_subpolygroups() {
+                assert!(polygroup.h.subhypergroup_is_closed(&k));
+                let (quotient,_) = polygroup.get_quotient_polygroup(&k).unwrap();
+                assert_eq!(quotient.cardinality() as usize,polygroup.h.collect_double_cosets(&k).len());
+            }
+        }
+    }
+    #[test]
+    fn rings_and_small_hyperfields(){
+        use crate::hyperring::HyperRing;
+        for n in 2..=8u64 {
+            let ring = HyperRing::new_from_functions(|a,b|1<<((a+b)%n), |a,b|1<<((a*b)%n), &n).unwrap();
+            let divisors = (1..=n).filter(|d|n%d==0).count();
+            assert_eq!(ring.collect_hyperideals().len(),divisors);
+            assert_eq!(ring.is_hyperfield(),(2..n).all(|d|n%d!=0));
+            assert_eq!(ring.get_fundamental_ring(),ring);
+        }
+        for hyperfield in [HyperRing::krasner(),HyperRing::sign()] {
+            assert!(hyperfield.is_hyperfield());
+            assert_eq!(hyperfield.collect_hyperideals(),vec![1,(1<<hyperfield.cardinality())-1]);
+            assert_eq!(hyperfield.get_fundamental_ring().cardinality(),1);
+        }
+    }
+    #[test]
+    fn quotient_hyperfields_of_small_fields(){
+        use crate::{finite_fields::FiniteField, hyperring::HyperRing};
+        for q in [2u64,3,4,5,7,8,9,11,13,16] {
+            let field = FiniteField::new(&q).unwrap();
+            assert!(field.to_hyperring().is_hyperfield());
+            for subgroup in field.collect_multiplicative_subgroups() {
+                let quotient = HyperRing::new_quotient_hyperfield(&field, &subgroup).unwrap();
+                assert!(quotient.is_hyperfield());
+                assert_eq!(quotient.cardinality(),(q-1)/subgroup.len() as u64+1);
+                if subgroup.len()==1 {
+                    assert!(quotient.is_isomorphic_to(&field.to_hyperring()));
+                }
+            }
+            let krasner = HyperRing::new_quotient_hyperfield(&field, &(1..q).collect::<Vec<u64>>()).unwrap();
+            assert_eq!(krasner==HyperRing::krasner(),q>2);
+        }
+        for n in [6u64,10,12] {
+            assert!(FiniteField::new(&n).is_err());
+        }
+    }
+    #[test]
+    fn hyperfield_catalogue(){
+        use crate::{enumeration::{classify_hyperfields, HyperfieldEntry}, hyperfields::{HYPERFIELDS_2, HYPERFIELDS_3, HYPERFIELDS_4, HYPERFIELDS_5}, hyperring::HyperRing};
+        let catalogue:Vec<Vec<HyperfieldEntry>> = vec![HYPERFIELDS_2.to_vec(),HYPERFIELDS_3.to_vec(),HYPERFIELDS_4.to_vec(),HYPERFIELDS_5.to_vec()];
+        for (cardinality,expected) in (2..=5u64).zip(catalogue) {
+            let classes:Vec<HyperfieldEntry> = classify_hyperfields(&cardinality, &64).iter()
+                .map(|class|(class.tags.0,class.tags.1,class.quotient_of))
+                .collect();
+            assert_eq!(classes,expected);
+        }
+        for (addition,multiplication,_) in HYPERFIELDS_5 {
+            let addition = HyperGroup::new_from_tag_u128(&addition, &5);
+            let hyperfield = HyperRing::new(&addition, &HyperGroupoid::new_from_tag_u128(&multiplication, &5)).unwrap();
+            assert!(hyperfield.is_hyperfield());
+        }
+    }
+    #[test]
+    fn polynomial_root_multiplicities(){
+        use itertools::Itertools;
+        use crate::{finite_fields::FiniteField, hyperring::HyperRing, polynomials::Polynomial};
+        let sign = HyperRing::sign();
+        let sign_changes = |coefficients:&[u64]|coefficients.iter().filter(|c|**c!=0).tuple_windows().filter(|(a,b)|a!=b).count();
+        for degree in 0..=4usize {
+            for coefficients in (0..=degree).map(|_|0..3u64).multi_cartesian_product().filter(|c|c[degree]!=0) {
+                let p = Polynomial::new(&sign, &coefficients).unwrap();
+                // Descartes' rule of signs, for p(T) and p(-T).
+                assert_eq!(p.multiplicity(&0b010),sign_changes(&coefficients));
+                let reflected = coefficients.iter().enumerate().map(|(i,c)|if i%2==1 {sign.negation[*c as usize]} else {*c}).collect_vec();
+                assert_eq!(p.multiplicity(&0b100),sign_changes(&reflected));
+                assert_eq!(p.multiplicity(&0b001),coefficients.iter().position(|c|*c!=0).unwrap());
+            }
+        }
+        let f5 = FiniteField::new(&5).unwrap().to_hyperring();
+        let linear = |a:u64|Polynomial::linear(&f5, &(1<<a)).unwrap();
+        let square = linear(1).mul(&linear(1));
+        assert_eq!(square.len(),1);
+        let product = square[0].mul(&linear(2));
+        assert_eq!(product.len(),1);
+        let p = &product[0];
+        assert_eq!(p.to_string(),"3 + 1T^2 + 1T^3");
+        assert_eq!((0..5).map(|a|p.multiplicity(&(1<<a))).collect_vec(),vec![0,2,1,0,0]);
+        assert_eq!(p.collect_roots(),vec![0b00010,0b00100]);
+    }
+    #[test]
+    fn hypermodules_over_small_hyperrings(){
+        use itertools::Itertools;
+        use crate::{fundamental_relations::relation_from_classes, hyperfields::{HYPERFIELDS_2, HYPERFIELDS_3, HYPERFIELDS_4}, hypermodule::HyperModule, hyperring::HyperRing};
+        for (cardinality,catalogue) in [(2u64,HYPERFIELDS_2.to_vec()),(3,HYPERFIELDS_3.to_vec()),(4,HYPERFIELDS_4.to_vec())] {
+            for (addition,multiplication,_) in catalogue {
+                let addition = HyperGroup::new_from_tag_u128(&addition, &cardinality);
+                let hyperfield = HyperRing::new(&addition, &HyperGroupoid::new_from_tag_u128(&multiplication, &cardinality)).unwrap();
+                let module = HyperModule::new_regular(&hyperfield);
+                assert!(module.is_hypervector_space());
+                assert_eq!(module.collect_submodules(),vec![1,(1<<cardinality)-1]);
+                assert_eq!(module.collect_fundamental_classes(),hyperfield.collect_fundamental_classes());
+            }
+        }
+        let cardinality = 6u64;
+        let z6 = HyperRing::new_from_functions(|a,b|1<<((a+b)%cardinality), |a,b|1<<((a*b)%cardinality), &cardinality).unwrap();
+        let module = HyperModule::new_regular(&z6);
+        assert_eq!(module.get_fundamental_module(),module);
+        for ideal in module.collect_submodules() {
+            // The cosets of a submodule are the classes of a strongly regular equivalence.
+            let classes = (0..cardinality).map(|x|module.add_by_representation(&(1<<x), &ideal)).unique().sorted_by_key(|class|class.trailing_zeros()).collect::<Vec<u64>>();
+            let quotient = module.get_quotient(&relation_from_classes(&classes, &cardinality)).unwrap();
+            assert_eq!(quotient.cardinality()*ideal.count_ones() as u64,cardinality);
+            let submodule = module.get_submodule(&ideal).unwrap();
+            assert_eq!(submodule.cardinality(),ideal.count_ones() as u64);
+            assert!(submodule.is_submodule(&submodule.zero));
+        }
+    }
 }
-
-
-
@@ -0,0 +1,208 @@
+//!
+//! Polygroups.
+//!
+//! A polygroup is a semihypergroup `P` with a scalar identity `e` and an involution `x -> x⁻¹` such that `e` is in
+//! `xx⁻¹` and in `x⁻¹x`, and the reversibility law holds: if `z` is in `xy`, then `x` is in `zy⁻¹` and `y` is in `x⁻¹z`.
+//! Every polygroup is a hypergroup, in which `x⁻¹` is the unique two-sided inverse of `x`.
+//!
+//! Standard examples are groups, the double coset polygroups `G//K` of a group `G` and a subgroup `K`, and the color
+//! scheme (or chromatic) polygroups of association schemes, see [`Polygroup::new_from_color_scheme`].
+//!
+use std::fmt::{self, Display};
+use itertools::Itertools;
+use nalgebra::DMatrix;
+use crate::{diagnostics::{Side, Violation}, hypergroups::{HyperGroup, HyperStructureError}, product_table::elements_of};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygroup {
+    pub h:HyperGroup,
+    /// The scalar identity, as a singleton.
+    pub identity:u64,
+    /// `inversion[x]` is the inverse of `x`, as an integer in `[0,n-1]`.
+    pub inversion:Vec<u64>,
+}
+impl Polygroup {
+/// Returns the polygroup given by the hypergroup, or an error if it has not a unique scalar identity, if some element has
+/// not a unique inverse or if reversibility fails.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::{HyperGroup, HyperStructureError};
+/// use hyperstruc::polygroup::Polygroup;
+///
+/// let cardinality = 3u64;
+/// let z3 = HyperGroup::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality).unwrap();
+/// let polygroup = Polygroup::new_from_hypergroup(&z3).unwrap();
+/// assert_eq!(polygroup.inversion,vec![0,2,1]);
+/// let total = HyperGroup::new_from_function(|_,_|7, &cardinality).unwrap();
+/// match Polygroup::new_from_hypergroup(&total) {
+///     Err(HyperStructureError::NoUniqueIdentity { identities }) => assert_eq!(identities,0),
+///     _ => panic!("The total hypergroup has no scalar identity!")
+/// }
+/// ```
+pub fn new_from_hypergroup(hg:&HyperGroup)->Result<Self,HyperStructureError>{
+    let identities = hg.0.collect_scalar_identities();
+    if identities.len()!=1 {
+        return Err(HyperStructureError::NoUniqueIdentity { identities: identities.len() });
+    }
+    let identity = identities[0];
+    let inversion = (0..hg.cardinality())
+        .map(|x|{
+            let inverses = hg.get_singleton().into_iter()
+                .filter(|y|hg.mul_by_representation(&(1<<x), y)&hg.mul_by_representation(y, &(1<<x))&identity!=0)
+                .fold(0, |acc,y|acc|y);
+            if inverses.is_power_of_two() {Ok(inverses.trailing_zeros() as u64)} else {Err(HyperStructureError::NoUniqueInverse { x, inverses })}
+        })
+        .collect::<Result<Vec<u64>,_>>()?;
+    let polygroup = Polygroup { h: hg.clone(), identity, inversion };
+    polygroup.check_reversibility().map_err(HyperStructureError::NotReversible)?;
+    Ok(polygroup)
+}
+/// The function is sent to [`HyperGroup::new_from_function`] and the hypergroup to [`Polygroup::new_from_hypergroup`].
+pub fn new_from_function<F>(function:F,cardinality:&u64)->Result<Self,HyperStructureError>
+    where F: Fn(u64,u64) -> u64
+{
+    Polygroup::new_from_hypergroup(&HyperGroup::new_from_function(function, cardinality)?)
+}
+/// Returns the color scheme polygroup of a coloring of the pairs of a set `X`, given as the matrix `colors` whose entry
+/// `(a,b)` is the color of `(a,b)`, in `[0,d-1]`. The elements are the colors and `ij` is the set of colors `k` such that
+/// `(a,c)` has color `k` for some `b` with `(a,b)` of color `i` and `(b,c)` of color `j`. If the coloring is an
+/// association scheme whose color `0` is the diagonal, this is a polygroup with identity `0`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::polygroup::Polygroup;
+/// use hyperstruc::hypergroups::HyperStructureError;
+/// use nalgebra::DMatrix;
+///
+/// // The distances in the pentagon.
+/// let colors = DMatrix::from_fn(5, 5, |a,b|{let d = (a+5-b)%5; d.min(5-d) as u64});
+/// let polygroup = Polygroup::new_from_color_scheme(&colors).unwrap();
+/// assert_eq!(polygroup.mul_by_representation(&0b010, &0b010),0b101);
+/// assert_eq!(polygroup.mul_by_representation(&0b010, &0b100),0b110);
+/// assert_eq!(polygroup.mul_by_representation(&0b100, &0b100),0b011);
+/// // The differences in Z_65 are 65 colors, too many for a polygroup.
+/// let colors = DMatrix::from_fn(65, 65, |a,b|((a+65-b)%65) as u64);
+/// assert!(matches!(Polygroup::new_from_color_scheme(&colors),Err(HyperStructureError::TooManyElements { found: 65 })));
+/// ```
+pub fn new_from_color_scheme(colors:&DMatrix<u64>)->Result<Self,HyperStructureError>{
+    if !colors.is_square() {
+        return Err(HyperStructureError::NonSquareMatrix { rows: colors.nrows(), columns: colors.ncols() });
+    }
+    let n = colors.nrows();
+    let d = colors.iter().max().map_or(0, |c|c+1);
+    if d>64 {
+        return Err(HyperStructureError::TooManyElements { found: d });
+    }
+    let mut table = DMatrix::from_element(d as usize, d as usize, 0u64);
+    for ((a,b),c) in (0..n).cartesian_product(0..n).cartesian_product(0..n) {
+        table[(colors[(a,b)] as usize,colors[(b,c)] as usize)] |= 1<<colors[(a,c)];
+    }
+    Polygroup::new_from_hypergroup(&HyperGroup::try_new_from_matrix(&table)?)
+}
+/// Returns the double coset polygroup `G//K` of a group, or more generally of a polygroup, and a subpolygroup `K`,
+/// together with the map `x -> KxK`. See [`Polygroup::get_quotient_polygroup`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+/// use hyperstruc::polygroup::Polygroup;
+///
+/// // The symmetric group S3, with permutations in lexicographic order.
+/// let perms = [[0,1,2],[0,2,1],[1,0,2],[1,2,0],[2,0,1],[2,1,0]];
+/// let index = |p:[usize;3]|perms.iter().position(|q|*q==p).unwrap();
+/// let function = |a:u64,b:u64|{
+///     let (p,q) = (perms[a as usize],perms[b as usize]);
+///     1<<index([p[q[0]],p[q[1]],p[q[2]]])
+/// };
+/// let s3 = HyperGroup::new_from_function(function, &6).unwrap();
+/// let (polygroup,map) = Polygroup::new_from_double_cosets(&s3, &0b000011).unwrap();
+/// // S3//S2 has the identity and an element t with tt = {e,t}.
+/// assert_eq!(polygroup.cardinality(),2);
+/// assert_eq!(polygroup.mul_by_representation(&0b10, &0b10),0b11);
+/// assert_eq!(map,vec![0,0,1,1,1,1]);
+/// ```
+pub fn new_from_double_cosets(group:&HyperGroup,k:&u64)->Result<(Self,Vec<u64>),HyperStructureError>{
+    Polygroup::new_from_hypergroup(group)?.get_quotient_polygroup(k)
+}
+pub fn cardinality(&self)->u64{
+    self.h.cardinality()
+}
+pub fn mul_by_representation(&self,subset_a:&u64,subset_b:&u64)->u64{
+    self.h.mul_by_representation(subset_a, subset_b)
+}
+/// Returns the inverse of the element `x`, given as a singleton, as a singleton.
+pub fn inverse(&self,x:&u64)->u64{
+    assert!(x.is_power_of_two()&&x.trailing_zeros()<self.cardinality() as u32,"{} is not an element in H",x);
+    1<<self.inversion[x.trailing_zeros() as usize]
+}
+/// Returns `K⁻¹ = {x⁻¹ : x in K}`.
+pub fn inverse_of_subset(&self,k:&u64)->u64{
+    elements_of(*k).fold(0, |acc,x|acc|1<<self.inversion[x])
+}
+/// Returns the first triple `(x,y,z)` such that `z` is in `xy` and reversibility fails, if any.
+pub fn check_reversibility(&self)->Result<(),Violation>{
+    let n = self.cardinality();
+    (0..n).cartesian_product(0..n)
+        .flat_map(|(x,y)|elements_of(self.h.0.hyper_composition[(x as usize,y as usize)]).map(move |z|(x,y,z as u64)))
+        .find_map(|(x,y,z)|{
+            if self.mul_by_representation(&(1<<z), &self.inverse(&(1<<y)))&(1<<x)==0 {
+                Some(Violation::Reversibility { x, y, z, side: Side::Left })
+            } else if self.mul_by_representation(&self.inverse(&(1<<x)), &(1<<z))&(1<<y)==0 {
+                Some(Violation::Reversibility { x, y, z, side: Side::Right })
+            } else {None}
+        })
+        .map_or(Ok(()), Err)
+}
+/// Return true if `K` contains the identity, `K⁻¹ ⊆ K` and `KK ⊆ K`.
+pub fn is_subpolygroup(&self,k:&u64)->bool{
+    *k>>self.cardinality()==0
+    &&
+    k&self.identity!=0
+    &&
+    self.inverse_of_subset(k)&!k==0
+    &&
+    self.mul_by_representation(k, k)&!k==0
+}
+/// Returns all the subpolygroups, `{e}` and P included, sorted by representation.
+///
+/// # Example
+/// ```
+/// use hyperstruc::polygroup::Polygroup;
+///
+/// let cardinality = 4u64;
+/// let z4 = Polygroup::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality).unwrap();
+/// assert_eq!(z4.collect_subpolygroups(),vec![0b0001,0b0101,0b1111]);
+/// ```
+pub fn collect_subpolygroups(&self)->Vec<u64>{
+    (1..1u64<<self.cardinality()).filter(|k|self.is_subpolygroup(k)).collect()
+}
+/// Returns the subpolygroup `K` as a polygroup, with elements relabelled as in [`crate::hs::HyperGroupoid::sub_hypergroupoid`].
+pub fn get_subpolygroup(&self,k:&u64)->Result<Self,HyperStructureError>{
+    if !self.is_subpolygroup(k) {
+        return Err(HyperStructureError::NotSubPolygroup { subset: *k });
+    }
+    let hs = self.h.0.sub_hypergroupoid(k).unwrap();
+    Polygroup::new_from_hypergroup(&HyperGroup::try_new_from_hypergroupoid(&hs)?)
+}
+/// Returns the quotient polygroup `P//K` of the subpolygroup `K`, whose elements are the double cosets `KxK`, together
+/// with the map `x -> KxK`. The identity of the quotient is `K` and the inverse of `KxK` is `Kx⁻¹K`.
+pub fn get_quotient_polygroup(&self,k:&u64)->Result<(Self,Vec<u64>),HyperStructureError>{
+    if !self.is_subpolygroup(k) {
+        return Err(HyperStructureError::NotSubPolygroup { subset: *k });
+    }
+    let (quotient,map) = self.h.get_double_coset_quotient(k)?;
+    Ok((Polygroup::new_from_hypergroup(&quotient)?,map))
+}
+}
+impl Display for Polygroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\nIdentity: {}\nInversion: {:?}", self.h, self.identity.trailing_zeros(), self.inversion)
+    }
+}
+impl HyperGroup {
+/// Return true if the hypergroup is a polygroup, see [`Polygroup::new_from_hypergroup`].
+pub fn is_polygroup(&self)->bool{
+    Polygroup::new_from_hypergroup(self).is_ok()
+}
+}
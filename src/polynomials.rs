@@ -0,0 +1,244 @@
+//!
+//! Polynomials over finite hyperfields.
+//!
+//! A polynomial over a hyperfield F is a finite sequence of coefficients in F. The sum and the product of two
+//! polynomials are sets of polynomials: `p+q` is the set of `c` with `c_k` in `p_k+q_k`, and `pq` is the set of `c` with
+//! `c_k` in the hypersum of the products `p_i q_j` with `i+j = k`. The evaluation `p(a)` is the subset `Σ p_i a^i`, and
+//! `a` is a root of `p` if `0` is in `p(a)`.
+//!
+//! Following Baker and Lorscheid, the multiplicity of `a` in a nonzero polynomial `p` is `0` if `a` is not a root, and
+//! otherwise `1 + max mult_a(q)`, the maximum being taken over all the polynomials `q` such that `p` is in `(T-a)q`.
+//! Over the sign hyperfield, the multiplicity of `1` is the number of sign changes in the coefficients of `p`, which is
+//! Descartes' rule of signs.
+//!
+//! The hypergroup given by [`crate::generating_functions::tropical_hypergroup`] of order 2 is the addition of the
+//! Krasner hyperfield. For larger orders it is not the addition of a hyperfield, since `x -> ux` is an automorphism of
+//! the addition for every nonzero `u`, while `a+a = {0,...,a}` has a different cardinality for every `a`.
+//!
+use std::fmt::{self, Display};
+use itertools::Itertools;
+use crate::{hyperring::HyperRing, hypergroups::HyperStructureError, product_table::elements_of};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial {
+    pub hyperfield:HyperRing,
+    /// `coefficients[i]` is the coefficient of `T^i`, as an integer in `[0,n-1]`. The last coefficient is not zero.
+    pub coefficients:Vec<u64>,
+}
+impl Polynomial {
+/// Returns the polynomial with the given coefficients, from the constant term to the leading one. Trailing zeros are
+/// removed. It fails if the hyperring is not a hyperfield or if some coefficient is not one of its elements.
+///
+/// # Example
+/// ```
+/// use hyperstruc::generating_functions::tropical_hypergroup;
+/// use hyperstruc::hyperring::HyperRing;
+/// use hyperstruc::polynomials::Polynomial;
+///
+/// let krasner = HyperRing::new_from_functions(tropical_hypergroup(), |a,b|1<<(a&b), &2).unwrap();
+/// assert_eq!(krasner,HyperRing::krasner());
+/// let p = Polynomial::new(&krasner, &[1,1,0,0]).unwrap();
+/// assert_eq!(p.coefficients,vec![1,1]);
+/// assert_eq!(p.degree(),Some(1));
+/// assert!(Polynomial::new(&krasner, &[2]).is_err());
+/// ```
+pub fn new(hyperfield:&HyperRing,coefficients:&[u64])->Result<Self,HyperStructureError>{
+    if !hyperfield.is_hyperfield() {
+        return Err(HyperStructureError::NotHyperfield);
+    }
+    if let Some((degree,coefficient)) = coefficients.iter().enumerate().find(|(_,c)|**c>=hyperfield.cardinality()) {
+        return Err(HyperStructureError::CoefficientOutOfRange { degree, coefficient: *coefficient });
+    }
+    Ok(Polynomial::new_unchecked(hyperfield, coefficients.to_vec()))
+}
+fn new_unchecked(hyperfield:&HyperRing,mut coefficients:Vec<u64>)->Self{
+    let zero = hyperfield.zero.trailing_zeros() as u64;
+    while coefficients.last()==Some(&zero) {
+        coefficients.pop();
+    }
+    Polynomial { hyperfield: hyperfield.clone(), coefficients }
+}
+/// Returns the zero polynomial, which has no coefficients.
+pub fn zero(hyperfield:&HyperRing)->Result<Self,HyperStructureError>{
+    Polynomial::new(hyperfield, &[])
+}
+/// Returns the monic polynomial `T-a`, where `a` is given as a singleton.
+pub fn linear(hyperfield:&HyperRing,a:&u64)->Result<Self,HyperStructureError>{
+    let one = hyperfield.one().ok_or(HyperStructureError::NotHyperfield)?;
+    let minus_a = hyperfield.negation_of_subset(a);
+    Polynomial::new(hyperfield, &[minus_a.trailing_zeros() as u64,one.trailing_zeros() as u64])
+}
+pub fn is_zero(&self)->bool{
+    self.coefficients.is_empty()
+}
+/// Returns the degree, or `None` for the zero polynomial.
+pub fn degree(&self)->Option<usize>{
+    self.coefficients.len().checked_sub(1)
+}
+/// All the polynomials whose coefficient of degree `k` is in `sets[k]`.
+fn collect_polynomials_with_coefficients_in(&self,sets:&[u64])->Vec<Self>{
+    if sets.is_empty() {
+        return vec![Polynomial::new_unchecked(&self.hyperfield, Vec::new())];
+    }
+    sets.iter()
+        .map(|set|elements_of(*set).map(|c|c as u64).collect_vec())
+        .multi_cartesian_product()
+        .map(|coefficients|Polynomial::new_unchecked(&self.hyperfield, coefficients))
+        .collect()
+}
+/// Returns the coefficient of degree `k` as a singleton, zero included.
+fn coefficient(&self,k:usize)->u64{
+    self.coefficients.get(k).map_or(self.hyperfield.zero, |c|1<<c)
+}
+/// Returns the set `p+q` of polynomials `c` with `c_k` in `p_k+q_k`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hyperring::HyperRing;
+/// use hyperstruc::polynomials::Polynomial;
+///
+/// let krasner = HyperRing::krasner();
+/// let p = Polynomial::new(&krasner, &[1,1]).unwrap();
+/// // (1+T)+(1+T) = {0, 1, T, 1+T}.
+/// assert_eq!(p.add(&p).len(),4);
+/// ```
+pub fn add(&self,other:&Polynomial)->Vec<Polynomial>{
+    assert_eq!(self.hyperfield,other.hyperfield,"The polynomials have different hyperfields");
+    let sets = (0..self.coefficients.len().max(other.coefficients.len()))
+        .map(|k|self.hyperfield.add_by_representation(&self.coefficient(k), &other.coefficient(k)))
+        .collect_vec();
+    self.collect_polynomials_with_coefficients_in(&sets)
+}
+/// Returns the set `pq` of polynomials `c` with `c_k` in `Σ p_i q_j`, the sum being over `i+j = k`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hyperring::HyperRing;
+/// use hyperstruc::polynomials::Polynomial;
+///
+/// let sign = HyperRing::sign();
+/// let t_minus_one = Polynomial::linear(&sign, &0b010).unwrap();
+/// let t_plus_one = Polynomial::linear(&sign, &0b100).unwrap();
+/// // Since 1-1 = {0,1,-1}, (T-1)(T+1) = {T^2-1, T^2+T-1, T^2-T-1}, where -1 is represented by 2.
+/// let products = t_minus_one.mul(&t_plus_one);
+/// assert!(products.contains(&Polynomial::new(&sign, &[2,0,1]).unwrap()));
+/// assert!(!products.contains(&Polynomial::new(&sign, &[1,0,1]).unwrap()));
+/// // (T-1)(T-1) = {T^2-T+1}.
+/// assert_eq!(t_minus_one.mul(&t_minus_one),vec![Polynomial::new(&sign, &[1,2,1]).unwrap()]);
+/// assert_eq!(products.len(),3);
+/// ```
+pub fn mul(&self,other:&Polynomial)->Vec<Polynomial>{
+    assert_eq!(self.hyperfield,other.hyperfield,"The polynomials have different hyperfields");
+    if self.is_zero()||other.is_zero() {
+        return self.collect_polynomials_with_coefficients_in(&[]);
+    }
+    let sets = (0..self.coefficients.len()+other.coefficients.len()-1)
+        .map(|k|{
+            (0..=k).filter(|i|*i<self.coefficients.len()&&k-i<other.coefficients.len())
+                .map(|i|self.hyperfield.mul_by_representation(&self.coefficient(i), &other.coefficient(k-i)))
+                .fold(self.hyperfield.zero, |acc,term|self.hyperfield.add_by_representation(&acc, &term))
+        })
+        .collect_vec();
+    self.collect_polynomials_with_coefficients_in(&sets)
+}
+/// Returns the subset `p(a) = Σ p_i a^i`, where `a` is given as a singleton.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hyperring::HyperRing;
+/// use hyperstruc::polynomials::Polynomial;
+///
+/// let sign = HyperRing::sign();
+/// // T^2+T+1 is positive at 1 and takes every sign at -1.
+/// let p = Polynomial::new(&sign, &[1,1,1]).unwrap();
+/// assert_eq!(p.evaluate(&0b010),0b010);
+/// assert_eq!(p.evaluate(&0b100),0b111);
+/// assert!(!p.is_root(&0b010)&&p.is_root(&0b100));
+/// ```
+pub fn evaluate(&self,a:&u64)->u64{
+    let one = self.hyperfield.one().unwrap();
+    self.coefficients.iter()
+        .fold((self.hyperfield.zero,one), |(acc,power),c|{
+            let term = self.hyperfield.mul_by_representation(&(1<<c), &power);
+            (self.hyperfield.add_by_representation(&acc, &term),self.hyperfield.mul_by_representation(&power, a))
+        })
+        .0
+}
+/// Return true if `0` is in `p(a)`.
+pub fn is_root(&self,a:&u64)->bool{
+    self.evaluate(a)&self.hyperfield.zero!=0
+}
+/// Returns the roots, as singletons sorted by representation. Every element is a root of the zero polynomial.
+pub fn collect_roots(&self)->Vec<u64>{
+    self.hyperfield.multiplication.get_singleton().into_iter().filter(|a|self.is_root(a)).collect()
+}
+/// Returns all the polynomials `q` such that `p` is in `(T-a)q`, sorted by coefficients. They have degree `deg p - 1`,
+/// so there are none if `p` is constant.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hyperring::HyperRing;
+/// use hyperstruc::polynomials::Polynomial;
+///
+/// let krasner = HyperRing::krasner();
+/// // Since -1 = 1, T^2+T+1 is in (T-1)q only for q = T+1.
+/// let p = Polynomial::new(&krasner, &[1,1,1]).unwrap();
+/// let cofactors = p.collect_cofactors(&0b10);
+/// assert_eq!(cofactors,vec![Polynomial::new(&krasner, &[1,1]).unwrap()]);
+/// ```
+pub fn collect_cofactors(&self,a:&u64)->Vec<Polynomial>{
+    let Some(degree) = self.degree().filter(|d|*d>0) else {return Vec::new();};
+    let minus_a = self.hyperfield.negation_of_subset(a);
+    let elements = self.hyperfield.multiplication.get_singleton();
+    // The coefficients of q are chosen from the leading one down: p_k must be in -a q_k + q_{k-1}.
+    let mut partial:Vec<Vec<u64>> = vec![vec![1<<self.coefficients[degree]]];
+    for k in (1..degree).rev() {
+        partial = partial.into_iter()
+            .flat_map(|q|{
+                let minus_a_q_k = self.hyperfield.mul_by_representation(&minus_a, q.last().unwrap());
+                elements.iter()
+                    .filter(move |q_k_minus_1|self.hyperfield.add_by_representation(&minus_a_q_k, q_k_minus_1)&self.coefficient(k)!=0)
+                    .map(move |q_k_minus_1|q.iter().copied().chain(std::iter::once(*q_k_minus_1)).collect_vec())
+            })
+            .collect();
+    }
+    partial.into_iter()
+        .filter(|q|self.hyperfield.mul_by_representation(&minus_a, q.last().unwrap())==self.coefficient(0))
+        .map(|q|Polynomial::new_unchecked(&self.hyperfield, q.iter().rev().map(|c|c.trailing_zeros() as u64).collect()))
+        .sorted_by(|p,q|p.coefficients.cmp(&q.coefficients))
+        .collect()
+}
+/// Returns the multiplicity of `a`, given as a singleton, as a root of the nonzero polynomial `p`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hyperring::HyperRing;
+/// use hyperstruc::polynomials::Polynomial;
+///
+/// let sign = HyperRing::sign();
+/// // T^3-T^2-T+1 has two sign changes, and one sign change after replacing T with -T.
+/// let p = Polynomial::new(&sign, &[1,2,2,1]).unwrap();
+/// assert_eq!(p.multiplicity(&0b010),2);
+/// assert_eq!(p.multiplicity(&0b100),1);
+/// assert_eq!(p.multiplicity(&0b001),0);
+/// ```
+pub fn multiplicity(&self,a:&u64)->usize{
+    assert!(!self.is_zero(),"The zero polynomial has no root multiplicities");
+    if !self.is_root(a) {return 0;}
+    1+self.collect_cofactors(a).iter().map(|q|q.multiplicity(a)).max().unwrap_or(0)
+}
+}
+impl Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let zero = self.hyperfield.zero.trailing_zeros() as u64;
+        let terms = self.coefficients.iter().enumerate()
+            .filter(|(_,c)|**c!=zero)
+            .map(|(i,c)|match i {
+                0 => format!("{c}"),
+                1 => format!("{c}T"),
+                _ => format!("{c}T^{i}"),
+            })
+            .collect_vec();
+        if terms.is_empty() {write!(f, "{zero}")} else {write!(f, "{}", terms.join(" + "))}
+    }
+}
@@ -0,0 +1,179 @@
+//!
+//! Precomputed products of subsets.
+//!
+//! A [`ProductTable`] stores the products `ab` of all pairs of elements of a hypergroupoid in a flat vector, and computes
+//! the product of two subsets by OR-ing those entries while iterating over the bits of the subsets. If `n` is at most
+//! [`ProductTable::SUBSET_TABLE_MAX_ORDER`] it also caches the whole `2^n × 2^n` table of products of `P*(H)`, so that
+//! every product is a single lookup.
+//!
+//! Build it once with [`HyperGroupoid::product_table`] or [`HyperGroup::product_table`] and reuse it when many
+//! products are needed. The predicates of [`HyperGroupoid`] and [`HyperGroup`] that loop over products already do so.
+//!
+use std::collections::HashSet;
+use crate::{hs::HyperGroupoid, hypergroups::HyperGroup};
+
+/// Iterates over the elements of a subset, i.e., over the positions of its bits equal to 1.
+pub(crate) fn elements_of(subset:u64)->impl Iterator<Item = usize>{
+    let mut rest = subset;
+    std::iter::from_fn(move ||{
+        if rest==0 {return None;}
+        let x = rest.trailing_zeros() as usize;
+        rest&=rest-1;
+        Some(x)
+    })
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductTable{
+    pub n:u64,
+    /// Row-major table of the products `ab` of elements.
+    singleton_products:Vec<u64>,
+    /// Products `AB` of subsets, at index `A*2^n+B`, if `n<=SUBSET_TABLE_MAX_ORDER`.
+    subset_products:Option<Vec<u64>>,
+}
+impl ProductTable {
+    /// Largest order for which the table of products of all subsets is cached. It has `4^n` entries.
+    pub const SUBSET_TABLE_MAX_ORDER:u64 = 8;
+/// Precomputes the products of hs.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use nalgebra::DMatrix;
+///
+/// let matrix=DMatrix::from_row_slice(3usize,3usize,&[1,2,7,2,7,7,7,7,5]);
+/// let hyperstructure=HyperGroupoid::new_from_matrix(&matrix);
+/// let table = hyperstructure.product_table();
+/// assert!(table.has_subset_table());
+/// for (a,b) in [(1u64,1u64),(3,4),(5,6),(7,7)] {
+///     assert_eq!(table.mul_by_representation(&a, &b),hyperstructure.mul_by_representation(&a, &b));
+/// }
+/// assert_eq!(table.left_division(&2, &4),3);
+/// assert_eq!(table.right_division(&1, &2),6);
+/// assert!(table.is_associative());
+/// ```
+pub fn new(hs:&HyperGroupoid)->Self{
+    let mut table = ProductTable::new_without_subset_table(hs);
+    let n = hs.n as usize;
+    let singleton_products = &table.singleton_products;
+    table.subset_products = (hs.n<=ProductTable::SUBSET_TABLE_MAX_ORDER).then(||{
+        let size = 1usize<<n;
+        let mut products = vec![0u64;size*size];
+        for a in 1..size {
+            let x = a.trailing_zeros() as usize;
+            let rest = a&(a-1);
+            for b in 1..size {
+                products[a*size+b] = if rest==0 {
+                    //a is the singleton {x}: {x}B = {x}(B-{y}) ∪ xy, where y is the least element of B
+                    products[a*size+(b&(b-1))]|singleton_products[x*n+b.trailing_zeros() as usize]
+                } else {
+                    products[rest*size+b]|products[(1<<x)*size+b]
+                };
+            }
+        }
+        products
+    });
+    table
+}
+/// Precomputes only the products of elements. Building the table of subsets costs `4^n` operations, which is more than
+/// a single pass over all triples of elements needs.
+pub fn new_without_subset_table(hs:&HyperGroupoid)->Self{
+    let n = hs.n as usize;
+    let singleton_products:Vec<u64> = (0..n*n).map(|i|hs.hyper_composition[(i/n,i%n)]).collect();
+    ProductTable { n:hs.n, singleton_products, subset_products:None }
+}
+pub fn has_subset_table(&self)->bool{
+    self.subset_products.is_some()
+}
+/// The product `ab` of the elements `a` and `b`, given as integers in `0..n`.
+pub fn entry(&self,a:usize,b:usize)->u64{
+    self.singleton_products[a*self.n as usize+b]
+}
+pub fn mul_by_representation(&self,subset_a:&u64,subset_b:&u64)->u64{
+    match &self.subset_products {
+        Some(products) => products[((*subset_a as usize)<<self.n)+*subset_b as usize],
+        None => {
+            let mut product = 0u64;
+            for a in elements_of(*subset_a) {
+                for b in elements_of(*subset_b) {
+                    product|=self.entry(a, b);
+                }
+            }
+            product
+        }
+    }
+}
+/// Compute `b\a={x in H : a meets bx}`, as [`HyperGroupoid::left_division`].
+pub fn left_division(&self,a:&u64,b:&u64)->u64{
+    (0..self.n)
+        .filter(|x|a&self.mul_by_representation(b, &(1<<x))!=0)
+        .fold(0, |acc,x|acc|1<<x)
+}
+/// Compute `a/b={x in H : a meets xb}`, as [`HyperGroupoid::right_division`].
+pub fn right_division(&self,a:&u64,b:&u64)->u64{
+    (0..self.n)
+        .filter(|x|a&self.mul_by_representation(&(1<<x), b)!=0)
+        .fold(0, |acc,x|acc|1<<x)
+}
+fn associativity_sides(&self,a:usize,b:usize,c:usize)->(u64,u64){
+    (self.mul_by_representation(&self.entry(a, b), &(1<<c)),self.mul_by_representation(&(1<<a), &self.entry(b, c)))
+}
+pub fn is_associative(&self)->bool{
+    let n = self.n as usize;
+    (0..n).all(|a|(0..n).all(|b|(0..n).all(|c|{
+        let (left,right) = self.associativity_sides(a, b, c);
+        left==right
+    })))
+}
+pub fn is_weak_associative(&self)->bool{
+    let n = self.n as usize;
+    (0..n).all(|a|(0..n).all(|b|(0..n).all(|c|{
+        let (left,right) = self.associativity_sides(a, b, c);
+        left&right!=0
+    })))
+}
+/// Return true if `b\a meets c/d implies ad meets bc`, for all `a,b,c,d` in H. See [`HyperGroup::is_transposition`].
+pub fn is_transposition(&self)->bool{
+    let n = self.n as usize;
+    let left_divisions:Vec<u64> = (0..n*n).map(|i|self.left_division(&(1<<(i/n)), &(1<<(i%n)))).collect();
+    let right_divisions:Vec<u64> = (0..n*n).map(|i|self.right_division(&(1<<(i/n)), &(1<<(i%n)))).collect();
+    (0..n*n).all(|ab|(0..n*n).all(|cd|{
+        let (a,b,c,d) = (ab/n,ab%n,cd/n,cd%n);
+        left_divisions[ab]&right_divisions[cd]==0||self.entry(a, d)&self.entry(b, c)!=0
+    }))
+}
+/// The set `P(H)` of all finite hyperproducts, sorted. See [`HyperGroupoid::collect_ph`].
+pub fn collect_ph(&self)->Vec<u64>{
+    self.collect_hyperproducts_of(&(0..self.n).map(|x|1<<x).collect::<Vec<u64>>())
+}
+/// The set of all finite hyperproducts of the given subsets, including the subsets themselves, sorted.
+pub fn collect_hyperproducts_of(&self,subsets:&[u64])->Vec<u64>{
+    let mut seen:HashSet<u64> = HashSet::new();
+    let mut ph:Vec<u64> = subsets.iter().copied().filter(|q|seen.insert(*q)).collect();
+    let mut next = 0;
+    while next<ph.len() {
+        let q = ph[next];
+        for i in 0..=next {
+            let p = ph[i];
+            for product in [self.mul_by_representation(&p, &q),self.mul_by_representation(&q, &p)] {
+                if seen.insert(product) {
+                    ph.push(product);
+                }
+            }
+        }
+        next+=1;
+    }
+    ph.sort();
+    ph
+}
+}
+impl HyperGroupoid {
+/// Precomputes the products of the hypergroupoid in a [`ProductTable`].
+pub fn product_table(&self)->ProductTable{
+    ProductTable::new(self)
+}
+}
+impl HyperGroup {
+pub fn product_table(&self)->ProductTable{
+    self.0.product_table()
+}
+}
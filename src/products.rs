@@ -0,0 +1,280 @@
+//!
+//! Products and sums of hypergroupoids.
+//!
+//! Given hypergroupoids `(H,∘)` of order `n` and `(K,*)` of order `m`, the elements of `H×K` are indexed as
+//! `(a,b) -> a*m+b`, see [`ProductHyperGroupoid`]:
+//! - the direct product is `(a,b)(c,d) = (a∘c)×(b*d)`;
+//! - the lexicographic product is `(a,b)(a,d) = {a}×(b*d)` if `a∘a={a}`, and `(a,b)(c,d) = (a∘c)×K` otherwise. The first
+//!   coordinate decides, and the second one matters only inside the fibres of the idempotent elements. If H and K are
+//!   hypergroups, so is the lexicographic product.
+//!
+//! The ordinal sum lives on the disjoint union of H and K, where the elements of H keep their index and the element `y`
+//! of K becomes `n+y`, see [`SumHyperGroupoid`]. Products inside H and inside K are unchanged, while `xy=yx={x}` for `x`
+//! in H and `y` in K: the first summand absorbs the second one. The sum of two semihypergroups is a semihypergroup, but it
+//! is never reproductive, since `x(H∪K)` is contained in H for `x` in H.
+//!
+//! The order of the result must be at most 64, since subsets are represented by `u64`.
+//!
+use itertools::Itertools;
+use crate::{fundamental_relations::relation_from_classes, hs::HyperGroupoid, hypergroups::HyperGroup, product_table::elements_of};
+
+/// A hypergroupoid on `H×K`, where `(a,b)` is the element `a*m+b` and `m` is the order of K.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductHyperGroupoid {
+    pub product:HyperGroupoid,
+    pub first_order:u64,
+    pub second_order:u64,
+}
+impl ProductHyperGroupoid {
+    /// Builds the table from `function(a,b,c,d)`, the product of `(a,b)` and `(c,d)`.
+    fn new<F>(first:&HyperGroupoid,second:&HyperGroupoid,function:F)->Self
+    where
+        F: Fn(u64,u64,u64,u64)->u64
+    {
+        let (n,m) = (first.n,second.n);
+        assert!(n*m<=64,"The product has order {}, while at most 64 is supported",n*m);
+        let product = HyperGroupoid::new_from_function(|x,y|function(x/m,x%m,y/m,y%m), &(n*m));
+        ProductHyperGroupoid { product, first_order: n, second_order: m }
+    }
+    /// The index of the pair `(a,b)`.
+    pub fn index(&self,a:u64,b:u64)->u64{
+        assert!(a<self.first_order&&b<self.second_order,"({},{}) is not a pair of elements of H×K",a,b);
+        a*self.second_order+b
+    }
+    /// The pair `(a,b)` of index `z`.
+    pub fn projections(&self,z:u64)->(u64,u64){
+        (z/self.second_order,z%self.second_order)
+    }
+    /// The projection onto H, as a map in the sense of [`crate::homomorphisms`].
+    pub fn first_projection(&self)->Vec<u64>{
+        (0..self.product.n).map(|z|self.projections(z).0).collect()
+    }
+    /// The projection onto K, as a map in the sense of [`crate::homomorphisms`].
+    pub fn second_projection(&self)->Vec<u64>{
+        (0..self.product.n).map(|z|self.projections(z).1).collect()
+    }
+    /// The subset `A×B` of H×K.
+    pub fn product_of_subsets(&self,subset_a:u64,subset_b:u64)->u64{
+        cartesian_product_of_subsets(subset_a, subset_b, self.second_order)
+    }
+}
+/// The subset `A×B`, where `(a,b)` is `a*m+b`.
+fn cartesian_product_of_subsets(subset_a:u64,subset_b:u64,m:u64)->u64{
+    elements_of(subset_a).cartesian_product(elements_of(subset_b).collect_vec())
+        .fold(0, |acc,(a,b)|acc|1<<(a as u64*m+b as u64))
+}
+/// Summand containing an element of an ordinal sum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Summand {
+    First(u64),
+    Second(u64),
+}
+/// A hypergroupoid on the disjoint union of H and K, where `y` in K is the element `n+y` and `n` is the order of H.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SumHyperGroupoid {
+    pub sum:HyperGroupoid,
+    pub first_order:u64,
+    pub second_order:u64,
+}
+impl SumHyperGroupoid {
+    /// The summand and the element of the summand corresponding to `z`.
+    pub fn split(&self,z:u64)->Summand{
+        assert!(z<self.sum.n,"{} is not an element of the sum",z);
+        if z<self.first_order {Summand::First(z)} else {Summand::Second(z-self.first_order)}
+    }
+    /// The embedding of H, as a map in the sense of [`crate::homomorphisms`].
+    pub fn first_embedding(&self)->Vec<u64>{
+        (0..self.first_order).collect()
+    }
+    /// The embedding of K, as a map in the sense of [`crate::homomorphisms`].
+    pub fn second_embedding(&self)->Vec<u64>{
+        (0..self.second_order).map(|y|self.first_order+y).collect()
+    }
+}
+impl HyperGroupoid {
+/// Returns the direct product `H×K`.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::homomorphisms::HomomorphismKind;
+///
+/// let z2 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%2), &2);
+/// let z3 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%3), &3);
+/// let z6 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%6), &6);
+/// let product = z2.direct_product(&z3);
+/// assert_eq!(product.projections(product.index(1,2)),(1,2));
+/// assert!(product.product.is_hypergroup());
+/// assert!(product.product.is_homomorphism(&z2, &product.first_projection(), HomomorphismKind::Good));
+/// assert!(product.product.is_isomorphic_to(&z6));
+/// ```
+pub fn direct_product(&self,other:&HyperGroupoid)->ProductHyperGroupoid{
+    let function = |a:u64,b:u64,c:u64,d:u64|cartesian_product_of_subsets(
+        self.hyper_composition[(a as usize,c as usize)], other.hyper_composition[(b as usize,d as usize)], other.n);
+    ProductHyperGroupoid::new(self, other, function)
+}
+/// Returns the lexicographic product of H and K.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// let z2 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%2), &2);
+/// let product = z2.lexicographic_product(&z2);
+/// assert!(product.product.is_hypergroup());
+/// // 0 is idempotent in Z_2, so the second coordinate is kept
+/// assert_eq!(product.product.hyper_composition[(product.index(0,1) as usize,product.index(0,1) as usize)],1<<product.index(0,0));
+/// // 1+1 = 0, so the second coordinate is lost
+/// assert_eq!(product.product.hyper_composition[(product.index(1,0) as usize,product.index(1,0) as usize)],0b0011);
+/// ```
+pub fn lexicographic_product(&self,other:&HyperGroupoid)->ProductHyperGroupoid{
+    let m = other.n;
+    let whole_k = (1<<m)-1;
+    let function = |a:u64,b:u64,c:u64,d:u64|{
+        let ac = self.hyper_composition[(a as usize,c as usize)];
+        if a!=c {
+            return cartesian_product_of_subsets(ac, whole_k, m);
+        }
+        if ac==1<<a {
+            return cartesian_product_of_subsets(ac, other.hyper_composition[(b as usize,d as usize)], m);
+        }
+        cartesian_product_of_subsets(ac, whole_k, m)
+    };
+    ProductHyperGroupoid::new(self, other, function)
+}
+/// Returns the ordinal sum of H and K, where the elements of H absorb those of K.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+/// use hyperstruc::products::Summand;
+///
+/// let z2 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%2), &2);
+/// let total = HyperGroupoid::new_from_function(|_,_|7, &3);
+/// let sum = z2.ordinal_sum(&total);
+/// assert!(sum.sum.is_associative());
+/// assert!(!sum.sum.is_reproductive());
+/// assert_eq!(sum.split(3),Summand::Second(1));
+/// assert_eq!(sum.sum.hyper_composition[(0,3)],1);
+/// assert_eq!(sum.sum.sub_hypergroupoid(&0b11),Some(z2));
+/// ```
+pub fn ordinal_sum(&self,other:&HyperGroupoid)->SumHyperGroupoid{
+    let (n,m) = (self.n,other.n);
+    assert!(n+m<=64,"The sum has order {}, while at most 64 is supported",n+m);
+    let function = |x:u64,y:u64|match (x<n,y<n) {
+        (true,true) => self.hyper_composition[(x as usize,y as usize)],
+        (false,false) => other.hyper_composition[((x-n) as usize,(y-n) as usize)]<<n,
+        (true,false) => 1<<x,
+        (false,true) => 1<<y,
+    };
+    SumHyperGroupoid { sum: HyperGroupoid::new_from_function(function, &(n+m)), first_order: n, second_order: m }
+}
+/// Returns factors `H_1` and `H_2`, both of order at least 2, such that hs is isomorphic to `H_1×H_2`, if any.
+/// The factor `H_1` has the least possible order.
+///
+/// A decomposition is a pair of partitions of H, the fibres of the two projections, such that every block of the first one
+/// meets every block of the second one in exactly one element. The fibres of a projection are the classes of a regular
+/// equivalence, so only the partitions into equal blocks that are regular are paired. The number of such partitions still
+/// grows quickly with the order, so this is meant for small orders.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// let z2 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%2), &2);
+/// let klein = z2.direct_product(&z2).product;
+/// let (first,second) = klein.direct_product_decomposition().unwrap();
+/// assert!(first.is_isomorphic_to(&z2)&&second.is_isomorphic_to(&z2));
+/// let z4 = HyperGroupoid::new_from_function(|a,b|1<<((a+b)%4), &4);
+/// assert!(!z4.is_direct_product());
+/// ```
+pub fn direct_product_decomposition(&self)->Option<(HyperGroupoid,HyperGroupoid)>{
+    let n = self.n;
+    (2..n).filter(|p|n.is_multiple_of(*p)&&p*p<=n).find_map(|p|{
+        let q = n/p;
+        // fibres of the first projection have q elements, fibres of the second one have p elements
+        let regular_partitions = |size:u64|partitions_into_blocks(n, size).into_iter()
+            .filter(|partition|relation_from_classes(partition, &n).is_regular(self))
+            .collect_vec();
+        let first_fibres = regular_partitions(q);
+        let second_fibres = if p==q {first_fibres.clone()} else {regular_partitions(p)};
+        first_fibres.iter().cartesian_product(second_fibres.iter())
+            .filter(|(first,second)|first.iter().all(|a|second.iter().all(|b|(a&b).is_power_of_two())))
+            .find_map(|(first,second)|self.factors_from_fibres(first, second))
+    })
+}
+/// Return true if hs is isomorphic to a direct product of two hypergroupoids of order at least 2.
+pub fn is_direct_product(&self)->bool{
+    self.direct_product_decomposition().is_some()
+}
+/// Builds the factors whose product would be hs along the given fibres, and returns them if the product is indeed hs.
+/// The products inside the two slices through `(0,0)` are compared first, since they fail for most fibres.
+fn factors_from_fibres(&self,first:&[u64],second:&[u64])->Option<(HyperGroupoid,HyperGroupoid)>{
+    let (p,q) = (first.len() as u64,second.len() as u64);
+    let coordinates:Vec<(u64,u64)> = (0..self.n as usize)
+        .map(|z|(
+            first.iter().position(|fibre|(fibre>>z)&1==1).unwrap() as u64,
+            second.iter().position(|fibre|(fibre>>z)&1==1).unwrap() as u64))
+        .collect();
+    let element = |a:u64,b:u64|(first[a as usize]&second[b as usize]).trailing_zeros() as usize;
+    let project = |subset:u64,first_coordinate:bool|elements_of(subset)
+        .map(|z|if first_coordinate {coordinates[z].0} else {coordinates[z].1})
+        .fold(0, |acc,x|acc|1<<x);
+    let h1 = HyperGroupoid::new_from_function(|a,c|project(self.hyper_composition[(element(a,0),element(c,0))],true), &p);
+    let h2 = HyperGroupoid::new_from_function(|b,d|project(self.hyper_composition[(element(0,b),element(0,d))],false), &q);
+    // the element (a,b) of H_1×H_2 is a*q+b, as in ProductHyperGroupoid
+    let relabel = |subset:u64|elements_of(subset).fold(0u64, |acc,z|{
+        let (e,f) = coordinates[z];
+        acc|1<<(e*q+f)
+    });
+    let agrees = |(x,y):(usize,usize)|{
+        let ((a,b),(c,d)) = (coordinates[x],coordinates[y]);
+        let expected = elements_of(h1.hyper_composition[(a as usize,c as usize)])
+            .cartesian_product(elements_of(h2.hyper_composition[(b as usize,d as usize)]).collect_vec())
+            .fold(0u64, |acc,(e,f)|acc|1<<(e as u64*q+f as u64));
+        relabel(self.hyper_composition[(x,y)])==expected
+    };
+    let first_slice = (0..p).map(|a|element(a,0)).collect_vec();
+    let second_slice = (0..q).map(|b|element(0,b)).collect_vec();
+    let on_slices = first_slice.iter().cartesian_product(first_slice.iter())
+        .chain(second_slice.iter().cartesian_product(second_slice.iter()))
+        .all(|(x,y)|agrees((*x,*y)));
+    let relabelled = on_slices&&(0..self.n as usize).cartesian_product(0..self.n as usize).all(agrees);
+    relabelled.then_some((h1,h2))
+}
+}
+/// All the partitions of `{0,1,...,n-1}` into blocks of `size` elements, as sorted vectors of subsets.
+fn partitions_into_blocks(n:u64,size:u64)->Vec<Vec<u64>>{
+    fn extend(rest:u64,size:u64,blocks:&mut Vec<u64>,partitions:&mut Vec<Vec<u64>>){
+        if rest==0 {
+            partitions.push(blocks.clone());
+            return;
+        }
+        // the least element left opens the next block
+        let least = rest.trailing_zeros() as u64;
+        for companions in elements_of(rest&!(1<<least)).combinations((size-1) as usize) {
+            let block = companions.iter().fold(1u64<<least, |acc,x|acc|1<<x);
+            blocks.push(block);
+            extend(rest&!block, size, blocks, partitions);
+            blocks.pop();
+        }
+    }
+    let mut partitions = Vec::new();
+    extend((1u64<<n)-1, size, &mut Vec::new(), &mut partitions);
+    partitions
+}
+impl HyperGroup {
+pub fn direct_product(&self,other:&HyperGroup)->HyperGroup{
+    HyperGroup::new_from_hypergroupiod(&self.0.direct_product(&other.0).product)
+}
+pub fn lexicographic_product(&self,other:&HyperGroup)->HyperGroup{
+    HyperGroup::new_from_hypergroupiod(&self.0.lexicographic_product(&other.0).product)
+}
+pub fn ordinal_sum(&self,other:&HyperGroup)->HyperGroupoid{
+    self.0.ordinal_sum(&other.0).sum
+}
+pub fn direct_product_decomposition(&self)->Option<(HyperGroup,HyperGroup)>{
+    self.0.direct_product_decomposition()
+        .map(|(first,second)|(HyperGroup::new_from_hypergroupiod(&first),HyperGroup::new_from_hypergroupiod(&second)))
+}
+}
@@ -0,0 +1,260 @@
+//!
+//! Compact hypergroupoids of small order.
+//!
+//! A [`HyperGroupoid`] carries a heap allocated `DMatrix<u64>` and the set `h`, which is expensive when millions of them
+//! are built from tags during an enumeration. For orders up to 8 every subset of `H` fits in a `u8`, so
+//! [`SmallHyperGroupoid<N>`] stores the hypercomposition table as `[[u8;N];N]` and is `Copy`.
+//!
+//! Elements are passed and returned as singletons, exactly as in [`HyperGroupoid`], so results of the two types can be compared.
+//!
+use std::collections::HashSet;
+use itertools::Itertools;
+use nalgebra::DMatrix;
+use crate::{binary_relations::relations::Relation, hs::HyperGroupoid, hypergroups::HyperStructureError, utilities::{support, U1024}};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SmallHyperGroupoid<const N: usize>{
+    pub hyper_composition:[[u8;N];N],
+}
+impl<const N: usize> SmallHyperGroupoid<N> {
+    const ORDER_FITS:() = assert!(N>=1&&N<=8, "SmallHyperGroupoid is defined for orders from 1 to 8.");
+    const H:u8 = ((1u16<<N)-1) as u8;
+/// Fallible constructor from the hypercomposition table. It returns an error if some entry is empty or is not a subset of H.
+pub fn try_new_from_table(table:&[[u8;N];N])->Result<Self,HyperStructureError>{
+    let () = Self::ORDER_FITS;
+    for (a,b) in (0..N).cartesian_product(0..N) {
+        let entry = table[a][b];
+        if entry==0 {
+            return Err(HyperStructureError::EmptyEntry { a: a as u64, b: b as u64 });
+        }
+        if entry&!Self::H!=0 {
+            return Err(HyperStructureError::EntryOutOfRange { a: a as u64, b: b as u64, entry: entry as u64 });
+        }
+    }
+    Ok(SmallHyperGroupoid { hyper_composition: *table })
+}
+pub fn new_from_table(table:&[[u8;N];N])->Self{
+    SmallHyperGroupoid::try_new_from_table(table).unwrap_or_else(|error|panic!("{error}"))
+}
+/// Fallible constructor from a tag, read as in [`HyperGroupoid::new_from_tag_u1024`]. It returns `HyperStructureError::InvalidTag`
+/// if the tag does not represent a hypergroupoid of order `N`. No allocation is performed.
+///
+/// # Example
+/// ```
+/// use hyperstruc::small_hs::SmallHyperGroupoid;
+/// use hyperstruc::hs::HyperGroupoid;
+///
+/// let cardinality = 6u64;
+/// let hs = HyperGroupoid::new_random_from_cardinality(&cardinality);
+/// let tag = hs.get_integer_tag_u1024();
+/// let small = SmallHyperGroupoid::<6>::try_new_from_tag_u1024(&tag).unwrap();
+/// assert_eq!(small.get_integer_tag_u1024(),tag);
+/// assert_eq!(HyperGroupoid::from(small),hs);
+/// assert_eq!(small.is_hypergroup(),hs.is_hypergroup());
+/// ```
+pub fn try_new_from_tag_u1024(tag:&U1024)->Result<Self,HyperStructureError>{
+    let () = Self::ORDER_FITS;
+    let invalid = ||HyperStructureError::InvalidTag { cardinality: N as u64 };
+    if tag.bits()>N.pow(3) {return Err(invalid());}
+    let words = &tag.0;
+    let mut hyper_composition = [[0u8;N];N];
+    for (i,entry) in hyper_composition.iter_mut().flatten().enumerate() {
+        //the first entry is the most significant one
+        let position = N*(N*N-1-i);
+        let (word,shift) = (position/64,position%64);
+        let mut bits = words[word]>>shift;
+        if shift+N>64 {
+            bits|=words[word+1]<<(64-shift);
+        }
+        *entry = (bits as u8)&Self::H;
+        if *entry==0 {return Err(invalid());}
+    }
+    Ok(SmallHyperGroupoid { hyper_composition })
+}
+pub fn try_new_from_tag_u128(tag:&u128)->Result<Self,HyperStructureError>{
+    SmallHyperGroupoid::try_new_from_tag_u1024(&U1024::from(*tag))
+}
+pub fn new_from_tag_u1024(tag:&U1024)->Self{
+    SmallHyperGroupoid::try_new_from_tag_u1024(tag).unwrap_or_else(|error|panic!("{error}"))
+}
+pub fn new_from_tag_u128(tag:&u128)->Self{
+    SmallHyperGroupoid::try_new_from_tag_u128(tag).unwrap_or_else(|error|panic!("{error}"))
+}
+pub fn get_integer_tag_u1024(&self)->U1024{
+    let mut words = [0u64;32];
+    for (i,entry) in self.hyper_composition.iter().flatten().enumerate() {
+        let position = N*(N*N-1-i);
+        let (word,shift) = (position/64,position%64);
+        words[word]|=(*entry as u64)<<shift;
+        if shift+N>64 {
+            words[word+1]|=(*entry as u64)>>(64-shift);
+        }
+    }
+    U1024(words)
+}
+/// Returns the tag as `u128`. It panics if `N>5`, since the tag has `N^3` bits.
+pub fn get_integer_tag(&self)->u128{
+    assert!(N<=5,"A hypergroupoid of order {} has a tag of {} bits, use get_integer_tag_u1024()",N,N.pow(3));
+    self.hyper_composition.iter().flatten().fold(0u128, |tag,entry|(tag<<N)|*entry as u128)
+}
+pub fn cardinality(&self)->u64{
+    N as u64
+}
+pub fn get_singleton(&self)->Vec<u64>{
+    (0..N).map(|x|1<<x).collect()
+}
+fn product(&self,subset_a:u8,subset_b:u8)->u8{
+    let mut product = 0u8;
+    for a in (0..N).filter(|a|(subset_a>>a)&1==1) {
+        for b in (0..N).filter(|b|(subset_b>>b)&1==1) {
+            product|=self.hyper_composition[a][b];
+        }
+    }
+    product
+}
+pub fn mul_by_representation(&self,subset_a:&u64,subset_b:&u64)->u64{
+    self.product(*subset_a as u8, *subset_b as u8) as u64
+}
+pub fn is_associative(&self)->bool{
+    (0..N).cartesian_product(0..N).cartesian_product(0..N)
+        .all(|((a,b),c)|
+            self.product(self.hyper_composition[a][b], 1<<c)==self.product(1<<a, self.hyper_composition[b][c]))
+}
+pub fn is_weak_associative(&self)->bool{
+    (0..N).cartesian_product(0..N).cartesian_product(0..N)
+        .all(|((a,b),c)|
+            self.product(self.hyper_composition[a][b], 1<<c)&self.product(1<<a, self.hyper_composition[b][c])!=0)
+}
+pub fn is_reproductive(&self)->bool{
+    (0..N).all(|x|
+        self.hyper_composition[x].iter().fold(0, |acc,entry|acc|entry)==Self::H
+        &&
+        self.hyper_composition.iter().fold(0, |acc,row|acc|row[x])==Self::H)
+}
+pub fn is_hypergroup(&self)->bool{
+    self.is_reproductive()&&self.is_associative()
+}
+pub fn is_commutative(&self)->bool{
+    (0..N).tuple_combinations().all(|(a,b)|self.hyper_composition[a][b]==self.hyper_composition[b][a])
+}
+pub fn is_left_identity(&self,e:&u64)->bool{
+    if !e.is_power_of_two() {panic!("Not an element in hypergroupoid!")}
+    let e = e.trailing_zeros() as usize;
+    (0..N).all(|x|(self.hyper_composition[e][x]>>x)&1==1)
+}
+pub fn is_right_identity(&self,e:&u64)->bool{
+    if !e.is_power_of_two() {panic!("Not an element in hypergroupoid!")}
+    let e = e.trailing_zeros() as usize;
+    (0..N).all(|x|(self.hyper_composition[x][e]>>x)&1==1)
+}
+pub fn is_identity(&self,e:&u64)->bool{
+    self.is_left_identity(e)&&self.is_right_identity(e)
+}
+pub fn collect_left_identities(&self)->Vec<u64>{
+    self.get_singleton().into_iter().filter(|e|self.is_left_identity(e)).collect()
+}
+pub fn collect_right_identities(&self)->Vec<u64>{
+    self.get_singleton().into_iter().filter(|e|self.is_right_identity(e)).collect()
+}
+pub fn collect_identities(&self)->Vec<u64>{
+    self.get_singleton().into_iter().filter(|e|self.is_identity(e)).collect()
+}
+pub fn is_left_scalar(&self,s:&u64)->bool{
+    if !s.is_power_of_two() {panic!("Not an element in hypergroupoid!")}
+    self.hyper_composition[s.trailing_zeros() as usize].iter().all(|x|x.is_power_of_two())
+}
+pub fn is_right_scalar(&self,s:&u64)->bool{
+    if !s.is_power_of_two() {panic!("Not an element in hypergroupoid!")}
+    let s = s.trailing_zeros() as usize;
+    self.hyper_composition.iter().all(|row|row[s].is_power_of_two())
+}
+pub fn is_scalar(&self,s:&u64)->bool{
+    self.is_left_scalar(s)&&self.is_right_scalar(s)
+}
+pub fn collect_scalars(&self)->Vec<u64>{
+    self.get_singleton().into_iter().filter(|s|self.is_scalar(s)).collect()
+}
+pub fn collect_scalar_identities(&self)->Vec<u64>{
+    self.collect_scalars().into_iter().filter(|s|self.is_identity(s)).collect()
+}
+/// The set `P(H)` of all finite hyperproducts, as in [`HyperGroupoid::collect_ph`].
+pub fn collect_ph(&self)->Vec<u64>{
+    // P(H) is a set of subsets of H, hence a subset of a set with at most 256 elements.
+    let mut ph = [false;256];
+    let mut a_current:Vec<u8> = (0..N).map(|x|1<<x).collect();
+    a_current.iter().for_each(|x|ph[*x as usize]=true);
+    loop {
+        let a_next:Vec<u8> = a_current.iter().cartesian_product(a_current.iter())
+            .map(|(x,y)|self.product(*x, *y))
+            .filter(|q|!ph[*q as usize])
+            .unique()
+            .collect();
+        if a_next.is_empty() {break;}
+        a_next.iter().for_each(|q|ph[*q as usize]=true);
+        a_current.extend(a_next);
+    }
+    (1..256u64).filter(|q|ph[*q as usize]).collect()
+}
+/// The relation `β = ⋃ q × q`, where `q` runs over `P(H)`. See [`HyperGroupoid::beta_relation`].
+///
+/// # Example
+/// ```
+/// use hyperstruc::small_hs::SmallHyperGroupoid;
+///
+/// let hs = SmallHyperGroupoid::<4>::new_from_table(&[[6,10,10,10],[10,10,10,10],[10,10,10,10],[10,10,10,10]]);
+/// assert_eq!(hs.collect_ph(),vec![1,2,4,6,8,10]);
+/// let expected_beta: Vec<(u64, u64)>  = vec![(0, 0), (1, 1), (1, 2), (1, 3), (2, 1), (2, 2), (3, 1), (3, 3)];
+/// assert_eq!(hs.beta_relation().rel,expected_beta);
+/// ```
+pub fn beta_relation(&self)->Relation{
+    let beta:Vec<(u64,u64)> = self.collect_ph().iter()
+        .flat_map(|q|{
+            let q = support(q, &(N as u64));
+            q.iter().cartesian_product(q.iter()).map(|(x,y)|(*x as u64,*y as u64)).collect_vec()
+        })
+        .sorted()
+        .dedup()
+        .collect();
+    let h:HashSet<u64> = (0..N as u64).collect();
+    Relation { a: h.clone(), b: h, rel: beta }
+}
+pub fn collect_beta_classes(&self)->Vec<(u64,Vec<u64>)>{
+    self.beta_relation().quotient_set()
+}
+}
+impl<const N: usize> From<SmallHyperGroupoid<N>> for HyperGroupoid {
+    fn from(hs: SmallHyperGroupoid<N>) -> Self {
+        HyperGroupoid::new_from_matrix(&DMatrix::from_fn(N, N, |a,b|hs.hyper_composition[a][b] as u64))
+    }
+}
+impl<const N: usize> TryFrom<&HyperGroupoid> for SmallHyperGroupoid<N> {
+    type Error = HyperStructureError;
+    fn try_from(hs: &HyperGroupoid) -> Result<Self, Self::Error> {
+        if hs.n!=N as u64 {
+            return Err(HyperStructureError::CardinalityMismatch { expected: N as u64, found: hs.n });
+        }
+        let mut table = [[0u8;N];N];
+        for (a,b) in (0..N).cartesian_product(0..N) {
+            table[a][b] = hs.hyper_composition[(a,b)] as u8;
+        }
+        SmallHyperGroupoid::try_new_from_table(&table)
+    }
+}
+/// Builds the [`SmallHyperGroupoid`] of order `$cardinality` with the constructor `$constructor` applied to `$tag`, and
+/// evaluates `$body` on it. The result is a `Result<_,HyperStructureError>`, which is `$fallback` when the order is larger than 8.
+macro_rules! small_hypergroupoid_dispatch {
+    ($cardinality:expr, $constructor:ident($tag:expr), |$hs:ident| $body:expr, $fallback:expr) => {
+        match $cardinality {
+            1 => $crate::small_hs::SmallHyperGroupoid::<1>::$constructor($tag).map(|$hs|$body),
+            2 => $crate::small_hs::SmallHyperGroupoid::<2>::$constructor($tag).map(|$hs|$body),
+            3 => $crate::small_hs::SmallHyperGroupoid::<3>::$constructor($tag).map(|$hs|$body),
+            4 => $crate::small_hs::SmallHyperGroupoid::<4>::$constructor($tag).map(|$hs|$body),
+            5 => $crate::small_hs::SmallHyperGroupoid::<5>::$constructor($tag).map(|$hs|$body),
+            6 => $crate::small_hs::SmallHyperGroupoid::<6>::$constructor($tag).map(|$hs|$body),
+            7 => $crate::small_hs::SmallHyperGroupoid::<7>::$constructor($tag).map(|$hs|$body),
+            8 => $crate::small_hs::SmallHyperGroupoid::<8>::$constructor($tag).map(|$hs|$body),
+            _ => $fallback,
+        }
+    };
+}
+pub(crate) use small_hypergroupoid_dispatch;
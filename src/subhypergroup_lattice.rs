@@ -0,0 +1,140 @@
+//!
+//! Generated subhypergroups and the poset of subhypergroups of a hypergroup.
+//!
+//! Unlike subgroups, the intersection of two subhypergroups need not be a subhypergroup: in `xy = {x,y}` every nonempty
+//! subset is a subhypergroup and `{0}`, `{1}` are disjoint. Therefore subhypergroups are generated with respect to
+//! closed subhypergroups, i.e., subhypergroups `K` such that `x\y` and `y/x` are contained in `K` for all `x,y` in `K`.
+//! Closed subhypergroups are stable under intersection and, in a group, every subgroup is closed.
+//!
+//! Subhypergroups ordered by inclusion form a poset, whose meets and joins may not exist. They are computed on demand
+//! from the members of a [`SubHypergroupLattice`].
+//!
+use itertools::Itertools;
+use crate::hypergroups::HyperGroup;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubHypergroupInfo {
+    pub subset:u64,
+    /// See [`HyperGroup::subhypergroup_is_closed`].
+    pub closed:bool,
+    /// See [`HyperGroup::subhypergroup_is_reflexive`].
+    pub reflexive:bool,
+    /// See [`HyperGroup::subhypergroup_is_normal`].
+    pub normal:bool,
+    /// See [`HyperGroup::subhypergroup_is_invertible`].
+    pub invertible:bool,
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubHypergroupLattice {
+    /// All subhypergroups, H included, sorted by cardinality and then by representation.
+    pub members:Vec<SubHypergroupInfo>,
+    /// Pairs `(i,j)` of indices of `members` such that `members[j]` covers `members[i]`.
+    pub hasse_edges:Vec<(usize,usize)>,
+}
+impl SubHypergroupLattice {
+    pub fn subsets(&self)->Vec<u64>{
+        self.members.iter().map(|member|member.subset).collect()
+    }
+    /// Returns the index of the subhypergroup `k` in `members`, if `k` is a subhypergroup.
+    pub fn position(&self,k:&u64)->Option<usize>{
+        self.members.iter().position(|member|member.subset==*k)
+    }
+    /// Returns the greatest subhypergroup contained in both `a` and `b`, if it exists.
+    ///
+    /// # Example
+    /// ```
+    /// use hyperstruc::hypergroups::HyperGroup;
+    ///
+    /// let hg = HyperGroup::new_from_function(|a,b|1<<a|1<<b, &3).unwrap();
+    /// let lattice = hg.subhypergroup_lattice();
+    /// assert_eq!(lattice.meet(&0b011,&0b110),Some(0b010));
+    /// assert_eq!(lattice.meet(&0b001,&0b010),None);
+    /// ```
+    pub fn meet(&self,a:&u64,b:&u64)->Option<u64>{
+        let lower_bounds = self.subsets().into_iter().filter(|k|k&!a==0&&k&!b==0).collect_vec();
+        lower_bounds.iter().find(|k|lower_bounds.iter().all(|l|l&!*k==0)).copied()
+    }
+    /// Returns the least subhypergroup containing both `a` and `b`, if it exists.
+    ///
+    /// # Example
+    /// ```
+    /// use hyperstruc::hypergroups::HyperGroup;
+    ///
+    /// let hg = HyperGroup::new_from_function(|a,b|1<<a|1<<b, &3).unwrap();
+    /// let lattice = hg.subhypergroup_lattice();
+    /// assert_eq!(lattice.join(&0b001,&0b010),Some(0b011));
+    /// ```
+    pub fn join(&self,a:&u64,b:&u64)->Option<u64>{
+        let upper_bounds = self.subsets().into_iter().filter(|k|(a|b)&!k==0).collect_vec();
+        upper_bounds.iter().find(|k|upper_bounds.iter().all(|u|*k&!u==0)).copied()
+    }
+    /// Return true if every pair of subhypergroups has a meet and a join.
+    pub fn is_lattice(&self)->bool{
+        let subsets = self.subsets();
+        subsets.iter().tuple_combinations()
+            .all(|(a,b)|self.meet(a, b).is_some()&&self.join(a, b).is_some())
+    }
+}
+impl HyperGroup {
+/// Returns the smallest closed subhypergroup containing the subset represented by `mask`, obtained by adding products
+/// and left and right quotients until the subset is stable. The empty set is mapped to itself.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// let cardinality = 6u64;
+/// let z6 = HyperGroup::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality).unwrap();
+/// assert_eq!(z6.generated_subhypergroup(&0b000100),0b010101);
+/// assert_eq!(z6.generated_subhypergroup(&0b001100),0b111111);
+/// // {0} is a subhypergroup of xy = {x,y}, but 0\0 = H.
+/// let hg = HyperGroup::new_from_function(|a,b|1<<a|1<<b, &3).unwrap();
+/// assert_eq!(hg.generated_subhypergroup(&0b001),0b111);
+/// ```
+pub fn generated_subhypergroup(&self,mask:&u64)->u64{
+    assert!(*mask>>self.cardinality()==0,"{} does not represent a subset of H",mask);
+    let mut subset = *mask;
+    loop {
+        let next = subset
+            |self.mul_by_representation(&subset, &subset)
+            |self.left_division(&subset, &subset)
+            |self.right_division(&subset, &subset);
+        if next==subset {return subset;}
+        subset = next;
+    }
+}
+/// Returns all the subhypergroups, H included, with their flags and the Hasse diagram of the inclusion order.
+///
+/// # Example
+/// ```
+/// use hyperstruc::hypergroups::HyperGroup;
+///
+/// let cardinality = 4u64;
+/// let z4 = HyperGroup::new_from_function(|a,b|1<<((a+b)%cardinality), &cardinality).unwrap();
+/// let lattice = z4.subhypergroup_lattice();
+/// assert_eq!(lattice.subsets(),vec![0b0001,0b0101,0b1111]);
+/// assert_eq!(lattice.hasse_edges,vec![(0,1),(1,2)]);
+/// assert!(lattice.members.iter().all(|member|member.closed&&member.normal));
+/// assert!(lattice.is_lattice());
+/// ```
+pub fn subhypergroup_lattice(&self)->SubHypergroupLattice{
+    let h = (1u64<<self.cardinality())-1;
+    let members = self.collect_proper_subhypergroups().into_iter()
+        .chain(std::iter::once(h))
+        .sorted_by_key(|k|(k.count_ones(),*k))
+        .map(|subset|SubHypergroupInfo {
+            subset,
+            closed: self.subhypergroup_is_closed(&subset),
+            reflexive: self.subhypergroup_is_reflexive(&subset),
+            normal: self.subhypergroup_is_normal(&subset),
+            invertible: self.subhypergroup_is_invertible(&subset),
+        })
+        .collect_vec();
+    let contained = |a:u64,b:u64|a!=b&&a&!b==0;
+    let hasse_edges = (0..members.len()).cartesian_product(0..members.len())
+        .filter(|(i,j)|contained(members[*i].subset, members[*j].subset))
+        .filter(|(i,j)|!members.iter().any(|m|contained(members[*i].subset, m.subset)&&contained(m.subset, members[*j].subset)))
+        .collect_vec();
+    SubHypergroupLattice { members, hasse_edges }
+}
+}
@@ -2,7 +2,7 @@ use core::panic;
 use std::fmt::Display;
 extern crate nalgebra as na;
 use nalgebra::DMatrix;
-use crate::{hs::HyperGroupoid, utilities::{get_subset, representing_hypergroupoid, vec_to_set}};
+use crate::{hs::HyperGroupoid, hypergroups::HyperStructureError, utilities::{get_subset, representing_hypergroupoid, vec_to_set}};
 #[derive(Debug, Clone,PartialEq)]
 pub struct UnitalMagma{
     pub h:HyperGroupoid,
@@ -10,6 +10,29 @@ pub struct UnitalMagma{
 }
 
 impl UnitalMagma {
+    /// Fallible version of [`UnitalMagma::new_from_tag_u128`]. It returns an error if the tag does not represent a hypergroupoid,
+    /// or if the hypergroupoid has not exactly one scalar identity.
+    /// 
+    /// # Example
+    /// ```
+    /// use hyperstruc::unital_magma::UnitalMagma;
+    /// use hyperstruc::hypergroups::HyperStructureError;
+    /// 
+    /// let cardinality = 2u64;
+    /// assert!(UnitalMagma::try_new_from_tag_u128(&105u128, &cardinality).is_ok());
+    /// match UnitalMagma::try_new_from_tag_u128(&255u128, &cardinality) {
+    ///     Err(HyperStructureError::NoUniqueIdentity { identities }) => assert_eq!(identities,0),
+    ///     _ => panic!("The total hypergroup has no scalar identity!")
+    /// }
+    /// ```
+    pub fn try_new_from_tag_u128(tag:&u128,cardinality:&u64)->Result<Self,HyperStructureError> {
+        let h=HyperGroupoid::try_new_from_tag_u128(tag, cardinality)?;
+        let e = h.collect_scalar_identities();
+        if e.len()!=1 {
+            return Err(HyperStructureError::NoUniqueIdentity { identities: e.len() });
+        }
+        Ok(UnitalMagma { h, identity: e[0] })
+    }
     pub fn new_from_tag_u128(tag:&u128,cardinality:&u64)->Self {
         assert!(representing_hypergroupoid(&mut tag.clone(), &(*cardinality)),"Tag doesn't represent a hypergroupoid!");
         let h=HyperGroupoid::new_from_tag_u128(&tag, &cardinality);
@@ -4,6 +4,7 @@ use std::fmt::Debug;
 use itertools::Itertools;
 use nalgebra::DMatrix;
 use permutation::Permutation;
+use crate::hypergroups::HyperStructureError;
 
 use uint::construct_uint;
 construct_uint!{
@@ -415,6 +416,23 @@ pub fn collect_n_digits_u1024(width: &u64,m_representation_hypergroupoid:&U1024)
             .collect();
     out
 }
+/// Fallible version of [`from_tag_u1024_to_vec`]. It returns `HyperStructureError::InvalidTag` if `tag` does not represent
+/// a hypergroupoid of cardinality `n`, i.e., if some group of `n` bits is zero or the tag has the wrong length.
+/// 
+/// # Example
+/// ```
+/// use hyperstruc::utilities::{try_from_tag_u1024_to_vec, U1024};
+/// 
+/// let cardinality = 2u64;
+/// assert_eq!(try_from_tag_u1024_to_vec(&U1024::from(185u64), &cardinality).unwrap(),vec![vec![1,0],vec![1,1],vec![1,0],vec![0,1]]);
+/// assert!(try_from_tag_u1024_to_vec(&U1024::from(184u64), &cardinality).is_err());
+/// ```
+pub fn try_from_tag_u1024_to_vec(tag:&U1024,n:&u64)->Result<Vec<Vec<u64>>,HyperStructureError>{
+    if n.pow(3)>1024||!representing_hypergroupoid_u1024(tag, n) {
+        return Err(HyperStructureError::InvalidTag { cardinality: *n });
+    }
+    Ok(from_tag_u1024_to_vec(tag, n))
+}
 pub fn from_tag_u1024_to_vec(tag:&U1024,n:&u64) ->Vec<Vec<u64>>{
     let mut tag = tag.clone();
     let mut tag_vec:Vec<Vec<u64>>=Vec::new();